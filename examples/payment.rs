@@ -1,7 +1,10 @@
 use tbot::{
     markup::{inline_code, markdown_v2},
     prelude::*,
-    types::{parameters::Photo, shipping, LabeledPrice},
+    types::{
+        parameters::{Invoice, Photo},
+        shipping, LabeledPrice,
+    },
     Bot,
 };
 
@@ -24,19 +27,25 @@ async fn main() {
     bot.start(move |context| async move {
         let call_result = if context.text.value == START_PARAMETER {
             let price: &[_] = &[LabeledPrice::new(TITLE, 1_00)];
-            let mut invoice = context.send_invoice(
-                TITLE,
-                DESCRIPTION,
-                PAYLOAD,
-                provider_token,
-                START_PARAMETER,
-                CURRENCY,
-                price,
-            );
             let photo = Photo::new(
                 "https://www.rustacean.net/assets/rustacean-flat-happy.png",
             );
-            invoice = invoice.photo(photo).is_flexible(true);
+            let invoice =
+                Invoice::new(TITLE, DESCRIPTION, PAYLOAD, CURRENCY, price)
+                    .provider_token(provider_token)
+                    .max_tip_amount(1_00)
+                    .suggested_tip_amounts(&[10, 25, 50][..])
+                    .photo(photo)
+                    .is_flexible(true);
+
+            if let Err(err) = invoice.validate() {
+                dbg!(err);
+                return;
+            }
+
+            let invoice = context
+                .send_invoice_with(invoice)
+                .start_parameter(START_PARAMETER);
 
             invoice.call().await
         } else {
@@ -25,11 +25,18 @@ use crate::{
         keyboard::inline,
         message,
         parameters::{
-            poll, BotCommand, CallbackAction, ImplicitChatId, Text, UpdateKind,
+            poll, BotCommand, CallbackAction, ImplicitChatId, Invoice, Text,
+            UpdateKind,
         },
         passport, pre_checkout_query, shipping, user, InlineMessageId,
         LabeledPrice,
     },
+    util::{
+        admins::{self, Registry},
+        conversation::Waiters,
+        directory::Directory,
+        long_message::SendLongMessage,
+    },
 };
 use std::{borrow::Cow, net::IpAddr, num::NonZeroU32, sync::Arc};
 
@@ -156,6 +163,41 @@ impl Bot {
         download_file(&self.inner, file).await
     }
 
+    pub(crate) fn waiters(&self) -> &Waiters {
+        self.inner.waiters()
+    }
+
+    /// Returns the [registry] of chat administrators if it was enabled with
+    /// [`bot::Builder::admin_registry`].
+    ///
+    /// [registry]: ../util/admins/index.html
+    /// [`bot::Builder::admin_registry`]: ./struct.Builder.html#method.admin_registry
+    #[must_use]
+    pub fn admin_registry(&self) -> Option<&Registry> {
+        self.inner.admin_registry()
+    }
+
+    /// Gets the administrators of a chat. If the [registry] is enabled,
+    /// they're taken from the cache when possible.
+    ///
+    /// [registry]: ../util/admins/index.html
+    pub async fn cached_chat_administrators(
+        &self,
+        chat_id: chat::Id,
+    ) -> Result<Arc<[chat::Member]>, errors::MethodCall> {
+        admins::administrators(self, chat_id).await
+    }
+
+    /// Returns the [directory] of observed users and chats if it was enabled
+    /// with [`bot::Builder::directory`].
+    ///
+    /// [directory]: ../util/directory/index.html
+    /// [`bot::Builder::directory`]: ./struct.Builder.html#method.directory
+    #[must_use]
+    pub fn directory(&self) -> Option<&Directory> {
+        self.inner.directory()
+    }
+
     /// Constructs an `EventLoop`.
     pub fn event_loop(self) -> EventLoop {
         EventLoop::new(self)
@@ -174,10 +216,10 @@ impl Bot {
         &'a self,
         user_id: user::Id,
         name: impl Into<Cow<'a, str>>,
-        png_sticker: impl Into<StickerForStickerSet<'a>>,
+        sticker: impl Into<StickerForStickerSet<'a>>,
         emojis: impl Into<Cow<'a, str>>,
     ) -> AddStickerToSet<'a> {
-        AddStickerToSet::new(&self.inner, user_id, name, png_sticker, emojis)
+        AddStickerToSet::new(&self.inner, user_id, name, sticker, emojis)
     }
 
     pub(crate) fn answer_callback_query<'a>(
@@ -225,13 +267,21 @@ impl Bot {
         CopyMessage::new(&self.inner, chat_id, from_chat_id, message_id)
     }
 
+    /// Creates a link for an invoice.
+    pub fn create_invoice_link<'a>(
+        &'a self,
+        invoice: Invoice<'a>,
+    ) -> CreateInvoiceLink<'a> {
+        CreateInvoiceLink::new(&self.inner, invoice)
+    }
+
     /// Creates a new sticker set.
     pub fn create_new_sticker_set<'a>(
         &'a self,
         user_id: user::Id,
         name: impl Into<Cow<'a, str>>,
         title: impl Into<Cow<'a, str>>,
-        png_sticker: impl Into<StickerForStickerSet<'a>>,
+        sticker: impl Into<StickerForStickerSet<'a>>,
         emojis: impl Into<Cow<'a, str>>,
     ) -> CreateNewStickerSet<'a> {
         CreateNewStickerSet::new(
@@ -239,7 +289,7 @@ impl Bot {
             user_id,
             name,
             title,
-            png_sticker,
+            sticker,
             emojis,
         )
     }
@@ -269,6 +319,11 @@ impl Bot {
         DeleteMessage::new(&self.inner, chat_id, message_id)
     }
 
+    /// Deletes the list of the bot's commands.
+    pub fn delete_my_commands(&self) -> DeleteMyCommands<'_> {
+        DeleteMyCommands::new(&self.inner)
+    }
+
     /// Deletes a sticker from a sticker set.
     pub fn delete_sticker_from_set<'a>(
         &'a self,
@@ -633,6 +688,17 @@ impl Bot {
         )
     }
 
+    /// Sends an invoice built with [`Invoice`].
+    ///
+    /// [`Invoice`]: ./types/parameters/struct.Invoice.html
+    pub fn send_invoice_with<'a>(
+        &'a self,
+        chat_id: impl Into<chat::Id>,
+        invoice: Invoice<'a>,
+    ) -> SendInvoice<'a> {
+        SendInvoice::with_invoice(&self.inner, chat_id, invoice)
+    }
+
     /// Sends a location.
     pub fn send_location<'a>(
         &'a self,
@@ -642,6 +708,18 @@ impl Bot {
         SendLocation::new(&self.inner, chat_id, position)
     }
 
+    /// Sends a text of any length, splitting it into several messages
+    /// if needed. See [`util::long_message`] for details.
+    ///
+    /// [`util::long_message`]: ../util/long_message/index.html
+    pub fn send_long_message<'a>(
+        &'a self,
+        chat_id: impl ImplicitChatId<'a>,
+        text: impl Into<Text<'a>>,
+    ) -> SendLongMessage<'a> {
+        SendLongMessage::new(&self.inner, chat_id, text)
+    }
+
     /// Sends an album.
     pub fn send_media_group<'a>(
         &'a self,
@@ -5,8 +5,9 @@ use crate::{
     methods::{Close, DeleteWebhook, LogOut},
     proxy::Proxy,
     token::Token,
+    util::{admins::Registry, directory::Directory},
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 /// A builder for a [`Bot`] with advanced configuration.
 ///
@@ -38,6 +39,24 @@ impl Builder {
         self
     }
 
+    /// Enables the [registry] of chat administrators, caching them
+    /// for `ttl`.
+    ///
+    /// [registry]: ../util/admins/index.html
+    pub fn admin_registry(mut self, ttl: Duration) -> Self {
+        self.0.set_admin_registry(Registry::new(ttl));
+        self
+    }
+
+    /// Enables the [directory] of users and chats the bot observes.
+    /// The directory may be pre-filled, e.g. loaded from a storage.
+    ///
+    /// [directory]: ../util/directory/index.html
+    pub fn directory(mut self, directory: Directory) -> Self {
+        self.0.set_directory(directory);
+        self
+    }
+
     // I don't think marking `localhost` as a link is a good idea
     #[allow(clippy::doc_markdown)]
     /// Configures the URI where the bot will make requests.
@@ -1,4 +1,8 @@
-use crate::{connectors::Client, token::Token};
+use crate::{
+    connectors::Client,
+    token::Token,
+    util::{admins::Registry, conversation::Waiters, directory::Directory},
+};
 use hyper::Uri;
 
 const CLOUD_BOT_API: &str = "https://api.telegram.org/";
@@ -8,6 +12,9 @@ pub struct InnerBot {
     token: Token,
     client: Client,
     uri: Uri,
+    waiters: Waiters,
+    admin_registry: Option<Registry>,
+    directory: Option<Directory>,
 }
 
 impl InnerBot {
@@ -16,6 +23,9 @@ impl InnerBot {
             token,
             client,
             uri: Uri::from_static(CLOUD_BOT_API),
+            waiters: Waiters::default(),
+            admin_registry: None,
+            directory: None,
         }
     }
 
@@ -27,6 +37,14 @@ impl InnerBot {
         self.uri = uri;
     }
 
+    pub fn set_admin_registry(&mut self, registry: Registry) {
+        self.admin_registry = Some(registry);
+    }
+
+    pub fn set_directory(&mut self, directory: Directory) {
+        self.directory = Some(directory);
+    }
+
     pub fn token(&self) -> &str {
         &self.token.0
     }
@@ -38,4 +56,16 @@ impl InnerBot {
     pub fn uri(&self) -> Uri {
         self.uri.clone()
     }
+
+    pub const fn waiters(&self) -> &Waiters {
+        &self.waiters
+    }
+
+    pub const fn admin_registry(&self) -> Option<&Registry> {
+        self.admin_registry.as_ref()
+    }
+
+    pub const fn directory(&self) -> Option<&Directory> {
+        self.directory.as_ref()
+    }
 }
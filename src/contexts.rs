@@ -62,10 +62,13 @@ mod macros;
 
 mod animation;
 mod audio;
+mod auto_delete_timer;
 mod chosen_inline;
+mod closed_forum_topic;
 mod command;
 mod connected_website;
 mod contact;
+mod created_forum_topic;
 mod created_group;
 mod data_callback;
 mod deleted_chat_photo;
@@ -79,6 +82,7 @@ mod edited_location;
 mod edited_photo;
 mod edited_text;
 mod edited_video;
+mod ended_voice_chat;
 mod game;
 mod game_callback;
 mod inline;
@@ -96,7 +100,11 @@ mod pinned_message;
 mod poll;
 mod poll_answer;
 mod pre_checkout;
+mod proximity_alert;
+mod reopened_forum_topic;
+mod scheduled_voice_chat;
 mod shipping;
+mod started_voice_chat;
 mod sticker;
 mod text;
 mod unhandled;
@@ -106,16 +114,20 @@ mod venue;
 mod video;
 mod video_note;
 mod voice;
+mod voice_chat_invitation;
 
 pub mod fields;
 pub mod methods;
 
 pub use animation::Animation;
 pub use audio::Audio;
+pub use auto_delete_timer::AutoDeleteTimer;
 pub use chosen_inline::ChosenInline;
+pub use closed_forum_topic::ClosedForumTopic;
 pub use command::Command;
 pub use connected_website::ConnectedWebsite;
 pub use contact::Contact;
+pub use created_forum_topic::CreatedForumTopic;
 pub use created_group::CreatedGroup;
 pub use data_callback::{InlineDataCallback, MessageDataCallback};
 pub use deleted_chat_photo::DeletedChatPhoto;
@@ -129,6 +141,7 @@ pub use edited_location::EditedLocation;
 pub use edited_photo::EditedPhoto;
 pub use edited_text::EditedText;
 pub use edited_video::EditedVideo;
+pub use ended_voice_chat::EndedVoiceChat;
 pub use game::Game;
 pub use game_callback::{InlineGameCallback, MessageGameCallback};
 pub use inline::Inline;
@@ -146,7 +159,11 @@ pub use pinned_message::PinnedMessage;
 pub use poll::Poll;
 pub use poll_answer::PollAnswer;
 pub use pre_checkout::PreCheckout;
+pub use proximity_alert::ProximityAlert;
+pub use reopened_forum_topic::ReopenedForumTopic;
+pub use scheduled_voice_chat::ScheduledVoiceChat;
 pub use shipping::Shipping;
+pub use started_voice_chat::StartedVoiceChat;
 pub use sticker::Sticker;
 pub use text::Text;
 pub use unhandled::Unhandled;
@@ -156,3 +173,4 @@ pub use venue::Venue;
 pub use video::Video;
 pub use video_note::VideoNote;
 pub use voice::Voice;
+pub use voice_chat_invitation::VoiceChatInvitation;
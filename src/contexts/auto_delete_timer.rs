@@ -0,0 +1,12 @@
+message_base! {
+    struct AutoDeleteTimer {
+        /// The new auto-delete time in seconds, or `0` if it was disabled.
+        auto_delete_time: u32,
+    } -> EventLoop::auto_delete_timer
+
+    fn new(auto_delete_time: u32,) -> Self {
+        Self {
+            auto_delete_time: auto_delete_time,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+message_base! {
+    struct ClosedForumTopic { } -> EventLoop::closed_forum_topic
+
+    fn new() -> Self {
+        Self {}
+    }
+}
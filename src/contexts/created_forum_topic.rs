@@ -0,0 +1,14 @@
+use crate::types::forum_topic;
+
+message_base! {
+    struct CreatedForumTopic {
+        /// Information about the created topic.
+        topic: forum_topic::Created,
+    } -> EventLoop::created_forum_topic
+
+    fn new(topic: forum_topic::Created,) -> Self {
+        Self {
+            topic: topic,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::types::voice_chat;
+
+message_base! {
+    struct EndedVoiceChat {
+        /// Information about the ended voice chat.
+        voice_chat: voice_chat::Ended,
+    } -> EventLoop::ended_voice_chat
+
+    fn new(voice_chat: voice_chat::Ended,) -> Self {
+        Self {
+            voice_chat: voice_chat,
+        }
+    }
+}
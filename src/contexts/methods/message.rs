@@ -2,6 +2,7 @@
 #![allow(clippy::wildcard_imports)]
 use crate::{
     contexts::fields,
+    errors,
     methods::*,
     types::{
         chat,
@@ -11,10 +12,12 @@ use crate::{
         },
         keyboard::inline,
         message,
-        parameters::{poll, ImplicitChatId, Text},
+        parameters::{poll, ImplicitChatId, Invoice, Text},
         user, LabeledPrice,
     },
+    util::{admins, long_message::SendLongMessage},
 };
+use futures::future::BoxFuture;
 use std::borrow::Cow;
 
 /// Provides methods appliable to all messages.
@@ -161,6 +164,23 @@ pub trait Message: fields::Message {
         )
     }
 
+    /// Checks if the author of this message is an admin of this chat.
+    /// Messages sent on behalf of the chat itself, e.g. by anonymous admins,
+    /// are considered to be sent by an admin. If the [registry] is enabled,
+    /// administrators are taken from the cache when possible.
+    ///
+    /// [registry]: ../../util/admins/index.html
+    fn is_sender_admin(&self) -> BoxFuture<'_, Result<bool, errors::MethodCall>>
+    where
+        Self: Sync,
+    {
+        Box::pin(admins::is_sender_admin(
+            self.bot(),
+            self.chat(),
+            self.from(),
+        ))
+    }
+
     /// Kicks a member of this chat.
     fn kick_chat_member(&self, user_id: user::Id) -> KickChatMember<'_> {
         self.bot().kick_chat_member(self.chat().id, user_id)
@@ -331,6 +351,27 @@ pub trait Message: fields::Message {
         .in_reply_to(self.message_id())
     }
 
+    /// Sends an invoice built with [`Invoice`] to this chat.
+    ///
+    /// [`Invoice`]: ../../types/parameters/struct.Invoice.html
+    fn send_invoice_with<'a>(
+        &'a self,
+        invoice: Invoice<'a>,
+    ) -> SendInvoice<'a> {
+        self.bot().send_invoice_with(self.chat().id, invoice)
+    }
+
+    /// Sends an invoice built with [`Invoice`] in reply to this message.
+    ///
+    /// [`Invoice`]: ../../types/parameters/struct.Invoice.html
+    fn send_invoice_with_in_reply<'a>(
+        &'a self,
+        invoice: Invoice<'a>,
+    ) -> SendInvoice<'a> {
+        self.send_invoice_with(invoice)
+            .in_reply_to(self.message_id())
+    }
+
     /// Sends a location to this chat.
     fn send_location(&self, location: (f64, f64)) -> SendLocation {
         self.bot().send_location(self.chat().id, location)
@@ -341,6 +382,24 @@ pub trait Message: fields::Message {
         self.send_location(location).in_reply_to(self.message_id())
     }
 
+    /// Sends a text of any length to this chat, splitting it into several
+    /// messages if needed.
+    fn send_long_message<'a>(
+        &'a self,
+        text: impl Into<Text<'a>>,
+    ) -> SendLongMessage<'a> {
+        self.bot().send_long_message(self.chat().id, text)
+    }
+
+    /// Sends a text of any length in reply to this message, splitting it
+    /// into several messages if needed.
+    fn send_long_message_in_reply<'a>(
+        &'a self,
+        text: impl Into<Text<'a>>,
+    ) -> SendLongMessage<'a> {
+        self.send_long_message(text).in_reply_to(self.message_id())
+    }
+
     /// Sends an album to this chat.
     fn send_media_group<'a>(
         &'a self,
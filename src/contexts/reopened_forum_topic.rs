@@ -0,0 +1,7 @@
+message_base! {
+    struct ReopenedForumTopic { } -> EventLoop::reopened_forum_topic
+
+    fn new() -> Self {
+        Self {}
+    }
+}
@@ -0,0 +1,14 @@
+use crate::types::voice_chat;
+
+message_base! {
+    struct ScheduledVoiceChat {
+        /// Information about the scheduled voice chat.
+        voice_chat: voice_chat::Scheduled,
+    } -> EventLoop::scheduled_voice_chat
+
+    fn new(voice_chat: voice_chat::Scheduled,) -> Self {
+        Self {
+            voice_chat: voice_chat,
+        }
+    }
+}
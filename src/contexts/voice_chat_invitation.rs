@@ -0,0 +1,14 @@
+use crate::types::User;
+
+message_base! {
+    struct VoiceChatInvitation {
+        /// The users who were invited to the voice chat.
+        users: Vec<User>,
+    } -> EventLoop::voice_chat_invitation
+
+    fn new(users: Vec<User>,) -> Self {
+        Self {
+            users: users,
+        }
+    }
+}
@@ -1,13 +1,34 @@
 //! Types representing errors.
 
+mod broadcast;
+mod callback_data;
+mod conversation;
 mod download;
 mod http_webhook;
 mod https_webhook;
+mod invoice;
 mod method_call;
+mod parse_duration;
+mod parse_entities;
+#[cfg(feature = "passport")]
+mod passport;
+mod payments;
 mod polling;
 mod polling_setup;
+mod send_long_message;
+mod stateful_polling;
+mod sticker_set;
+mod storage;
 
 pub use {
-    download::Download, http_webhook::HttpWebhook, https_webhook::HttpsWebhook,
-    method_call::MethodCall, polling::Polling, polling_setup::PollingSetup,
+    broadcast::Broadcast, callback_data::CallbackData,
+    conversation::Conversation, download::Download, http_webhook::HttpWebhook,
+    https_webhook::HttpsWebhook, invoice::Invoice, method_call::MethodCall,
+    parse_duration::ParseDuration, parse_entities::ParseEntities,
+    payments::Payments, polling::Polling, polling_setup::PollingSetup,
+    send_long_message::SendLongMessage, stateful_polling::StatefulPolling,
+    sticker_set::StickerSet, storage::Storage,
 };
+
+#[cfg(feature = "passport")]
+pub use passport::Passport;
@@ -0,0 +1,46 @@
+use super::{MethodCall, Storage};
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may stop a broadcast. Errors specific
+/// to a recipient don't stop a broadcast and are reported instead.
+#[derive(Debug, Is)]
+pub enum Broadcast {
+    /// A method call failed for a reason unrelated to the recipient,
+    /// e.g. a network error or a message Telegram refuses to send.
+    MethodCall(MethodCall),
+    /// The checkpoint could not be loaded or saved.
+    Storage(Storage),
+}
+
+impl Display for Broadcast {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MethodCall(error) => {
+                write!(formatter, "The broadcast was interrupted: {}", error)
+            }
+            Self::Storage(error) => write!(
+                formatter,
+                "The broadcast's checkpoint could not be loaded or saved: {}",
+                error,
+            ),
+        }
+    }
+}
+
+impl Error for Broadcast {}
+
+impl From<MethodCall> for Broadcast {
+    fn from(error: MethodCall) -> Self {
+        Self::MethodCall(error)
+    }
+}
+
+impl From<Storage> for Broadcast {
+    fn from(error: Storage) -> Self {
+        Self::Storage(error)
+    }
+}
@@ -0,0 +1,69 @@
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while encoding or decoding
+/// callback data.
+#[derive(Debug, Is)]
+pub enum CallbackData {
+    /// The value could not be serialized or deserialized.
+    Serialization(serde_json::Error),
+    /// The encoded data exceeds 64 bytes even though the payload was stored
+    /// on the server. This happens if the type's tag is too long.
+    TooLong {
+        /// The length of the encoded data.
+        length: usize,
+    },
+    /// The data doesn't look like data encoded by a codec.
+    Malformed,
+    /// The data was encoded for another type.
+    UnexpectedTag,
+    /// The codec expects signed data, but the data isn't signed.
+    MissingSignature,
+    /// The data's signature is invalid, i.e. the data was forged.
+    InvalidSignature,
+    /// The payload was stored on the server, but it isn't available anymore,
+    /// e.g. because the bot was restarted or the payload was evicted.
+    Expired,
+}
+
+impl Display for CallbackData {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Serialization(error) => write!(
+                formatter,
+                "Callback data could not be (de)serialized: {}",
+                error,
+            ),
+            Self::TooLong { length } => write!(
+                formatter,
+                "Callback data is {} bytes long, while the limit is 64 bytes.",
+                length,
+            ),
+            Self::Malformed => write!(formatter, "Callback data is malformed."),
+            Self::UnexpectedTag => {
+                write!(formatter, "Callback data was encoded for another type.")
+            }
+            Self::MissingSignature => {
+                write!(formatter, "Callback data is not signed.")
+            }
+            Self::InvalidSignature => {
+                write!(formatter, "Callback data has an invalid signature.")
+            }
+            Self::Expired => write!(
+                formatter,
+                "Callback data refers to a payload which is no longer stored.",
+            ),
+        }
+    }
+}
+
+impl Error for CallbackData {}
+
+impl From<serde_json::Error> for CallbackData {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error)
+    }
+}
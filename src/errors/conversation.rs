@@ -0,0 +1,51 @@
+use super::MethodCall;
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+use tokio::time::error::Elapsed;
+
+/// Represents possible errors which may occur while waiting for a message
+/// from a user.
+#[derive(Debug, Is)]
+pub enum Conversation {
+    /// Sending the question to the user resulted in an error.
+    MethodCall(MethodCall),
+    /// The user didn't send a matching message in time.
+    Timeout(Elapsed),
+    /// Waiting for the message was cancelled.
+    Cancelled,
+}
+
+impl Display for Conversation {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MethodCall(error) => write!(
+                formatter,
+                "The question could not be sent to the user: {}",
+                error,
+            ),
+            Self::Timeout(timeout) => {
+                write!(formatter, "The user didn't reply in time: {}", timeout)
+            }
+            Self::Cancelled => {
+                write!(formatter, "Waiting for a reply was cancelled.")
+            }
+        }
+    }
+}
+
+impl Error for Conversation {}
+
+impl From<MethodCall> for Conversation {
+    fn from(error: MethodCall) -> Self {
+        Self::MethodCall(error)
+    }
+}
+
+impl From<Elapsed> for Conversation {
+    fn from(error: Elapsed) -> Self {
+        Self::Timeout(error)
+    }
+}
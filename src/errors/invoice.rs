@@ -0,0 +1,79 @@
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while validating an invoice
+/// or parsing a price.
+#[derive(Debug, PartialEq, Eq, Clone, Is)]
+pub enum Invoice {
+    /// Telegram doesn't support this currency.
+    UnknownCurrency(String),
+    /// The invoice has no prices.
+    NoPrices,
+    /// The total price doesn't fit into an integer.
+    TotalOverflow,
+    /// The amount could not be parsed.
+    InvalidAmount(String),
+    /// The amount has more fractional digits than the currency allows.
+    TooManyFractionDigits {
+        /// The number of fractional digits the currency allows.
+        exponent: u32,
+    },
+    /// More than four suggested tip amounts were given.
+    TooManySuggestedTips,
+    /// Suggested tip amounts were given without the maximum tip amount.
+    MissingMaxTipAmount,
+    /// Suggested tip amounts aren't positive and strictly increasing.
+    UnorderedSuggestedTips,
+    /// A suggested tip amount exceeds the maximum tip amount.
+    SuggestedTipTooLarge {
+        /// The offending tip amount.
+        amount: u32,
+    },
+}
+
+impl Display for Invoice {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownCurrency(currency) => write!(
+                formatter,
+                "The currency {} is not supported by Telegram",
+                currency,
+            ),
+            Self::NoPrices => write!(formatter, "The invoice has no prices"),
+            Self::TotalOverflow => {
+                write!(formatter, "The total price of the invoice overflows")
+            }
+            Self::InvalidAmount(amount) => {
+                write!(formatter, "{:?} is not a valid amount", amount)
+            }
+            Self::TooManyFractionDigits { exponent } => write!(
+                formatter,
+                "The amount has more than {} fractional digits",
+                exponent,
+            ),
+            Self::TooManySuggestedTips => write!(
+                formatter,
+                "At most 4 suggested tip amounts may be specified",
+            ),
+            Self::MissingMaxTipAmount => write!(
+                formatter,
+                "Suggested tip amounts require the maximum tip amount",
+            ),
+            Self::UnorderedSuggestedTips => write!(
+                formatter,
+                "Suggested tip amounts must be positive and strictly \
+                 increasing",
+            ),
+            Self::SuggestedTipTooLarge { amount } => write!(
+                formatter,
+                "The suggested tip amount {} exceeds the maximum tip amount",
+                amount,
+            ),
+        }
+    }
+}
+
+impl Error for Invoice {}
@@ -0,0 +1,44 @@
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while parsing a duration
+/// such as `10m` or `1h30m`.
+#[derive(Debug, PartialEq, Eq, Clone, Is)]
+pub enum ParseDuration {
+    /// The input is empty.
+    Empty,
+    /// A unit is not preceded by a number.
+    MissingNumber,
+    /// A number is not followed by a unit.
+    MissingUnit,
+    /// The unit is not one of `s`, `m`, `h`, `d` or `w`.
+    UnknownUnit(String),
+    /// The duration is too long to be represented.
+    Overflow,
+}
+
+impl Display for ParseDuration {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => write!(formatter, "The duration is empty"),
+            Self::MissingNumber => {
+                write!(formatter, "The duration has a unit without a number")
+            }
+            Self::MissingUnit => {
+                write!(formatter, "The duration has a number without a unit")
+            }
+            Self::UnknownUnit(unit) => write!(
+                formatter,
+                "The duration has an unknown unit `{}`, while only `s`, `m`, \
+                 `h`, `d` and `w` are supported",
+                unit,
+            ),
+            Self::Overflow => write!(formatter, "The duration is too long"),
+        }
+    }
+}
+
+impl Error for ParseDuration {}
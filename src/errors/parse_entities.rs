@@ -0,0 +1,94 @@
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while parsing markup locally.
+///
+/// Offsets are byte offsets in the markup, like in Telegram's errors.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Is)]
+pub enum ParseEntities {
+    /// A tag could not be parsed.
+    MalformedTag {
+        /// The offset of the tag.
+        offset: usize,
+    },
+    /// Telegram doesn't support this tag.
+    UnsupportedTag {
+        /// The name of the tag.
+        tag: String,
+        /// The offset of the tag.
+        offset: usize,
+    },
+    /// An end tag doesn't match the last start tag.
+    UnmatchedEndTag {
+        /// The name of the end tag.
+        tag: String,
+        /// The offset of the end tag.
+        offset: usize,
+    },
+    /// A start tag isn't closed.
+    UnclosedTag {
+        /// The name of the start tag.
+        tag: String,
+        /// The offset of the start tag.
+        offset: usize,
+    },
+    /// A reserved character isn't escaped.
+    ReservedCharacter {
+        /// The character.
+        character: char,
+        /// The offset of the character.
+        offset: usize,
+    },
+    /// An entity isn't closed.
+    UnclosedEntity {
+        /// The offset where the entity starts.
+        offset: usize,
+    },
+}
+
+impl Display for ParseEntities {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MalformedTag { offset } => write!(
+                formatter,
+                "Can't parse entities: malformed tag at byte offset {}",
+                offset,
+            ),
+            Self::UnsupportedTag { tag, offset } => write!(
+                formatter,
+                "Can't parse entities: unsupported tag \"{}\" at byte \
+                 offset {}",
+                tag, offset,
+            ),
+            Self::UnmatchedEndTag { tag, offset } => write!(
+                formatter,
+                "Can't parse entities: unmatched end tag \"{}\" at byte \
+                 offset {}",
+                tag, offset,
+            ),
+            Self::UnclosedTag { tag, offset } => write!(
+                formatter,
+                "Can't parse entities: can't find end tag corresponding to \
+                 start tag \"{}\" at byte offset {}",
+                tag, offset,
+            ),
+            Self::ReservedCharacter { character, offset } => write!(
+                formatter,
+                "Can't parse entities: character '{}' at byte offset {} is \
+                 reserved and must be escaped with the preceding '\\'",
+                character, offset,
+            ),
+            Self::UnclosedEntity { offset } => write!(
+                formatter,
+                "Can't parse entities: can't find end of the entity starting \
+                 at byte offset {}",
+                offset,
+            ),
+        }
+    }
+}
+
+impl Error for ParseEntities {}
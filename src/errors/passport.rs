@@ -0,0 +1,68 @@
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while decrypting
+/// Telegram Passport data.
+#[derive(Debug, Is)]
+pub enum Passport {
+    /// The bot's private key could not be parsed.
+    InvalidKey(String),
+    /// The credentials' secret could not be decrypted with the bot's
+    /// private key.
+    Rsa,
+    /// The encrypted data is malformed, e.g. it is not valid base64
+    /// or has an invalid length or padding.
+    Malformed,
+    /// The decrypted data doesn't match its hash.
+    HashMismatch,
+    /// The decrypted data is not valid JSON or doesn't have expected fields.
+    Parse(serde_json::Error),
+    /// The credentials don't contain secrets for an element or a file.
+    MissingCredentials,
+    /// The credentials contain a nonce other than the bot expected.
+    NonceMismatch,
+}
+
+impl Display for Passport {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidKey(error) => write!(
+                formatter,
+                "The private key could not be parsed: {}",
+                error,
+            ),
+            Self::Rsa => write!(
+                formatter,
+                "The credentials' secret could not be decrypted"
+            ),
+            Self::Malformed => {
+                write!(formatter, "The encrypted data is malformed")
+            }
+            Self::HashMismatch => {
+                write!(formatter, "The decrypted data doesn't match its hash")
+            }
+            Self::Parse(error) => write!(
+                formatter,
+                "The decrypted data could not be parsed: {}",
+                error,
+            ),
+            Self::MissingCredentials => {
+                write!(formatter, "The credentials are missing for a value")
+            }
+            Self::NonceMismatch => {
+                write!(formatter, "The credentials contain an unexpected nonce")
+            }
+        }
+    }
+}
+
+impl Error for Passport {}
+
+impl From<serde_json::Error> for Passport {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Parse(error)
+    }
+}
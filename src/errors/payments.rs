@@ -0,0 +1,48 @@
+use super::MethodCall;
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while sending an invoice
+/// for a product from the catalogue.
+#[derive(Debug, Is)]
+pub enum Payments {
+    /// The catalogue doesn't contain a product with this ID.
+    UnknownProduct(String),
+    /// The encoded invoice payload exceeds Telegram's 128 bytes limit.
+    PayloadTooLong {
+        /// The length of the encoded payload.
+        length: usize,
+    },
+    /// Sending the invoice failed.
+    MethodCall(MethodCall),
+}
+
+impl Display for Payments {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownProduct(id) => {
+                write!(formatter, "There is no product with ID {}", id)
+            }
+            Self::PayloadTooLong { length } => write!(
+                formatter,
+                "The invoice payload is {} bytes long while Telegram accepts \
+                 at most 128 bytes",
+                length,
+            ),
+            Self::MethodCall(error) => {
+                write!(formatter, "Sending the invoice failed: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for Payments {}
+
+impl From<MethodCall> for Payments {
+    fn from(error: MethodCall) -> Self {
+        Self::MethodCall(error)
+    }
+}
@@ -0,0 +1,47 @@
+use super::{MethodCall, ParseEntities};
+use crate::types::Message;
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while sending a long message.
+#[derive(Debug, Is)]
+pub enum SendLongMessage {
+    /// The markup could not be parsed, so nothing was sent.
+    ParseEntities(ParseEntities),
+    /// Sending one of the parts failed.
+    MethodCall {
+        /// The error which occured while sending the part.
+        error: MethodCall,
+        /// The parts which were sent before the error occured.
+        sent: Vec<Message>,
+    },
+}
+
+impl Display for SendLongMessage {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::ParseEntities(error) => write!(
+                formatter,
+                "The long message could not be split: {}",
+                error,
+            ),
+            Self::MethodCall { error, sent } => write!(
+                formatter,
+                "Sending the long message failed after sending {} parts: {}",
+                sent.len(),
+                error,
+            ),
+        }
+    }
+}
+
+impl Error for SendLongMessage {}
+
+impl From<ParseEntities> for SendLongMessage {
+    fn from(error: ParseEntities) -> Self {
+        Self::ParseEntities(error)
+    }
+}
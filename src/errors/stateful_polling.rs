@@ -0,0 +1,25 @@
+use super::{PollingSetup, Storage};
+use is_macro::Is;
+
+/// Represents possible errors that may happen while running the stateful
+/// polling event loop until shutdown.
+#[derive(Debug, Is)]
+#[non_exhaustive]
+pub enum StatefulPolling {
+    /// Preparing the polling event loop failed.
+    Setup(PollingSetup),
+    /// Saving the state to its storage on shutdown failed.
+    SaveState(Storage),
+}
+
+impl From<PollingSetup> for StatefulPolling {
+    fn from(error: PollingSetup) -> Self {
+        Self::Setup(error)
+    }
+}
+
+impl From<Storage> for StatefulPolling {
+    fn from(error: Storage) -> Self {
+        Self::SaveState(error)
+    }
+}
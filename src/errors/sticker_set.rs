@@ -0,0 +1,57 @@
+use super::MethodCall;
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Represents possible errors which may occur while synchronizing
+/// a sticker set.
+#[derive(Debug, Is)]
+pub enum StickerSet {
+    /// The bot doesn't have a username, so the set's name can't be
+    /// generated.
+    NoUsername,
+    /// The set doesn't exist and the manifest is empty, so it can't be
+    /// created.
+    EmptyManifest,
+    /// Several stickers in the manifest have the same key.
+    DuplicateKey(String),
+    /// A method call failed.
+    MethodCall(MethodCall),
+}
+
+impl Display for StickerSet {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NoUsername => write!(
+                formatter,
+                "The sticker set's name could not be generated because the \
+                 bot doesn't have a username",
+            ),
+            Self::EmptyManifest => write!(
+                formatter,
+                "The sticker set could not be created because the manifest \
+                 is empty",
+            ),
+            Self::DuplicateKey(key) => write!(
+                formatter,
+                "The manifest contains several stickers with key {}",
+                key,
+            ),
+            Self::MethodCall(error) => write!(
+                formatter,
+                "Synchronizing the sticker set failed: {}",
+                error,
+            ),
+        }
+    }
+}
+
+impl Error for StickerSet {}
+
+impl From<MethodCall> for StickerSet {
+    fn from(error: MethodCall) -> Self {
+        Self::MethodCall(error)
+    }
+}
@@ -0,0 +1,70 @@
+use is_macro::Is;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+};
+
+/// Represents possible errors which may occur while persisting state.
+#[derive(Debug, Is)]
+#[non_exhaustive]
+pub enum Storage {
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// A value could not be serialized or deserialized.
+    Serialization(serde_json::Error),
+    /// A stored key could not be parsed.
+    InvalidKey(String),
+    /// The embedded key-value store returned an error.
+    #[cfg(feature = "sled")]
+    Sled(sled::Error),
+}
+
+impl Display for Storage {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(
+                formatter,
+                "The storage could not access its files: {}",
+                error,
+            ),
+            Self::Serialization(error) => write!(
+                formatter,
+                "A value could not be (de)serialized: {}",
+                error,
+            ),
+            Self::InvalidKey(key) => {
+                write!(
+                    formatter,
+                    "The storage contains an invalid key: {}",
+                    key
+                )
+            }
+            #[cfg(feature = "sled")]
+            Self::Sled(error) => {
+                write!(formatter, "The key-value store failed: {}", error)
+            }
+        }
+    }
+}
+
+impl Error for Storage {}
+
+impl From<io::Error> for Storage {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for Storage {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(error)
+    }
+}
+
+#[cfg(feature = "sled")]
+impl From<sled::Error> for Storage {
+    fn from(error: sled::Error) -> Self {
+        Self::Sled(error)
+    }
+}
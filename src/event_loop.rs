@@ -13,20 +13,27 @@ use crate::{
         },
         update,
     },
+    util::callback_data::{CallbackData, Codec},
     Bot,
 };
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, PoisonError, RwLock},
+};
 use tracing::{error, instrument, trace, warn};
 
 #[macro_use]
 mod handlers_macros;
 
+mod command_description;
 mod polling;
 pub mod webhook;
 
 use errors::MethodCall;
-use types::parameters::BotCommand;
-pub use {polling::Polling, webhook::Webhook};
+pub use {
+    command_description::CommandDescription, polling::Polling, webhook::Webhook,
+};
 
 type Handlers<T> = Vec<Box<T>>;
 type Map<T> = HashMap<String, Handlers<T>>;
@@ -36,11 +43,15 @@ type Handler<T> = dyn Fn(Arc<T>) + Send + Sync;
 
 type AnimationHandler = Handler<contexts::Animation>;
 type AudioHandler = Handler<contexts::Audio>;
+type AutoDeleteTimerHandler = Handler<contexts::AutoDeleteTimer>;
 type ChosenInlineHandler = Handler<contexts::ChosenInline>;
+type ClosedForumTopicHandler = Handler<contexts::ClosedForumTopic>;
 type CommandHandler = Handler<contexts::Command>;
 type ConnectedWebsiteHandler = Handler<contexts::ConnectedWebsite>;
 type ContactHandler = Handler<contexts::Contact>;
+type CreatedForumTopicHandler = Handler<contexts::CreatedForumTopic>;
 type CreatedGroupHandler = Handler<contexts::CreatedGroup>;
+type EndedVoiceChatHandler = Handler<contexts::EndedVoiceChat>;
 type MessageDataCallbackHandler = Handler<contexts::MessageDataCallback>;
 type InlineDataCallbackHandler = Handler<contexts::InlineDataCallback>;
 type DeletedChatPhotoHandler = Handler<contexts::DeletedChatPhoto>;
@@ -72,7 +83,11 @@ type PinnedMessageHandler = Handler<contexts::PinnedMessage>;
 type PollHandler = Handler<contexts::Poll>;
 type PollAnswerHandler = Handler<contexts::PollAnswer>;
 type PreCheckoutHandler = Handler<contexts::PreCheckout>;
+type ProximityAlertHandler = Handler<contexts::ProximityAlert>;
+type ReopenedForumTopicHandler = Handler<contexts::ReopenedForumTopic>;
+type ScheduledVoiceChatHandler = Handler<contexts::ScheduledVoiceChat>;
 type ShippingHandler = Handler<contexts::Shipping>;
+type StartedVoiceChatHandler = Handler<contexts::StartedVoiceChat>;
 type StickerHandler = Handler<contexts::Sticker>;
 type TextHandler = Handler<contexts::Text>;
 type UnhandledHandler = Handler<contexts::Unhandled>;
@@ -81,6 +96,7 @@ type UpdateHandler = Handler<contexts::Update>;
 type VenueHandler = Handler<contexts::Venue>;
 type VideoHandler = Handler<contexts::Video>;
 type VideoNoteHandler = Handler<contexts::VideoNote>;
+type VoiceChatInvitationHandler = Handler<contexts::VoiceChatInvitation>;
 type VoiceHandler = Handler<contexts::Voice>;
 
 /// Provides an event loop for handling Telegram updates.
@@ -106,17 +122,21 @@ type VoiceHandler = Handler<contexts::Voice>;
 pub struct EventLoop {
     bot: Bot,
     username: Option<String>,
+    callback_data_codec: Arc<RwLock<Codec>>,
 
     command_handlers: Map<CommandHandler>,
-    command_description: HashMap<String, String>,
+    command_description: HashMap<String, CommandDescription>,
     edited_command_handlers: Map<EditedCommandHandler>,
     after_update_handlers: Handlers<UpdateHandler>,
     animation_handlers: Handlers<AnimationHandler>,
     audio_handlers: Handlers<AudioHandler>,
+    auto_delete_timer_handlers: Handlers<AutoDeleteTimerHandler>,
     before_update_handlers: Handlers<UpdateHandler>,
     chosen_inline_handlers: Handlers<ChosenInlineHandler>,
+    closed_forum_topic_handlers: Handlers<ClosedForumTopicHandler>,
     contact_handlers: Handlers<ContactHandler>,
     connected_website_handlers: Handlers<ConnectedWebsiteHandler>,
+    created_forum_topic_handlers: Handlers<CreatedForumTopicHandler>,
     created_group_handlers: Handlers<CreatedGroupHandler>,
     deleted_chat_photo_handlers: Handlers<DeletedChatPhotoHandler>,
     dice_handlers: Handlers<DiceHandler>,
@@ -128,6 +148,7 @@ pub struct EventLoop {
     edited_photo_handlers: Handlers<EditedPhotoHandler>,
     edited_text_handlers: Handlers<EditedTextHandler>,
     edited_video_handlers: Handlers<EditedVideoHandler>,
+    ended_voice_chat_handlers: Handlers<EndedVoiceChatHandler>,
     game_handlers: Handlers<GameHandler>,
     inline_handlers: Handlers<InlineHandler>,
     inline_data_callback_handlers: Handlers<InlineDataCallbackHandler>,
@@ -148,7 +169,11 @@ pub struct EventLoop {
     poll_handlers: Handlers<PollHandler>,
     poll_answer_handlers: Handlers<PollAnswerHandler>,
     pre_checkout_handlers: Handlers<PreCheckoutHandler>,
+    proximity_alert_handlers: Handlers<ProximityAlertHandler>,
+    reopened_forum_topic_handlers: Handlers<ReopenedForumTopicHandler>,
+    scheduled_voice_chat_handlers: Handlers<ScheduledVoiceChatHandler>,
     shipping_handlers: Handlers<ShippingHandler>,
+    started_voice_chat_handlers: Handlers<StartedVoiceChatHandler>,
     sticker_handlers: Handlers<StickerHandler>,
     text_handlers: Handlers<TextHandler>,
     unhandled_handlers: Handlers<UnhandledHandler>,
@@ -156,6 +181,7 @@ pub struct EventLoop {
     venue_handlers: Handlers<VenueHandler>,
     video_handlers: Handlers<VideoHandler>,
     video_note_handlers: Handlers<VideoNoteHandler>,
+    voice_chat_invitation_handlers: Handlers<VoiceChatInvitationHandler>,
     voice_handlers: Handlers<VoiceHandler>,
 }
 
@@ -164,16 +190,20 @@ impl EventLoop {
         Self {
             bot,
             username: None,
+            callback_data_codec: Arc::new(RwLock::new(Codec::new())),
             command_handlers: HashMap::new(),
             command_description: HashMap::new(),
             edited_command_handlers: HashMap::new(),
             after_update_handlers: Vec::new(),
             animation_handlers: Vec::new(),
             audio_handlers: Vec::new(),
+            auto_delete_timer_handlers: Vec::new(),
             before_update_handlers: Vec::new(),
             chosen_inline_handlers: Vec::new(),
+            closed_forum_topic_handlers: Vec::new(),
             contact_handlers: Vec::new(),
             connected_website_handlers: Vec::new(),
+            created_forum_topic_handlers: Vec::new(),
             created_group_handlers: Vec::new(),
             deleted_chat_photo_handlers: Vec::new(),
             dice_handlers: Vec::new(),
@@ -185,6 +215,7 @@ impl EventLoop {
             edited_photo_handlers: Vec::new(),
             edited_text_handlers: Vec::new(),
             edited_video_handlers: Vec::new(),
+            ended_voice_chat_handlers: Vec::new(),
             game_handlers: Vec::new(),
             inline_handlers: Vec::new(),
             inline_data_callback_handlers: Vec::new(),
@@ -205,7 +236,11 @@ impl EventLoop {
             poll_handlers: Vec::new(),
             poll_answer_handlers: Vec::new(),
             pre_checkout_handlers: Vec::new(),
+            proximity_alert_handlers: Vec::new(),
+            reopened_forum_topic_handlers: Vec::new(),
+            scheduled_voice_chat_handlers: Vec::new(),
             shipping_handlers: Vec::new(),
+            started_voice_chat_handlers: Vec::new(),
             sticker_handlers: Vec::new(),
             text_handlers: Vec::new(),
             unhandled_handlers: Vec::new(),
@@ -213,6 +248,7 @@ impl EventLoop {
             venue_handlers: Vec::new(),
             video_handlers: Vec::new(),
             video_note_handlers: Vec::new(),
+            voice_chat_invitation_handlers: Vec::new(),
             voice_handlers: Vec::new(),
         }
     }
@@ -234,6 +270,21 @@ impl EventLoop {
         self.username = Some(username);
     }
 
+    /// Sets the codec for typed callback data.
+    ///
+    /// The codec is used by all handlers added with
+    /// [`message_data_callback_typed`] and [`inline_data_callback_typed`],
+    /// including the ones added before this call.
+    ///
+    /// [`message_data_callback_typed`]: #method.message_data_callback_typed
+    /// [`inline_data_callback_typed`]: #method.inline_data_callback_typed
+    pub fn callback_data_codec(&mut self, codec: Codec) {
+        *self
+            .callback_data_codec
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = codec;
+    }
+
     /// Starts polling configuration.
     pub fn polling(self) -> Polling {
         Polling::new(self)
@@ -271,23 +322,28 @@ impl EventLoop {
 
     /// Adds a new handler for a command and sets its description.
     ///
+    /// The description can be a plain string, or a [`CommandDescription`]
+    /// which also configures in which scopes the command is shown and
+    /// translates its description to other languages.
+    ///
     /// Note that commands such as `/command@username` will be completely
     /// ignored unless you configure the event loop with your bot's username
     /// with either [`username`] or [`fetch_username`].
     ///
+    /// [`CommandDescription`]: ./struct.CommandDescription.html
     /// [`username`]: #method.username
     /// [`fetch_username`]: #method.fetch_username
     pub fn command_with_description<H, F>(
         &mut self,
         command: &'static str,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>) -> F) + Send + Sync + 'static,
         F: Future<Output = ()> + Send + 'static,
     {
         self.command_description
-            .insert(command.to_string(), description.to_string());
+            .insert(command.to_string(), description.into());
         self.command(command, handler);
     }
 
@@ -336,7 +392,7 @@ impl EventLoop {
     pub fn command_with_description_if<H, HF, P, PF>(
         &mut self,
         command: &'static str,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -346,7 +402,7 @@ impl EventLoop {
         PF: Future<Output = bool> + Send + 'static,
     {
         self.command_description
-            .insert(command.to_string(), description.to_string());
+            .insert(command.to_string(), description.into());
         self.command_if(command, predicate, handler);
     }
 
@@ -439,7 +495,7 @@ impl EventLoop {
     /// Adds a new handler for the `/start` command and sets its description.
     pub fn start_with_description<H, F>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>) -> F) + Send + Sync + 'static,
@@ -474,7 +530,7 @@ impl EventLoop {
     /// if the predicate returns true. Also sets the command's description.
     pub fn start_with_description_if<H, HF, P, PF>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -503,7 +559,7 @@ impl EventLoop {
     /// Adds a new handler for the `/settings` command and sets its description.
     pub fn settings_with_description<H, F>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>) -> F) + Send + Sync + 'static,
@@ -538,7 +594,7 @@ impl EventLoop {
     /// if the predicate returns true. Also sets the command's description.
     pub fn settings_with_description_if<H, HF, P, PF>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -567,7 +623,7 @@ impl EventLoop {
     /// Adds a new handler for the `/help` command and sets its description.
     pub fn help_with_description<H, F>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>) -> F) + Send + Sync + 'static,
@@ -602,7 +658,7 @@ impl EventLoop {
     /// returns true. Also sets the command's description.
     pub fn help_with_description_if<H, HF, P, PF>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -749,6 +805,15 @@ impl EventLoop {
         audio_if,
     }
 
+    handler! {
+        contexts::AutoDeleteTimer,
+        /// Adds a new handler for changed auto-delete timers.
+        auto_delete_timer,
+        /// Adds a new handler for changed auto-delete timers which is run if
+        /// the predicate returns true.
+        auto_delete_timer_if,
+    }
+
     handler! {
         contexts::Update,
         /// Adds a new handler which is run before handling an update.
@@ -767,6 +832,15 @@ impl EventLoop {
         chosen_inline_if,
     }
 
+    handler! {
+        contexts::ClosedForumTopic,
+        /// Adds a new handler for closed forum topics.
+        closed_forum_topic,
+        /// Adds a new handler for closed forum topics which is run if the
+        /// predicate returns true.
+        closed_forum_topic_if,
+    }
+
     handler! {
         contexts::Contact,
         /// Adds a new handler for contacts.
@@ -785,6 +859,15 @@ impl EventLoop {
         connected_website_if,
     }
 
+    handler! {
+        contexts::CreatedForumTopic,
+        /// Adds a new handler for created forum topics.
+        created_forum_topic,
+        /// Adds a new handler for created forum topics which is run if the
+        /// predicate returns true.
+        created_forum_topic_if,
+    }
+
     handler! {
         contexts::CreatedGroup,
         /// Adds a new handler for created groups.
@@ -812,6 +895,60 @@ impl EventLoop {
         inline_data_callback_if,
     }
 
+    /// Adds a new handler for data callbacks from chat messages which is run
+    /// only if the data decodes to `T` with the event loop's
+    /// [codec][callback_data_codec].
+    ///
+    /// [callback_data_codec]: #method.callback_data_codec
+    pub fn message_data_callback_typed<T, H, F>(&mut self, handler: H)
+    where
+        T: CallbackData + Send + 'static,
+        H: (Fn(Arc<contexts::MessageDataCallback>, T) -> F)
+            + Send
+            + Sync
+            + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let codec = Arc::clone(&self.callback_data_codec);
+        self.message_data_callback_handlers
+            .push(Box::new(move |context| {
+                let data = codec
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .decode(&context.data);
+                if let Ok(data) = data {
+                    tokio::spawn(handler(context, data));
+                }
+            }));
+    }
+
+    /// Adds a new handler for data callbacks from inline messages which is
+    /// run only if the data decodes to `T` with the event loop's
+    /// [codec][callback_data_codec].
+    ///
+    /// [callback_data_codec]: #method.callback_data_codec
+    pub fn inline_data_callback_typed<T, H, F>(&mut self, handler: H)
+    where
+        T: CallbackData + Send + 'static,
+        H: (Fn(Arc<contexts::InlineDataCallback>, T) -> F)
+            + Send
+            + Sync
+            + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let codec = Arc::clone(&self.callback_data_codec);
+        self.inline_data_callback_handlers
+            .push(Box::new(move |context| {
+                let data = codec
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .decode(&context.data);
+                if let Ok(data) = data {
+                    tokio::spawn(handler(context, data));
+                }
+            }));
+    }
+
     handler! {
         contexts::DeletedChatPhoto,
         /// Adds a new handler for deleted chat photos.
@@ -902,6 +1039,15 @@ impl EventLoop {
         edited_video_if,
     }
 
+    handler! {
+        contexts::EndedVoiceChat,
+        /// Adds a new handler for ended voice chats.
+        ended_voice_chat,
+        /// Adds a new handler for ended voice chats which is run if the
+        /// predicate returns true.
+        ended_voice_chat_if,
+    }
+
     handler! {
         contexts::MessageGameCallback,
         /// Adds a new handler for game callbacks from chat messages.
@@ -1055,6 +1201,33 @@ impl EventLoop {
         pre_checkout_if,
     }
 
+    handler! {
+        contexts::ProximityAlert,
+        /// Adds a new handler for triggered proximity alerts.
+        proximity_alert,
+        /// Adds a new handler for triggered proximity alerts which is run if
+        /// the predicate returns true.
+        proximity_alert_if,
+    }
+
+    handler! {
+        contexts::ReopenedForumTopic,
+        /// Adds a new handler for reopened forum topics.
+        reopened_forum_topic,
+        /// Adds a new handler for reopened forum topics which is run if the
+        /// predicate returns true.
+        reopened_forum_topic_if,
+    }
+
+    handler! {
+        contexts::ScheduledVoiceChat,
+        /// Adds a new handler for scheduled voice chats.
+        scheduled_voice_chat,
+        /// Adds a new handler for scheduled voice chats which is run if the
+        /// predicate returns true.
+        scheduled_voice_chat_if,
+    }
+
     handler! {
         contexts::Shipping,
         /// Adds a new handler for shipping queries.
@@ -1064,6 +1237,15 @@ impl EventLoop {
         shipping_if,
     }
 
+    handler! {
+        contexts::StartedVoiceChat,
+        /// Adds a new handler for started voice chats.
+        started_voice_chat,
+        /// Adds a new handler for started voice chats which is run if the
+        /// predicate returns true.
+        started_voice_chat_if,
+    }
+
     handler! {
         contexts::Sticker,
         /// Adds a new handler for stickers.
@@ -1182,6 +1364,15 @@ impl EventLoop {
         voice_if,
     }
 
+    handler! {
+        contexts::VoiceChatInvitation,
+        /// Adds a new handler for invitations to voice chats.
+        voice_chat_invitation,
+        /// Adds a new handler for invitations to voice chats which is run if
+        /// the predicate returns true.
+        voice_chat_invitation_if,
+    }
+
     #[instrument(skip(self, update))]
     fn handle_update(&self, update: types::Update) {
         trace!(?update);
@@ -1191,6 +1382,13 @@ impl EventLoop {
 
         self.run_before_update_handlers(update_context.clone());
 
+        if let Some(directory) = self.bot.directory() {
+            directory.observe(&update.kind);
+        }
+        if let Some(registry) = self.bot.admin_registry() {
+            registry.observe(&update.kind);
+        }
+
         match update.kind {
             update::Kind::CallbackQuery(query) => match query {
                 Query {
@@ -1303,7 +1501,18 @@ impl EventLoop {
             }
             update::Kind::Message(message)
             | update::Kind::ChannelPost(message) => {
-                self.handle_message_update(message);
+                // Commands always go to their handlers so that a user can
+                // leave a conversation with a command such as `/cancel`.
+                let message = match &message.kind {
+                    message::Kind::Text(text) if is_command(text) => {
+                        Some(message)
+                    }
+                    _ => self.bot.waiters().resolve(message),
+                };
+
+                if let Some(message) = message {
+                    self.handle_message_update(message);
+                }
             }
             update::Kind::PreCheckoutQuery(query)
                 if self.will_handle_pre_checkout() =>
@@ -1339,6 +1548,8 @@ impl EventLoop {
             | update::Kind::PollAnswer(..)
             | update::Kind::PreCheckoutQuery(..)
             | update::Kind::ShippingQuery(..)
+            | update::Kind::MyChatMember(..)
+            | update::Kind::ChatMember(..)
             | update::Kind::Unknown => (),
         }
 
@@ -1376,6 +1587,16 @@ impl EventLoop {
                 );
                 self.run_audio_handlers(Arc::new(context));
             }
+            message::Kind::AutoDeleteTimerChanged(time)
+                if self.will_handle_auto_delete_timer() =>
+            {
+                let context = contexts::AutoDeleteTimer::new(
+                    self.bot.clone(),
+                    data,
+                    time,
+                );
+                self.run_auto_delete_timer_handlers(Arc::new(context));
+            }
             message::Kind::ChatPhotoDeleted
                 if self.will_handle_deleted_chat_photo() =>
             {
@@ -1416,6 +1637,30 @@ impl EventLoop {
                 );
                 self.run_document_handlers(Arc::new(context));
             }
+            message::Kind::ForumTopicClosed
+                if self.will_handle_closed_forum_topic() =>
+            {
+                let context =
+                    contexts::ClosedForumTopic::new(self.bot.clone(), data);
+                self.run_closed_forum_topic_handlers(Arc::new(context));
+            }
+            message::Kind::ForumTopicCreated(topic)
+                if self.will_handle_created_forum_topic() =>
+            {
+                let context = contexts::CreatedForumTopic::new(
+                    self.bot.clone(),
+                    data,
+                    topic,
+                );
+                self.run_created_forum_topic_handlers(Arc::new(context));
+            }
+            message::Kind::ForumTopicReopened
+                if self.will_handle_reopened_forum_topic() =>
+            {
+                let context =
+                    contexts::ReopenedForumTopic::new(self.bot.clone(), data);
+                self.run_reopened_forum_topic_handlers(Arc::new(context));
+            }
             message::Kind::Game(game) if self.will_handle_game() => {
                 let context =
                     contexts::Game::new(self.bot.clone(), data, *game);
@@ -1512,6 +1757,16 @@ impl EventLoop {
                 let context = contexts::Poll::new(self.bot.clone(), data, poll);
                 self.run_poll_handlers(Arc::new(context));
             }
+            message::Kind::ProximityAlert(alert)
+                if self.will_handle_proximity_alert() =>
+            {
+                let context = contexts::ProximityAlert::new(
+                    self.bot.clone(),
+                    data,
+                    alert,
+                );
+                self.run_proximity_alert_handlers(Arc::new(context));
+            }
             message::Kind::Sticker(sticker) if self.will_handle_sticker() => {
                 let context =
                     contexts::Sticker::new(self.bot.clone(), data, *sticker);
@@ -1591,6 +1846,43 @@ impl EventLoop {
                 );
                 self.run_voice_handlers(Arc::new(context));
             }
+            message::Kind::VoiceChatEnded(voice_chat)
+                if self.will_handle_ended_voice_chat() =>
+            {
+                let context = contexts::EndedVoiceChat::new(
+                    self.bot.clone(),
+                    data,
+                    voice_chat,
+                );
+                self.run_ended_voice_chat_handlers(Arc::new(context));
+            }
+            message::Kind::VoiceChatParticipantsInvited(invited)
+                if self.will_handle_voice_chat_invitation() =>
+            {
+                let context = contexts::VoiceChatInvitation::new(
+                    self.bot.clone(),
+                    data,
+                    invited.users,
+                );
+                self.run_voice_chat_invitation_handlers(Arc::new(context));
+            }
+            message::Kind::VoiceChatScheduled(voice_chat)
+                if self.will_handle_scheduled_voice_chat() =>
+            {
+                let context = contexts::ScheduledVoiceChat::new(
+                    self.bot.clone(),
+                    data,
+                    voice_chat,
+                );
+                self.run_scheduled_voice_chat_handlers(Arc::new(context));
+            }
+            message::Kind::VoiceChatStarted
+                if self.will_handle_started_voice_chat() =>
+            {
+                let context =
+                    contexts::StartedVoiceChat::new(self.bot.clone(), data);
+                self.run_started_voice_chat_handlers(Arc::new(context));
+            }
             message::Kind::SupergroupCreated
             | message::Kind::ChannelCreated => {
                 warn!("Update not expected; skipping it")
@@ -1602,11 +1894,15 @@ impl EventLoop {
             }
             message::Kind::Animation { .. }
             | message::Kind::Audio { .. }
+            | message::Kind::AutoDeleteTimerChanged(..)
             | message::Kind::ChatPhotoDeleted
             | message::Kind::ConnectedWebsite(..)
             | message::Kind::Contact(..)
             | message::Kind::Dice(..)
             | message::Kind::Document { .. }
+            | message::Kind::ForumTopicClosed
+            | message::Kind::ForumTopicCreated(..)
+            | message::Kind::ForumTopicReopened
             | message::Kind::Game(..)
             | message::Kind::GroupCreated
             | message::Kind::Invoice(..)
@@ -1620,6 +1916,7 @@ impl EventLoop {
             | message::Kind::Photo { .. }
             | message::Kind::Pinned(..)
             | message::Kind::Poll(..)
+            | message::Kind::ProximityAlert(..)
             | message::Kind::Sticker(..)
             | message::Kind::SuccessfulPayment(..)
             | message::Kind::Text(..)
@@ -1627,6 +1924,10 @@ impl EventLoop {
             | message::Kind::Video { .. }
             | message::Kind::VideoNote(..)
             | message::Kind::Voice { .. }
+            | message::Kind::VoiceChatEnded(..)
+            | message::Kind::VoiceChatParticipantsInvited(..)
+            | message::Kind::VoiceChatScheduled(..)
+            | message::Kind::VoiceChatStarted
             | message::Kind::Unknown => (),
         }
     }
@@ -1783,7 +2084,16 @@ impl EventLoop {
             | message::Kind::PassportData(..)
             | message::Kind::Pinned(..)
             | message::Kind::SuccessfulPayment(..)
-            | message::Kind::SupergroupCreated => warn!(
+            | message::Kind::SupergroupCreated
+            | message::Kind::ProximityAlert(..)
+            | message::Kind::AutoDeleteTimerChanged(..)
+            | message::Kind::VoiceChatScheduled(..)
+            | message::Kind::VoiceChatStarted
+            | message::Kind::VoiceChatEnded(..)
+            | message::Kind::VoiceChatParticipantsInvited(..)
+            | message::Kind::ForumTopicCreated(..)
+            | message::Kind::ForumTopicClosed
+            | message::Kind::ForumTopicReopened => warn!(
                 "Unexpected message kind received as an edited message; \
                 skipping it"
             ),
@@ -1817,13 +2127,18 @@ impl EventLoop {
             return Ok(());
         }
 
-        let commands: Vec<_> = self
-            .command_description
-            .iter()
-            .map(|(name, description)| BotCommand::new(name, description))
-            .collect();
+        for menu in command_description::menus(&self.command_description) {
+            let mut method = self
+                .bot
+                .set_my_commands(menu.commands)
+                .scope(menu.scope.clone());
 
-        self.bot.set_my_commands(&commands).call().await?;
+            if let Some(language_code) = menu.language_code {
+                method = method.language_code(language_code);
+            }
+
+            method.call().await?;
+        }
 
         Ok(())
     }
@@ -0,0 +1,208 @@
+use crate::types::parameters::{BotCommand, BotCommandScope};
+use std::collections::HashMap;
+
+/// Describes how a command is shown in the bot's command menu.
+///
+/// A plain string converts into a description which is shown in the default
+/// scope for all languages, so you can pass `&'static str` wherever
+/// a `CommandDescription` is expected. If you want to show a command only
+/// to some users, or translate its description, construct
+/// a `CommandDescription` explicitly:
+///
+/// ```
+/// use tbot::{
+///     event_loop::CommandDescription, types::parameters::BotCommandScope,
+/// };
+///
+/// let description = CommandDescription::new("Ban a user")
+///     .scope(BotCommandScope::AllChatAdministrators)
+///     .translation("ru", "Забанить пользователя");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[must_use]
+pub struct CommandDescription {
+    description: String,
+    scopes: Vec<BotCommandScope<'static>>,
+    translations: Vec<(String, String)>,
+}
+
+impl CommandDescription {
+    /// Constructs a new `CommandDescription`. Unless you add scopes,
+    /// the command is shown in the default scope.
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            scopes: Vec::new(),
+            translations: Vec::new(),
+        }
+    }
+
+    /// Adds a scope in which the command is shown. Can be called several
+    /// times to show the command in several scopes.
+    pub fn scope(mut self, scope: BotCommandScope<'static>) -> Self {
+        if !self.scopes.contains(&scope) {
+            self.scopes.push(scope);
+        }
+        self
+    }
+
+    /// Adds the command's description for users with the given language.
+    pub fn translation(
+        mut self,
+        language_code: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        let language_code = language_code.into();
+        let description = description.into();
+
+        match self
+            .translations
+            .iter_mut()
+            .find(|(language, _)| *language == language_code)
+        {
+            Some((_, old)) => *old = description,
+            None => self.translations.push((language_code, description)),
+        }
+
+        self
+    }
+
+    fn scopes(&self) -> &[BotCommandScope<'static>] {
+        if self.scopes.is_empty() {
+            std::slice::from_ref(&DEFAULT_SCOPE)
+        } else {
+            &self.scopes
+        }
+    }
+
+    fn description_for(&self, language_code: Option<&str>) -> &str {
+        language_code
+            .and_then(|language_code| {
+                self.translations
+                    .iter()
+                    .find(|(language, _)| language == language_code)
+            })
+            .map_or(&self.description, |(_, description)| description)
+    }
+}
+
+static DEFAULT_SCOPE: BotCommandScope<'static> = BotCommandScope::Default;
+
+impl From<&str> for CommandDescription {
+    fn from(description: &str) -> Self {
+        Self::new(description)
+    }
+}
+
+impl From<String> for CommandDescription {
+    fn from(description: String) -> Self {
+        Self::new(description)
+    }
+}
+
+/// A list of commands to be set for a scope and a language.
+pub struct Menu<'a> {
+    pub scope: &'a BotCommandScope<'static>,
+    pub language_code: Option<&'a str>,
+    pub commands: Vec<BotCommand<'a>>,
+}
+
+/// Groups commands' descriptions into lists that are set with separate
+/// `setMyCommands` calls.
+///
+/// If some command is translated to a language within a scope, the list
+/// for that language must contain all commands of that scope, as Telegram
+/// doesn't fall back to the default list for missing commands. Thus commands
+/// without a translation use their default description in such lists.
+pub fn menus(
+    descriptions: &HashMap<String, CommandDescription>,
+) -> Vec<Menu<'_>> {
+    let mut commands: Vec<_> = descriptions.iter().collect();
+    commands.sort_by_key(|(command, _)| *command);
+
+    let mut menus: Vec<Menu<'_>> = Vec::new();
+    for (_, description) in &commands {
+        for scope in description.scopes() {
+            if !menus.iter().any(|menu| menu.scope == scope) {
+                menus.push(Menu {
+                    scope,
+                    language_code: None,
+                    commands: Vec::new(),
+                });
+            }
+
+            for (language_code, _) in &description.translations {
+                let exists = menus.iter().any(|menu| {
+                    menu.scope == scope
+                        && menu.language_code == Some(language_code)
+                });
+
+                if !exists {
+                    menus.push(Menu {
+                        scope,
+                        language_code: Some(language_code),
+                        commands: Vec::new(),
+                    });
+                }
+            }
+        }
+    }
+
+    for menu in &mut menus {
+        for (command, description) in &commands {
+            if description.scopes().contains(menu.scope) {
+                let text = description.description_for(menu.language_code);
+                menu.commands.push(BotCommand::new(command.as_str(), text));
+            }
+        }
+    }
+
+    menus
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_commands_by_scope_and_language() {
+        let mut descriptions = HashMap::new();
+        descriptions.insert("start".to_string(), "Start the bot".into());
+        descriptions.insert(
+            "help".to_string(),
+            CommandDescription::new("Get help").translation("ru", "Помощь"),
+        );
+        descriptions.insert(
+            "ban".to_string(),
+            CommandDescription::new("Ban a user")
+                .scope(BotCommandScope::AllChatAdministrators),
+        );
+
+        let menus = menus(&descriptions);
+        assert_eq!(menus.len(), 3);
+
+        assert_eq!(menus[0].scope, &BotCommandScope::AllChatAdministrators);
+        assert_eq!(menus[0].language_code, None);
+        assert_eq!(menus[0].commands, [BotCommand::new("ban", "Ban a user")]);
+
+        assert_eq!(menus[1].scope, &BotCommandScope::Default);
+        assert_eq!(menus[1].language_code, None);
+        assert_eq!(
+            menus[1].commands,
+            [
+                BotCommand::new("help", "Get help"),
+                BotCommand::new("start", "Start the bot"),
+            ]
+        );
+
+        assert_eq!(menus[2].scope, &BotCommandScope::Default);
+        assert_eq!(menus[2].language_code, Some("ru"));
+        assert_eq!(
+            menus[2].commands,
+            [
+                BotCommand::new("help", "Помощь"),
+                BotCommand::new("start", "Start the bot"),
+            ]
+        );
+    }
+}
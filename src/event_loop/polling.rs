@@ -50,7 +50,7 @@ impl Polling {
     where
         S: Send + Sync + 'static,
     {
-        state::Polling::new(self.event_loop, Arc::new(state))
+        state::Polling::new(self.event_loop, Arc::new(state), None)
     }
 
     /// Configures the limit of updates per request.
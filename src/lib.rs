@@ -104,4 +104,5 @@ pub mod prelude {
     pub use super::contexts::methods::Pinnable as _;
     pub use super::util::ChatActionLoop as _;
     pub use super::util::ChatActionLoopBotExt as _;
+    pub use super::util::Conversation as _;
 }
@@ -57,8 +57,12 @@
 //! }
 //! ```
 //!
+//! To go the other way around and check what Telegram will make of your
+//! markup, see the [`parse`] module.
+//!
 //! [`html`]: ./html/fn.html.html
 //! [`markdown_v2`]: ./markdown_v2/fn.markdown_v2.html
+//! [`parse`]: ./parse/index.html
 
 macro_rules! impl_primitive {
     ($trait:ty, $($primitive:ty)+) => {
@@ -121,6 +125,7 @@ macro_rules! impl_tuples {
 
 pub mod html;
 pub mod markdown_v2;
+pub mod parse;
 
 pub use html::html;
 pub use markdown_v2::markdown_v2;
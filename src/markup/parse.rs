@@ -0,0 +1,104 @@
+//! Parsing markup into text and entities locally.
+//!
+//! These functions are the inverse of the [`markup`] formatters: they turn
+//! HTML, MarkdownV2 or legacy Markdown into the plain text and entities
+//! Telegram would get from it, following the same rules. This way, you can
+//! check templates in tests instead of getting "can't parse entities" errors
+//! in production, or check the text's length before sending it:
+//!
+//! ```
+//! use tbot::markup::parse;
+//!
+//! let text = parse::markdown_v2("*Hello*, [world](https://example.com)\\!")
+//!     .unwrap();
+//! assert_eq!(text.value, "Hello, world!");
+//! assert_eq!(text.entities.len(), 2);
+//!
+//! // Telegram counts the length in UTF-16 code units.
+//! assert!(text.value.encode_utf16().count() <= 4096);
+//!
+//! assert!(parse::html("<b>unclosed").is_err());
+//! ```
+//!
+//! Entities which Telegram detects automatically, such as mentions or URLs,
+//! aren't included. Links to `tg://user?id=` are returned as text links,
+//! as the mentioned user isn't known locally.
+//!
+//! [`markup`]: ../index.html
+
+mod html;
+mod markdown;
+mod markdown_v2;
+
+#[cfg(test)]
+mod tests;
+
+use crate::types::message::{
+    text::{Entity, EntityKind},
+    Text,
+};
+
+pub use {html::html, markdown::markdown, markdown_v2::markdown_v2};
+
+/// Accumulates text and entities, counting the text's length in UTF-16
+/// code units.
+#[derive(Debug, Default)]
+struct Builder {
+    value: String,
+    length: usize,
+    entities: Vec<Entity>,
+}
+
+impl Builder {
+    fn push(&mut self, character: char) {
+        self.value.push(character);
+        self.length += character.len_utf16();
+    }
+
+    fn push_str(&mut self, string: &str) {
+        string.chars().for_each(|character| self.push(character));
+    }
+
+    /// Adds an entity spanning from `offset` to the current position.
+    /// Empty entities are dropped, as Telegram does.
+    fn add(&mut self, kind: EntityKind, offset: usize) {
+        if self.length > offset {
+            self.entities.push(Entity {
+                kind,
+                offset,
+                length: self.length - offset,
+            });
+        }
+    }
+
+    fn finish(mut self) -> Text {
+        self.entities.sort_by(|a, b| {
+            a.offset
+                .cmp(&b.offset)
+                .then_with(|| b.length.cmp(&a.length))
+        });
+
+        Text {
+            value: self.value,
+            entities: self.entities,
+        }
+    }
+}
+
+/// Reads the language of a code block starting at `position`, returning it
+/// and the offset where the code starts.
+fn language(markup: &str, position: usize) -> (Option<String>, usize) {
+    let rest = &markup[position..];
+    let line_end = match rest.find('\n') {
+        Some(line_end) => line_end,
+        None => return (None, position),
+    };
+
+    let language = &rest[..line_end];
+    if language.contains(char::is_whitespace) || language.contains('`') {
+        return (None, position);
+    }
+
+    let language = Some(language.to_owned()).filter(|x| !x.is_empty());
+    (language, position + line_end + 1)
+}
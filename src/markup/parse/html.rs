@@ -0,0 +1,272 @@
+use super::Builder;
+use crate::{
+    errors,
+    types::message::{text::EntityKind, Text},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Kind {
+    Bold,
+    Italic,
+    Underline,
+    Strikethrough,
+    Link,
+    Code,
+    Pre,
+}
+
+#[derive(Debug)]
+struct Tag {
+    name: String,
+    kind: Kind,
+    offset: usize,
+    start: usize,
+    argument: Option<String>,
+}
+
+/// Parses Telegram-flavoured HTML.
+pub fn html(markup: &str) -> Result<Text, errors::ParseEntities> {
+    let mut builder = Builder::default();
+    let mut tags: Vec<Tag> = Vec::new();
+    let mut position = 0;
+
+    while let Some(character) = markup[position..].chars().next() {
+        match character {
+            '<' if markup[position + 1..].starts_with('/') => {
+                let (name, end) = end_tag(markup, position)?;
+                let tag = match tags.pop() {
+                    Some(tag) if tag.name == name => tag,
+                    _ => {
+                        return Err(errors::ParseEntities::UnmatchedEndTag {
+                            tag: name,
+                            offset: position,
+                        })
+                    }
+                };
+
+                close(&mut builder, &mut tags, tag);
+                position = end;
+            }
+            '<' => {
+                let (tag, end) = start_tag(markup, position, builder.length)?;
+                tags.push(tag);
+                position = end;
+            }
+            '&' => {
+                let (character, length) =
+                    decode_entity(&markup[position..]).unwrap_or(('&', 1));
+                builder.push(character);
+                position += length;
+            }
+            character => {
+                builder.push(character);
+                position += character.len_utf8();
+            }
+        }
+    }
+
+    match tags.pop() {
+        Some(tag) => Err(errors::ParseEntities::UnclosedTag {
+            tag: tag.name,
+            offset: tag.offset,
+        }),
+        None => Ok(builder.finish()),
+    }
+}
+
+fn close(builder: &mut Builder, tags: &mut [Tag], tag: Tag) {
+    // Telegram doesn't format text inside code.
+    let is_in_code = tags
+        .iter()
+        .any(|tag| matches!(tag.kind, Kind::Code | Kind::Pre));
+
+    let kind = match tag.kind {
+        Kind::Code if tags.last().map(|tag| tag.kind) == Some(Kind::Pre) => {
+            let language = tag.argument.as_deref().and_then(|class| {
+                class.strip_prefix("language-").map(ToOwned::to_owned)
+            });
+            let pre = tags.last_mut().unwrap();
+            if pre.argument.is_none() {
+                pre.argument = language;
+            }
+            return;
+        }
+        _ if is_in_code => return,
+        Kind::Bold => EntityKind::Bold,
+        Kind::Italic => EntityKind::Italic,
+        Kind::Underline => EntityKind::Underline,
+        Kind::Strikethrough => EntityKind::Strikethrough,
+        Kind::Code => EntityKind::Code,
+        Kind::Pre => EntityKind::Pre(tag.argument),
+        Kind::Link => match tag.argument {
+            Some(url) if !url.is_empty() => EntityKind::TextLink(url),
+            _ => return,
+        },
+    };
+
+    builder.add(kind, tag.start);
+}
+
+/// Parses an end tag starting at `offset`, returning its name and the offset
+/// right after it.
+fn end_tag(
+    markup: &str,
+    offset: usize,
+) -> Result<(String, usize), errors::ParseEntities> {
+    let malformed = errors::ParseEntities::MalformedTag { offset };
+    let rest = &markup[offset + 2..];
+    let end = rest.find('>').ok_or_else(|| malformed.clone())?;
+    let name = rest[..end].trim_end();
+
+    if name.is_empty() || !name.chars().all(is_name_character) {
+        return Err(malformed);
+    }
+
+    Ok((name.to_ascii_lowercase(), offset + 2 + end + 1))
+}
+
+/// Parses a start tag starting at `offset`, returning the tag and the offset
+/// right after it.
+fn start_tag(
+    markup: &str,
+    offset: usize,
+    start: usize,
+) -> Result<(Tag, usize), errors::ParseEntities> {
+    let malformed = || errors::ParseEntities::MalformedTag { offset };
+    let mut position = offset + 1;
+
+    let name = take_while(markup, &mut position, is_name_character);
+    if name.is_empty() {
+        return Err(malformed());
+    }
+
+    let name = name.to_ascii_lowercase();
+    let kind = match name.as_str() {
+        "b" | "strong" => Kind::Bold,
+        "i" | "em" => Kind::Italic,
+        "u" | "ins" => Kind::Underline,
+        "s" | "strike" | "del" => Kind::Strikethrough,
+        "a" => Kind::Link,
+        "code" => Kind::Code,
+        "pre" => Kind::Pre,
+        _ => {
+            return Err(errors::ParseEntities::UnsupportedTag {
+                tag: name,
+                offset,
+            })
+        }
+    };
+
+    let mut argument = None;
+
+    loop {
+        take_while(markup, &mut position, char::is_whitespace);
+
+        if markup[position..].starts_with('>') {
+            position += 1;
+            break;
+        }
+
+        let attribute = take_while(markup, &mut position, |character| {
+            is_name_character(character) || character == '_'
+        })
+        .to_ascii_lowercase();
+        if attribute.is_empty() {
+            return Err(malformed());
+        }
+
+        take_while(markup, &mut position, char::is_whitespace);
+        let value = if markup[position..].starts_with('=') {
+            position += 1;
+            take_while(markup, &mut position, char::is_whitespace);
+            attribute_value(markup, &mut position).ok_or_else(malformed)?
+        } else {
+            String::new()
+        };
+
+        match (kind, attribute.as_str()) {
+            (Kind::Link, "href") | (Kind::Code, "class") => {
+                argument = Some(value);
+            }
+            _ => (),
+        }
+    }
+
+    let tag = Tag {
+        name,
+        kind,
+        offset,
+        start,
+        argument,
+    };
+
+    Ok((tag, position))
+}
+
+fn attribute_value(markup: &str, position: &mut usize) -> Option<String> {
+    let rest = &markup[*position..];
+    let raw = match rest.chars().next()? {
+        quote @ '"' | quote @ '\'' => {
+            let end = rest[1..].find(quote)?;
+            *position += end + 2;
+            &rest[1..=end]
+        }
+        _ => take_while(markup, position, |character| {
+            !character.is_whitespace() && character != '>'
+        }),
+    };
+
+    let mut value = String::with_capacity(raw.len());
+    let mut index = 0;
+    while let Some(character) = raw[index..].chars().next() {
+        let (character, length) = match character {
+            '&' => decode_entity(&raw[index..]).unwrap_or(('&', 1)),
+            character => (character, character.len_utf8()),
+        };
+        value.push(character);
+        index += length;
+    }
+
+    Some(value)
+}
+
+/// Decodes an HTML entity at the start of `markup`, returning the character
+/// and the entity's length.
+fn decode_entity(markup: &str) -> Option<(char, usize)> {
+    let end = markup.find(';')?;
+    let name = &markup[1..end];
+
+    let character = match name {
+        "lt" => '<',
+        "gt" => '>',
+        "amp" => '&',
+        "quot" => '"',
+        _ => {
+            let code = name.strip_prefix('#')?;
+            let code = match code.strip_prefix(&['x', 'X'][..]) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => code.parse().ok()?,
+            };
+            std::char::from_u32(code).filter(|&character| character != '\0')?
+        }
+    };
+
+    Some((character, end + 1))
+}
+
+fn take_while<'a>(
+    markup: &'a str,
+    position: &mut usize,
+    predicate: impl Fn(char) -> bool,
+) -> &'a str {
+    let rest = &markup[*position..];
+    let length = rest
+        .find(|character| !predicate(character))
+        .unwrap_or(rest.len());
+    *position += length;
+    &rest[..length]
+}
+
+const fn is_name_character(character: char) -> bool {
+    character.is_ascii_alphanumeric() || character == '-'
+}
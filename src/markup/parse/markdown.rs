@@ -0,0 +1,92 @@
+use super::{language, Builder};
+use crate::{
+    errors,
+    types::message::{text::EntityKind, Text},
+};
+
+/// Characters which can be escaped with `\` outside of entities.
+const ESCAPABLE_CHARACTERS: [char; 4] = ['_', '*', '`', '['];
+
+/// Parses legacy Markdown. Entities can't be nested, and nothing can be
+/// escaped inside them.
+pub fn markdown(markup: &str) -> Result<Text, errors::ParseEntities> {
+    let mut builder = Builder::default();
+    let mut position = 0;
+
+    while let Some(character) = markup[position..].chars().next() {
+        let offset = position;
+        position += character.len_utf8();
+        let rest = &markup[position..];
+        let unclosed = || errors::ParseEntities::UnclosedEntity { offset };
+
+        let (value, kind, end) = match character {
+            '\\' => match rest.chars().next() {
+                Some(escaped) if ESCAPABLE_CHARACTERS.contains(&escaped) => {
+                    builder.push(escaped);
+                    position += 1;
+                    continue;
+                }
+                _ => {
+                    builder.push(character);
+                    continue;
+                }
+            },
+            '*' | '_' => {
+                let end = rest.find(character).ok_or_else(unclosed)?;
+                let kind = if character == '*' {
+                    EntityKind::Bold
+                } else {
+                    EntityKind::Italic
+                };
+                (&rest[..end], kind, position + end + 1)
+            }
+            '`' if rest.starts_with("``") => {
+                let (language, start) = language(markup, position + 2);
+                let end = markup[start..].find("```").ok_or_else(unclosed)?;
+                let code = &markup[start..start + end];
+                let code = code.strip_suffix('\n').unwrap_or(code);
+                (code, EntityKind::Pre(language), start + end + 3)
+            }
+            '`' => {
+                let end = rest.find('`').ok_or_else(unclosed)?;
+                (&rest[..end], EntityKind::Code, position + end + 1)
+            }
+            '[' => {
+                let text_end = rest.find(']').ok_or_else(unclosed)?;
+                let text = &rest[..text_end];
+                let after_text = position + text_end + 1;
+
+                if !markup[after_text..].starts_with('(') {
+                    builder.push_str(text);
+                    position = after_text;
+                    continue;
+                }
+
+                let url_start = after_text + 1;
+                let url_end =
+                    markup[url_start..].find(')').ok_or_else(unclosed)?;
+                let url = markup[url_start..url_start + url_end].to_owned();
+                let end = url_start + url_end + 1;
+
+                if url.is_empty() {
+                    builder.push_str(text);
+                    position = end;
+                    continue;
+                }
+
+                (text, EntityKind::TextLink(url), end)
+            }
+            character => {
+                builder.push(character);
+                continue;
+            }
+        };
+
+        let start = builder.length;
+        builder.push_str(value);
+        builder.add(kind, start);
+        position = end;
+    }
+
+    Ok(builder.finish())
+}
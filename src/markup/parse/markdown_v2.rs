@@ -0,0 +1,204 @@
+use super::{language, Builder};
+use crate::{
+    errors,
+    types::message::{text::EntityKind, Text},
+};
+
+/// Characters which must be escaped unless they're a part of markup.
+const RESERVED_CHARACTERS: [char; 13] = [
+    '(', ')', ']', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!',
+];
+
+/// An entity which is not closed yet. Links are represented with an empty
+/// `TextLink` until their URL is parsed.
+#[derive(Debug)]
+struct Open {
+    kind: EntityKind,
+    offset: usize,
+    start: usize,
+}
+
+/// Parses MarkdownV2.
+pub fn markdown_v2(markup: &str) -> Result<Text, errors::ParseEntities> {
+    let mut builder = Builder::default();
+    let mut opened: Vec<Open> = Vec::new();
+    let mut position = 0;
+
+    while let Some(character) = markup[position..].chars().next() {
+        let offset = position;
+        position += character.len_utf8();
+
+        let kind = match character {
+            '\\' => {
+                let escaped = markup[position..]
+                    .chars()
+                    .next()
+                    .filter(|&character| is_escapable(character))
+                    .ok_or(errors::ParseEntities::ReservedCharacter {
+                        character,
+                        offset,
+                    })?;
+                builder.push(escaped);
+                position += 1;
+                continue;
+            }
+            // Formatters separate adjacent entities with `\r`, and Telegram
+            // removes it from the text.
+            '\r' => continue,
+            '*' => EntityKind::Bold,
+            '_' if markup[position..].starts_with('_') => {
+                position += 1;
+                EntityKind::Underline
+            }
+            '_' => EntityKind::Italic,
+            '~' => EntityKind::Strikethrough,
+            '[' => EntityKind::TextLink(String::new()),
+            ']' => {
+                let open = pop_if(&mut opened, EntityKind::is_text_link)
+                    .ok_or(errors::ParseEntities::ReservedCharacter {
+                        character,
+                        offset,
+                    })?;
+                position = link(&mut builder, markup, position, open.start)?;
+                continue;
+            }
+            '`' if markup[position..].starts_with("``") => {
+                let (language, start) = language(markup, position + 2);
+                let (mut code, end) = code(markup, start, "```")
+                    .ok_or(errors::ParseEntities::UnclosedEntity { offset })?;
+                position = end;
+
+                if code.ends_with('\n') {
+                    code.pop();
+                }
+
+                let entity_start = builder.length;
+                builder.push_str(&code);
+                builder.add(EntityKind::Pre(language), entity_start);
+                continue;
+            }
+            '`' => {
+                let (code, end) = code(markup, position, "`")
+                    .ok_or(errors::ParseEntities::UnclosedEntity { offset })?;
+                position = end;
+
+                let entity_start = builder.length;
+                builder.push_str(&code);
+                builder.add(EntityKind::Code, entity_start);
+                continue;
+            }
+            character if RESERVED_CHARACTERS.contains(&character) => {
+                return Err(errors::ParseEntities::ReservedCharacter {
+                    character,
+                    offset,
+                });
+            }
+            character => {
+                builder.push(character);
+                continue;
+            }
+        };
+
+        let closed = if kind.is_text_link() {
+            None
+        } else {
+            pop_if(&mut opened, |open| *open == kind)
+        };
+
+        if let Some(open) = closed {
+            builder.add(kind, open.start);
+        } else if let Some(open) = opened.iter().find(|open| open.kind == kind)
+        {
+            // Entities of the same kind can't be nested.
+            return Err(errors::ParseEntities::UnclosedEntity {
+                offset: open.offset,
+            });
+        } else {
+            opened.push(Open {
+                kind,
+                offset,
+                start: builder.length,
+            });
+        }
+    }
+
+    match opened.pop() {
+        Some(open) => Err(errors::ParseEntities::UnclosedEntity {
+            offset: open.offset,
+        }),
+        None => Ok(builder.finish()),
+    }
+}
+
+/// Pops the innermost entity if its kind matches the predicate.
+fn pop_if(
+    opened: &mut Vec<Open>,
+    predicate: impl FnOnce(&EntityKind) -> bool,
+) -> Option<Open> {
+    if opened.last().map_or(false, |open| predicate(&open.kind)) {
+        opened.pop()
+    } else {
+        None
+    }
+}
+
+/// Finishes a link whose text ends right before `position`, returning
+/// the offset right after the link's URL.
+fn link(
+    builder: &mut Builder,
+    markup: &str,
+    position: usize,
+    start: usize,
+) -> Result<usize, errors::ParseEntities> {
+    if !markup[position..].starts_with('(') {
+        return Ok(position);
+    }
+
+    let (url, end) = code(markup, position + 1, ")")
+        .ok_or(errors::ParseEntities::UnclosedEntity { offset: position })?;
+    if !url.is_empty() {
+        builder.add(EntityKind::TextLink(url), start);
+    }
+
+    Ok(end)
+}
+
+/// Reads code or a URL starting at `position` until the unescaped `end`,
+/// returning the unescaped content and the offset right after `end`.
+fn code(
+    markup: &str,
+    mut position: usize,
+    end: &str,
+) -> Option<(String, usize)> {
+    let mut content = String::new();
+
+    loop {
+        let rest = &markup[position..];
+        if rest.starts_with(end) {
+            return Some((content, position + end.len()));
+        }
+
+        let mut characters = rest.chars();
+        match characters.next()? {
+            '\\' => match characters.next() {
+                Some(escaped) if is_escapable(escaped) => {
+                    content.push(escaped);
+                    position += 2;
+                }
+                _ => {
+                    content.push('\\');
+                    position += 1;
+                }
+            },
+            character => {
+                content.push(character);
+                position += character.len_utf8();
+            }
+        }
+    }
+}
+
+/// Checks if the character can be escaped with `\`.
+fn is_escapable(character: char) -> bool {
+    ('\u{1}'..='\u{7e}').contains(&character)
+}
@@ -0,0 +1,148 @@
+#![allow(clippy::non_ascii_literal)]
+
+use super::{html, markdown, markdown_v2, Entity, EntityKind};
+use crate::{
+    errors,
+    markup::{self, bold, code_block, italic, link, underline, Formattable},
+    types::parameters::Text,
+};
+
+fn entity(kind: EntityKind, offset: usize, length: usize) -> Entity {
+    Entity {
+        kind,
+        offset,
+        length,
+    }
+}
+
+#[test]
+fn parses_html() {
+    let text = html(
+        "🦀 <b>bold <I>both</I></b> &lt;&#x1F980;&gt; \
+         <a href='https://example.com/?a=1&amp;b=2'>link</a>\n\
+         <pre><code class=\"language-rust\">let x = 1 &lt; 2;</code></pre>",
+    )
+    .unwrap();
+
+    assert_eq!(text.value, "🦀 bold both <🦀> link\nlet x = 1 < 2;");
+    assert_eq!(
+        text.entities,
+        [
+            entity(EntityKind::Bold, 3, 9),
+            entity(EntityKind::Italic, 8, 4),
+            entity(
+                EntityKind::TextLink("https://example.com/?a=1&b=2".into()),
+                18,
+                4,
+            ),
+            entity(EntityKind::Pre(Some("rust".into())), 23, 14),
+        ],
+    );
+
+    assert_eq!(
+        html("<b>a</i>"),
+        Err(errors::ParseEntities::UnmatchedEndTag {
+            tag: "i".into(),
+            offset: 4,
+        }),
+    );
+    assert_eq!(
+        html("<br>"),
+        Err(errors::ParseEntities::UnsupportedTag {
+            tag: "br".into(),
+            offset: 0,
+        }),
+    );
+    assert_eq!(
+        html("a <b>b"),
+        Err(errors::ParseEntities::UnclosedTag {
+            tag: "b".into(),
+            offset: 2,
+        }),
+    );
+}
+
+#[test]
+fn parses_markdown_v2() {
+    let text = markdown_v2(
+        "*bold _both_* __under__ [a\\]b](https://example.com/(x\\)) \
+         `co\\`de` \\!\n```rust\nfn main() {}\n```",
+    )
+    .unwrap();
+
+    assert_eq!(text.value, "bold both under a]b co`de !\nfn main() {}");
+    assert_eq!(
+        text.entities,
+        [
+            entity(EntityKind::Bold, 0, 9),
+            entity(EntityKind::Italic, 5, 4),
+            entity(EntityKind::Underline, 10, 5),
+            entity(
+                EntityKind::TextLink("https://example.com/(x)".into()),
+                16,
+                3
+            ),
+            entity(EntityKind::Code, 20, 5),
+            entity(EntityKind::Pre(Some("rust".into())), 28, 12),
+        ],
+    );
+
+    assert_eq!(
+        markdown_v2("Hello, world!"),
+        Err(errors::ParseEntities::ReservedCharacter {
+            character: '!',
+            offset: 12,
+        }),
+    );
+    assert_eq!(
+        markdown_v2("a *b _c* d_"),
+        Err(errors::ParseEntities::UnclosedEntity { offset: 2 }),
+    );
+    assert_eq!(
+        markdown_v2("`code"),
+        Err(errors::ParseEntities::UnclosedEntity { offset: 0 }),
+    );
+}
+
+#[test]
+fn parses_markdown() {
+    let text =
+        markdown("*bold* _it_ \\*not\\* [link](tg://user?id=1)").unwrap();
+
+    assert_eq!(text.value, "bold it *not* link");
+    assert_eq!(
+        text.entities,
+        [
+            entity(EntityKind::Bold, 0, 4),
+            entity(EntityKind::Italic, 5, 2),
+            entity(EntityKind::TextLink("tg://user?id=1".into()), 14, 4),
+        ],
+    );
+
+    assert_eq!(
+        markdown("*bold"),
+        Err(errors::ParseEntities::UnclosedEntity { offset: 0 }),
+    );
+}
+
+#[test]
+fn parses_formatters_output() {
+    fn markup() -> impl Formattable {
+        (
+            bold(("a*b ", italic("c_d"))),
+            underline(italic("e")),
+            " ",
+            link("[f]", "https://example.com/(g)"),
+            code_block("h`i").language("rust"),
+        )
+    }
+    let expected = "a*b c_de [f]h`i";
+
+    let parsed = Text::from(markup::markdown_v2(markup())).parse().unwrap();
+    assert_eq!(parsed.value, expected);
+    assert_eq!(parsed.entities.len(), 6);
+
+    let parsed = Text::from(markup::html(markup())).parse().unwrap();
+    assert_eq!(parsed.value, expected);
+    assert_eq!(parsed.entities.len(), 6);
+}
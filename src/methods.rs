@@ -52,10 +52,12 @@ mod answer_pre_checkout_query;
 mod answer_shipping_query;
 mod close;
 mod copy_message;
+mod create_invoice_link;
 mod create_new_sticker_set;
 mod delete_chat_photo;
 mod delete_chat_sticker_set;
 mod delete_message;
+mod delete_my_commands;
 mod delete_sticker_from_set;
 mod delete_webhook;
 mod edit_inline_caption;
@@ -134,10 +136,12 @@ pub use answer_inline_query::AnswerInlineQuery;
 pub use answer_pre_checkout_query::AnswerPreCheckoutQuery;
 pub use answer_shipping_query::AnswerShippingQuery;
 pub use copy_message::CopyMessage;
+pub use create_invoice_link::CreateInvoiceLink;
 pub use create_new_sticker_set::CreateNewStickerSet;
 pub use delete_chat_photo::DeleteChatPhoto;
 pub use delete_chat_sticker_set::DeleteChatStickerSet;
 pub use delete_message::DeleteMessage;
+pub use delete_my_commands::DeleteMyCommands;
 pub use delete_sticker_from_set::DeleteStickerFromSet;
 pub use edit_inline_caption::EditInlineCaption;
 pub use edit_inline_location::EditInlineLocation;
@@ -69,6 +69,9 @@ impl AddStickerToSet<'_> {
             StickerForStickerSet::Tgs(sticker) => {
                 ("tgs_sticker", sticker.media)
             }
+            StickerForStickerSet::Webm(sticker) => {
+                ("webm_sticker", sticker.media)
+            }
         };
 
         match &media {
@@ -0,0 +1,40 @@
+use super::call_method;
+use crate::{bot::InnerBot, errors, types::parameters::Invoice};
+use serde::Serialize;
+
+/// Creates a link for an invoice.
+///
+/// Reflects the [`createInvoiceLink`][docs] method.
+///
+/// The invoice is not validated before sending; call [`Invoice::validate`]
+/// yourself to catch mistakes before Telegram rejects the invoice.
+///
+/// [docs]: https://core.telegram.org/bots/api#createinvoicelink
+/// [`Invoice::validate`]: ../types/parameters/struct.Invoice.html#method.validate
+#[derive(Debug, Clone, Serialize)]
+#[must_use = "methods do nothing unless turned into a future"]
+pub struct CreateInvoiceLink<'a> {
+    #[serde(skip)]
+    bot: &'a InnerBot,
+    #[serde(flatten)]
+    invoice: Invoice<'a>,
+}
+
+impl<'a> CreateInvoiceLink<'a> {
+    pub(crate) const fn new(bot: &'a InnerBot, invoice: Invoice<'a>) -> Self {
+        Self { bot, invoice }
+    }
+}
+
+impl CreateInvoiceLink<'_> {
+    /// Calls the method.
+    pub async fn call(self) -> Result<String, errors::MethodCall> {
+        call_method(
+            self.bot,
+            "createInvoiceLink",
+            None,
+            serde_json::to_vec(&self).unwrap(),
+        )
+        .await
+    }
+}
@@ -84,6 +84,9 @@ impl CreateNewStickerSet<'_> {
             StickerForStickerSet::Tgs(sticker) => {
                 ("tgs_sticker", sticker.media)
             }
+            StickerForStickerSet::Webm(sticker) => {
+                ("webm_sticker", sticker.media)
+            }
         };
 
         match &media {
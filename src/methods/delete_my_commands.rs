@@ -0,0 +1,62 @@
+use super::call_method;
+use crate::{bot::InnerBot, errors, types::parameters::BotCommandScope};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Deletes the list of the bot's commands.
+///
+/// Represents the [`deleteMyCommands`][docs] method.
+///
+/// [docs]: https://core.telegram.org/bots/api#deletemycommands
+#[derive(Serialize, Debug, Clone)]
+#[must_use = "methods do nothing unless turned into a future"]
+pub struct DeleteMyCommands<'a> {
+    #[serde(skip)]
+    bot: &'a InnerBot,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<BotCommandScope<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_code: Option<Cow<'a, str>>,
+}
+
+impl<'a> DeleteMyCommands<'a> {
+    pub(crate) const fn new(bot: &'a InnerBot) -> Self {
+        Self {
+            bot,
+            scope: None,
+            language_code: None,
+        }
+    }
+
+    /// Configures for which users the commands are deleted.
+    /// Reflects the `scope` parameter.
+    pub fn scope(mut self, scope: BotCommandScope<'a>) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Configures for users with which language the commands are deleted.
+    /// Reflects the `language_code` parameter.
+    pub fn language_code(
+        mut self,
+        language_code: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.language_code = Some(language_code.into());
+        self
+    }
+}
+
+impl DeleteMyCommands<'_> {
+    /// Calls the method.
+    pub async fn call(self) -> Result<(), errors::MethodCall> {
+        call_method::<bool>(
+            self.bot,
+            "deleteMyCommands",
+            None,
+            serde_json::to_vec(&self).unwrap(),
+        )
+        .await?;
+
+        Ok(())
+    }
+}
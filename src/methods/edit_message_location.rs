@@ -25,6 +25,12 @@ pub struct EditMessageLocation<'a> {
     latitude: f64,
     longitude: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
+    horizontal_accuracy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heading: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proximity_alert_radius: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     reply_markup: Option<inline::Keyboard<'a>>,
 }
 
@@ -41,10 +47,35 @@ impl<'a> EditMessageLocation<'a> {
             message_id,
             latitude,
             longitude,
+            horizontal_accuracy: None,
+            heading: None,
+            proximity_alert_radius: None,
             reply_markup: None,
         }
     }
 
+    /// Configures the radius of uncertainty for the location, in meters,
+    /// in range `0..=1500`. Reflects the `horizontal_accuracy` parameter.
+    pub const fn horizontal_accuracy(mut self, accuracy: f64) -> Self {
+        self.horizontal_accuracy = Some(accuracy);
+        self
+    }
+
+    /// Configures the direction in which the user is moving, in degrees
+    /// and in range `1..=360`. Reflects the `heading` parameter.
+    pub const fn heading(mut self, heading: u16) -> Self {
+        self.heading = Some(heading);
+        self
+    }
+
+    /// Configures the maximum distance for proximity alerts about
+    /// approaching another chat member, in meters.
+    /// Reflects the `proximity_alert_radius` parameter.
+    pub const fn proximity_alert_radius(mut self, radius: u32) -> Self {
+        self.proximity_alert_radius = Some(radius);
+        self
+    }
+
     /// Configures an inline keyboard for the message.
     /// Reflects the `reply_markup` parameter.
     pub const fn reply_markup(mut self, markup: inline::Keyboard<'a>) -> Self {
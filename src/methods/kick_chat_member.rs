@@ -58,6 +58,10 @@ impl KickChatMember<'_> {
         )
         .await?;
 
+        if let Some(registry) = self.bot.admin_registry() {
+            registry.invalidate_chat_id(&self.chat_id);
+        }
+
         Ok(())
     }
 }
@@ -138,6 +138,10 @@ impl PromoteChatMember<'_> {
         )
         .await?;
 
+        if let Some(registry) = self.bot.admin_registry() {
+            registry.invalidate_chat_id(&self.chat_id);
+        }
+
         Ok(())
     }
 }
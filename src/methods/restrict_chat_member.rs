@@ -62,6 +62,10 @@ impl RestrictChatMember<'_> {
         )
         .await?;
 
+        if let Some(registry) = self.bot.admin_registry() {
+            registry.invalidate_chat_id(&self.chat_id);
+        }
+
         Ok(())
     }
 }
@@ -6,7 +6,7 @@ use crate::{
         chat,
         keyboard::inline,
         message::{self, Message},
-        parameters::Photo,
+        parameters::{Invoice, Photo},
         LabeledPrice,
     },
 };
@@ -17,38 +17,21 @@ use std::borrow::Cow;
 ///
 /// Reflects the [`sendInvoice`][docs] method.
 ///
+/// The invoice is not validated before sending; call [`Invoice::validate`]
+/// yourself to catch mistakes before Telegram rejects the invoice.
+///
 /// [docs]: https://core.telegram.org/bots/api#sendinvoice
+/// [`Invoice::validate`]: ../types/parameters/struct.Invoice.html#method.validate
 #[derive(Debug, Clone, Serialize)]
 #[must_use = "methods do nothing unless turned into a future"]
 pub struct SendInvoice<'a> {
     #[serde(skip)]
     bot: &'a InnerBot,
     chat_id: chat::Id,
-    title: Cow<'a, str>,
-    description: Cow<'a, str>,
-    payload: Cow<'a, str>,
-    provider_token: Cow<'a, str>,
-    start_parameter: Cow<'a, str>,
-    currency: Cow<'a, str>,
-    prices: Cow<'a, [LabeledPrice<'a>]>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    provider_data: Option<Cow<'a, str>>,
-    #[serde(skip_serializing_if = "Option::is_none", flatten)]
-    photo: Option<Photo<'a>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    need_name: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    need_phone_number: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    need_email: Option<bool>,
+    #[serde(flatten)]
+    invoice: Invoice<'a>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    need_shipping_address: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    send_phone_number_to_provider: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    send_email_to_provider: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    is_flexible: Option<bool>,
+    start_parameter: Option<Cow<'a, str>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -69,39 +52,48 @@ impl<'a> SendInvoice<'a> {
         start_parameter: impl Into<Cow<'a, str>>,
         currency: impl Into<Cow<'a, str>>,
         prices: impl Into<Cow<'a, [LabeledPrice<'a>]>>,
+    ) -> Self {
+        let invoice =
+            Invoice::new(title, description, payload, currency, prices)
+                .provider_token(provider_token);
+
+        Self::with_invoice(bot, chat_id, invoice)
+            .start_parameter(start_parameter)
+    }
+
+    pub(crate) fn with_invoice(
+        bot: &'a InnerBot,
+        chat_id: impl Into<chat::Id>,
+        invoice: Invoice<'a>,
     ) -> Self {
         Self {
             bot,
             chat_id: chat_id.into(),
-            title: title.into(),
-            description: description.into(),
-            payload: payload.into(),
-            provider_token: provider_token.into(),
-            start_parameter: start_parameter.into(),
-            currency: currency.into(),
-            prices: prices.into(),
-            provider_data: None,
-            photo: None,
-            need_name: None,
-            need_phone_number: None,
-            need_email: None,
-            need_shipping_address: None,
-            send_phone_number_to_provider: None,
-            send_email_to_provider: None,
-            is_flexible: None,
+            invoice,
+            start_parameter: None,
             disable_notification: None,
             reply_to_message_id: None,
             reply_markup: None,
         }
     }
 
+    /// Configures the start parameter for the deep link used when the invoice
+    /// is forwarded. Reflects the `start_parameter` parameter.
+    pub fn start_parameter(
+        mut self,
+        start_parameter: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.start_parameter = Some(start_parameter.into());
+        self
+    }
+
     /// Configures data for your payment provider.
     /// Reflects the `provider_data` parameter.
     pub fn provider_data(
         mut self,
         provider_data: impl Into<Cow<'a, str>>,
     ) -> Self {
-        self.provider_data = Some(provider_data.into());
+        self.invoice.provider_data = Some(provider_data.into());
         self
     }
 
@@ -109,35 +101,35 @@ impl<'a> SendInvoice<'a> {
     /// Reflects the `photo_url`, `photo_width` and `photo_height` parameters.
     #[allow(clippy::missing_const_for_fn)]
     pub fn photo(mut self, photo: Photo<'a>) -> Self {
-        self.photo = Some(photo);
+        self.invoice.photo = Some(photo);
         self
     }
 
     /// Configures whether the user must specify their name.
     /// Reflects the `need_name` parameters.
     pub const fn is_name_needed(mut self, is_needed: bool) -> Self {
-        self.need_name = Some(is_needed);
+        self.invoice.need_name = Some(is_needed);
         self
     }
 
     /// Configures whether the user must specify their phone number.
     /// Reflects the `need_phone_number` parameter.
     pub const fn is_phone_number_needed(mut self, is_needed: bool) -> Self {
-        self.need_phone_number = Some(is_needed);
+        self.invoice.need_phone_number = Some(is_needed);
         self
     }
 
     /// Configures whether the user must specify their email.
     /// Reflects the `need_email` parameter.
     pub const fn is_email_needed(mut self, is_needed: bool) -> Self {
-        self.need_email = Some(is_needed);
+        self.invoice.need_email = Some(is_needed);
         self
     }
 
     /// Configures whether the user must specify their shipping address.
     /// Reflects the `need_shipping_address` parameter.
     pub const fn is_shipping_address_needed(mut self, is_needed: bool) -> Self {
-        self.need_shipping_address = Some(is_needed);
+        self.invoice.need_shipping_address = Some(is_needed);
         self
     }
 
@@ -147,7 +139,7 @@ impl<'a> SendInvoice<'a> {
         mut self,
         must_send: bool,
     ) -> Self {
-        self.send_phone_number_to_provider = Some(must_send);
+        self.invoice.send_phone_number_to_provider = Some(must_send);
         self
     }
 
@@ -157,14 +149,14 @@ impl<'a> SendInvoice<'a> {
         mut self,
         must_send: bool,
     ) -> Self {
-        self.send_email_to_provider = Some(must_send);
+        self.invoice.send_email_to_provider = Some(must_send);
         self
     }
 
     /// Configures whether the final price depends on the shipping method.
     /// Reflects the `is_flexible` parameter.
     pub const fn is_flexible(mut self, is_flexible: bool) -> Self {
-        self.is_flexible = Some(is_flexible);
+        self.invoice.is_flexible = Some(is_flexible);
         self
     }
 
@@ -26,6 +26,12 @@ pub struct SendLocation<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     live_period: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    horizontal_accuracy: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    heading: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proximity_alert_radius: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     disable_notification: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     reply_to_message_id: Option<message::Id>,
@@ -45,6 +51,9 @@ impl<'a> SendLocation<'a> {
             latitude,
             longitude,
             live_period: None,
+            horizontal_accuracy: None,
+            heading: None,
+            proximity_alert_radius: None,
             disable_notification: None,
             reply_to_message_id: None,
             reply_markup: None,
@@ -58,6 +67,28 @@ impl<'a> SendLocation<'a> {
         self
     }
 
+    /// Configures the radius of uncertainty for the location, in meters,
+    /// in range `0..=1500`. Reflects the `horizontal_accuracy` parameter.
+    pub const fn horizontal_accuracy(mut self, accuracy: f64) -> Self {
+        self.horizontal_accuracy = Some(accuracy);
+        self
+    }
+
+    /// Configures the direction in which the user is moving, in degrees
+    /// and in range `1..=360`. Reflects the `heading` parameter.
+    pub const fn heading(mut self, heading: u16) -> Self {
+        self.heading = Some(heading);
+        self
+    }
+
+    /// Configures the maximum distance for proximity alerts about
+    /// approaching another chat member, in meters.
+    /// Reflects the `proximity_alert_radius` parameter.
+    pub const fn proximity_alert_radius(mut self, radius: u32) -> Self {
+        self.proximity_alert_radius = Some(radius);
+        self
+    }
+
     /// Configures whether the message is sent silently.
     /// Reflects the `disable_notification` parameter.
     pub const fn is_notification_disabled(mut self, is_disabled: bool) -> Self {
@@ -52,6 +52,10 @@ impl SetChatAdministratorCustomTitle<'_> {
         )
         .await?;
 
+        if let Some(registry) = self.bot.admin_registry() {
+            registry.invalidate_chat_id(&self.chat_id);
+        }
+
         Ok(())
     }
 }
@@ -1,5 +1,9 @@
 use super::call_method;
-use crate::{bot::InnerBot, errors, types::parameters::BotCommand};
+use crate::{
+    bot::InnerBot,
+    errors,
+    types::parameters::{BotCommand, BotCommandScope},
+};
 use serde::Serialize;
 use std::borrow::Cow;
 
@@ -14,6 +18,10 @@ pub struct SetMyCommands<'a> {
     #[serde(skip)]
     bot: &'a InnerBot,
     commands: Cow<'a, [BotCommand<'a>]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scope: Option<BotCommandScope<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language_code: Option<Cow<'a, str>>,
 }
 
 impl<'a> SetMyCommands<'a> {
@@ -24,8 +32,27 @@ impl<'a> SetMyCommands<'a> {
         Self {
             bot,
             commands: commands.into(),
+            scope: None,
+            language_code: None,
         }
     }
+
+    /// Configures for which users the commands are shown.
+    /// Reflects the `scope` parameter.
+    pub fn scope(mut self, scope: BotCommandScope<'a>) -> Self {
+        self.scope = Some(scope);
+        self
+    }
+
+    /// Configures for users with which language the commands are shown.
+    /// Reflects the `language_code` parameter.
+    pub fn language_code(
+        mut self,
+        language_code: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.language_code = Some(language_code.into());
+        self
+    }
 }
 
 impl SetMyCommands<'_> {
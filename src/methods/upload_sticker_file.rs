@@ -11,6 +11,12 @@ use std::borrow::Cow;
 ///
 /// Reflects the [`uploadStickerFile`][docs] method.
 ///
+/// tbot follows Bot API 5.7 for stickers, where only PNG stickers can be
+/// uploaded in advance. Animated and video stickers are uploaded directly
+/// with [`CreateNewStickerSet`] and [`AddStickerToSet`].
+///
+/// [`CreateNewStickerSet`]: ./struct.CreateNewStickerSet.html
+/// [`AddStickerToSet`]: ./struct.AddStickerToSet.html
 /// [docs]: https://core.telegram.org/bots/api#uploadstickerfile
 #[derive(Debug, Clone)]
 #[must_use = "methods do nothing unless turned into a future"]
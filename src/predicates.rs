@@ -7,6 +7,7 @@
 pub mod chat;
 pub mod media;
 pub mod message;
+pub mod text;
 mod traits;
 
 use futures::{future::BoxFuture, Future};
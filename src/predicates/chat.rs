@@ -1,7 +1,24 @@
 //! A few useful predicates for chats.
 
-use crate::contexts::fields::Message;
-use std::sync::Arc;
+use crate::{
+    contexts::fields::Message,
+    types::{
+        chat::{self, member::Status},
+        message::From,
+        user,
+    },
+    util::admins,
+    Bot,
+};
+use futures::future::BoxFuture;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// How long the list of a chat's administrators is cached for.
+const ADMINISTRATORS_TTL: Duration = Duration::from_secs(5 * 60);
 
 /// Checks if the message is from a private chat.
 pub async fn is_private(context: Arc<impl Message>) -> bool {
@@ -22,3 +39,178 @@ pub async fn is_supergroup(context: Arc<impl Message>) -> bool {
 pub async fn is_channel(context: Arc<impl Message>) -> bool {
     context.chat().kind.is_channel()
 }
+
+/// Represents rights of chat administrators.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum AdminRight {
+    /// The admin can change the chat's info.
+    ChangeInfo,
+    /// The admin can post messages (channels only).
+    PostMessages,
+    /// The admin can edit messages of others (channels only).
+    EditMessages,
+    /// The admin can delete messages of others.
+    DeleteMessages,
+    /// The admin can invite users.
+    InviteUsers,
+    /// The admin can restrict members.
+    RestrictMembers,
+    /// The admin can pin messages.
+    PinMessages,
+    /// The admin can promote members.
+    PromoteMembers,
+}
+
+impl AdminRight {
+    /// Checks if a member with this status has the right. The creator
+    /// of the chat has all rights.
+    #[must_use]
+    pub fn is_granted(self, status: &Status) -> bool {
+        match status {
+            Status::Creator { .. } => true,
+            Status::Administrator {
+                can_change_info,
+                can_post_messages,
+                can_edit_messages,
+                can_delete_messages,
+                can_invite_users,
+                can_restrict_members,
+                can_pin_messages,
+                can_promote_members,
+                ..
+            } => match self {
+                Self::ChangeInfo => *can_change_info,
+                Self::PostMessages => *can_post_messages == Some(true),
+                Self::EditMessages => *can_edit_messages == Some(true),
+                Self::DeleteMessages => *can_delete_messages,
+                Self::InviteUsers => *can_invite_users,
+                Self::RestrictMembers => *can_restrict_members,
+                Self::PinMessages => *can_pin_messages == Some(true),
+                Self::PromoteMembers => *can_promote_members,
+            },
+            _ => false,
+        }
+    }
+}
+
+type Chats = HashMap<chat::Id, (Instant, Arc<[chat::Member]>)>;
+
+/// Caches administrators of chats for `ADMINISTRATORS_TTL`.
+#[derive(Default)]
+struct Administrators {
+    chats: Mutex<Chats>,
+}
+
+impl Administrators {
+    fn lock(&self) -> MutexGuard<'_, Chats> {
+        self.chats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn get(
+        &self,
+        bot: &Bot,
+        chat_id: chat::Id,
+    ) -> Option<Arc<[chat::Member]>> {
+        if bot.admin_registry().is_some() {
+            return admins::administrators(bot, chat_id).await.ok();
+        }
+
+        let cached = self
+            .lock()
+            .get(&chat_id)
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < ADMINISTRATORS_TTL)
+            .map(|(_, administrators)| Arc::clone(administrators));
+        if cached.is_some() {
+            return cached;
+        }
+
+        let administrators: Arc<[chat::Member]> = bot
+            .get_chat_administrators(chat_id)
+            .call()
+            .await
+            .ok()?
+            .into();
+        self.lock()
+            .insert(chat_id, (Instant::now(), Arc::clone(&administrators)));
+
+        Some(administrators)
+    }
+
+    /// Finds the status of the message's author among the chat's
+    /// administrators.
+    async fn status_of<C: Message>(&self, context: &C) -> Option<Status> {
+        let chat = context.chat();
+        if chat.kind.is_private() {
+            return None;
+        }
+
+        let user_id: user::Id = match context.from()? {
+            From::User(user) => user.id,
+            From::Chat(..) => return None,
+        };
+
+        self.get(context.bot(), chat.id)
+            .await?
+            .iter()
+            .find(|member| member.user.id == user_id)
+            .map(|member| member.status.clone())
+    }
+}
+
+/// Checks if the message's author is an administrator or the creator
+/// of the chat. Messages sent on behalf of the chat itself, e.g. by
+/// anonymous admins, are also considered to be sent by an admin.
+///
+/// The list of administrators is taken from the bot's [registry] if it's
+/// enabled. Otherwise, it's requested with `getChatAdministrators` and cached
+/// by the predicate for five minutes.
+///
+/// [registry]: ../../util/admins/index.html
+pub fn is_chat_admin<'a, C>(
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: Message + Send + Sync + 'a,
+{
+    let administrators = Arc::new(Administrators::default());
+
+    move |context: Arc<C>| {
+        let administrators = Arc::clone(&administrators);
+
+        Box::pin(async move {
+            if let Some(From::Chat(chat)) = context.from() {
+                return chat.id == context.chat().id;
+            }
+
+            administrators.status_of(&*context).await.is_some()
+        })
+    }
+}
+
+/// Checks if the message's author is an administrator with the given right.
+/// The creator of the chat has all rights.
+///
+/// The list of administrators is taken from the bot's [registry] if it's
+/// enabled. Otherwise, it's requested with `getChatAdministrators` and cached
+/// by the predicate for five minutes.
+///
+/// [registry]: ../../util/admins/index.html
+pub fn has_admin_right<'a, C>(
+    right: AdminRight,
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: Message + Send + Sync + 'a,
+{
+    let administrators = Arc::new(Administrators::default());
+
+    move |context: Arc<C>| {
+        let administrators = Arc::clone(&administrators);
+
+        Box::pin(async move {
+            administrators
+                .status_of(&*context)
+                .await
+                .map_or(false, |status| right.is_granted(&status))
+        })
+    }
+}
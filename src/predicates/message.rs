@@ -1,7 +1,16 @@
 //! A few useful predicates for messages.
 
-use crate::contexts::fields::{Forward, MediaMessage};
-use std::sync::Arc;
+use crate::{
+    contexts::fields::{Album, Forward, MediaMessage, Message},
+    types::{message::From, user},
+};
+use futures::future::BoxFuture;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    hash::Hash,
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 /// Checks if the message replies to another message.
 pub async fn is_in_reply(context: Arc<impl MediaMessage>) -> bool {
@@ -12,3 +21,124 @@ pub async fn is_in_reply(context: Arc<impl MediaMessage>) -> bool {
 pub async fn is_forwarded(context: Arc<impl Forward>) -> bool {
     context.forward().is_some()
 }
+
+/// Checks if the message is a part of an album.
+pub async fn has_media_group(context: Arc<impl Album>) -> bool {
+    context.media_group_id().is_some()
+}
+
+/// Checks if the message is sent by a bot.
+pub async fn is_from_bot(context: Arc<impl Message>) -> bool {
+    matches!(context.from(), Some(From::User(user)) if user.is_bot)
+}
+
+/// Checks if the message is sent by one of the given users.
+pub fn from_user_in<'a, C>(
+    ids: &[user::Id],
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: Message + Send + Sync + 'a,
+{
+    let ids: Arc<HashSet<user::Id>> = Arc::new(ids.iter().copied().collect());
+
+    move |context: Arc<C>| {
+        let ids = Arc::clone(&ids);
+
+        Box::pin(async move {
+            matches!(context.from(), Some(From::User(user)) if ids.contains(&user.id))
+        })
+    }
+}
+
+/// Counts messages of each sender in a sliding window.
+pub(crate) struct RateLimiter<K = i64> {
+    limit: usize,
+    window: Duration,
+    senders: Mutex<HashMap<K, VecDeque<Instant>>>,
+}
+
+impl<K: Eq + Hash> RateLimiter<K> {
+    pub(crate) fn new(limit: usize, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            senders: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records the message if the sender hasn't reached the limit yet.
+    pub(crate) fn try_acquire(&self, sender: K, now: Instant) -> bool {
+        let mut senders =
+            self.senders.lock().unwrap_or_else(PoisonError::into_inner);
+        let is_recent =
+            |&time: &Instant| now.duration_since(time) < self.window;
+
+        senders.retain(|_, times| times.back().map_or(false, is_recent));
+
+        let times = senders.entry(sender).or_default();
+        while times.front().map_or(false, |time| !is_recent(time)) {
+            times.pop_front();
+        }
+
+        let is_allowed = times.len() < self.limit;
+        if is_allowed {
+            times.push_back(now);
+        }
+        drop(senders);
+
+        is_allowed
+    }
+}
+
+/// Lets through at most `per_user` messages from each sender
+/// in `per_duration`.
+///
+/// Messages above the limit are rejected and don't count towards it,
+/// so a sender who keeps flooding gets through again as soon as the window
+/// allows.
+///
+/// Messages sent on behalf of chats are limited per sender chat.
+pub fn rate_limited<'a, C>(
+    per_user: usize,
+    per_duration: Duration,
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: Message + Send + Sync + 'a,
+{
+    let limiter: Arc<RateLimiter> =
+        Arc::new(RateLimiter::new(per_user, per_duration));
+
+    move |context: Arc<C>| {
+        let limiter = Arc::clone(&limiter);
+
+        Box::pin(async move {
+            let sender = match context.from() {
+                Some(From::User(user)) => user.id.0,
+                Some(From::Chat(chat)) => chat.id.0,
+                None => context.chat().id.0,
+            };
+
+            limiter.try_acquire(sender, Instant::now())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rate_limiter_uses_sliding_window() {
+        let limiter: RateLimiter = RateLimiter::new(2, Duration::from_secs(10));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(1, start));
+        assert!(limiter.try_acquire(1, start + Duration::from_secs(4)));
+        assert!(!limiter.try_acquire(1, start + Duration::from_secs(5)));
+        assert!(limiter.try_acquire(2, start + Duration::from_secs(5)));
+
+        assert!(limiter.try_acquire(1, start + Duration::from_secs(10)));
+        assert!(!limiter.try_acquire(1, start + Duration::from_secs(13)));
+        assert!(limiter.try_acquire(1, start + Duration::from_secs(14)));
+    }
+}
@@ -0,0 +1,100 @@
+//! A few useful predicates for texts and captions.
+
+use crate::{
+    contexts::fields::AnyText,
+    types::{
+        message::text::{EntityKind, Text},
+        User,
+    },
+};
+use futures::future::BoxFuture;
+use regex::Regex;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Checks if the text or the caption matches the regular expression.
+pub fn text_matches<'a, C>(
+    regex: Regex,
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: AnyText + Send + Sync + 'a,
+{
+    let regex = Arc::new(regex);
+
+    move |context: Arc<C>| {
+        let regex = Arc::clone(&regex);
+        Box::pin(async move { regex.is_match(&context.text().value) })
+    }
+}
+
+/// Checks if the text or the caption starts with the prefix.
+pub fn text_starts_with<'a, C>(
+    prefix: impl Into<String>,
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: AnyText + Send + Sync + 'a,
+{
+    let prefix: Arc<str> = prefix.into().into();
+
+    move |context: Arc<C>| {
+        let prefix = Arc::clone(&prefix);
+        Box::pin(async move { context.text().value.starts_with(&*prefix) })
+    }
+}
+
+/// Checks if the text or the caption mentions the bot, either by its
+/// username or with a text mention.
+///
+/// The bot's username is requested with `getMe` when the predicate is first
+/// run and cached afterwards. If the request fails, the predicate returns
+/// `false`.
+pub fn contains_mention_of_bot<'a, C>(
+) -> impl Fn(Arc<C>) -> BoxFuture<'a, bool> + Send + Sync + 'a
+where
+    C: AnyText + Send + Sync + 'a,
+{
+    let me: Arc<Mutex<Option<User>>> = Arc::new(Mutex::new(None));
+
+    move |context: Arc<C>| {
+        let me = Arc::clone(&me);
+
+        Box::pin(async move {
+            let cached =
+                me.lock().unwrap_or_else(PoisonError::into_inner).clone();
+            let me = match cached {
+                Some(me) => me,
+                None => match context.bot().get_me().call().await {
+                    Ok(fetched) => {
+                        *me.lock().unwrap_or_else(PoisonError::into_inner) =
+                            Some(fetched.user.clone());
+                        fetched.user
+                    }
+                    Err(_) => return false,
+                },
+            };
+
+            mentions(context.text(), &me)
+        })
+    }
+}
+
+fn mentions(text: &Text, user: &User) -> bool {
+    let utf16: Vec<u16> = text.value.encode_utf16().collect();
+
+    text.entities.iter().any(|entity| match &entity.kind {
+        EntityKind::Mention => {
+            let mention =
+                match utf16.get(entity.offset..entity.offset + entity.length) {
+                    Some(mention) => String::from_utf16_lossy(mention),
+                    None => return false,
+                };
+
+            user.username.as_deref().map_or(false, |username| {
+                mention.strip_prefix('@').map_or(false, |mention| {
+                    mention.eq_ignore_ascii_case(username)
+                })
+            })
+        }
+        EntityKind::TextMention(mentioned) => mentioned.id == user.id,
+        _ => false,
+    })
+}
@@ -89,14 +89,19 @@
 //!
 //! `tbot` also provides a few utility state storages for common patterns.
 //! You can combine them with other state storages or with your own storage
-//! if needed.
+//! if needed. To keep state between restarts, see [`storage`].
+//!
+//! [`storage`]: ./storage/index.html
 
 pub mod chats;
+pub mod dialogues;
 mod event_loop;
 pub mod messages;
 mod polling;
+pub mod storage;
 
 pub use chats::Chats;
+pub use dialogues::Dialogues;
 pub use event_loop::StatefulEventLoop;
 pub use messages::Messages;
 pub use polling::Polling;
@@ -0,0 +1,604 @@
+//! A finite-state machine for conversations with users.
+//!
+//! Many bots lead users through a dialogue: a questionary asks for the name,
+//! then for the age, then for something else. The [`Dialogues`] storage keeps
+//! track of the step each chat (or each user in a chat) is at, and lets you
+//! bind handlers to steps. First, describe the steps with an enum:
+//!
+//! ```
+//! #[derive(Debug, PartialEq, Eq, Clone)]
+//! enum Step {
+//!     AskName,
+//!     AskAge { name: String },
+//! }
+//! ```
+//!
+//! Then, use [`Dialogues`] as the state of the stateful event loop, or make
+//! it a part of your state and implement `AsRef<Dialogues<Step>>` for it.
+//! Dialogues which haven't seen any activity for the configured timeout are
+//! considered finished:
+//!
+//! ```
+//! # #[derive(PartialEq, Clone)] enum Step { AskName }
+//! use std::time::Duration;
+//! use tbot::state::Dialogues;
+//!
+//! let mut bot = tbot::from_env!("BOT_TOKEN").stateful_event_loop(
+//!     Dialogues::<Step>::per_chat().timeout(Duration::from_secs(600)),
+//! );
+//! ```
+//!
+//! A dialogue is started with [`Dialogues::start`], and handlers are bound
+//! to steps with [`on`] or [`on_match`]. A bound handler receives the current
+//! step and returns a [`Transition`] to the next one. As bound handlers are
+//! regular handlers, they can be passed to any handler of the event loop,
+//! including its `_if` variants:
+//!
+//! ```
+//! # #[derive(PartialEq, Clone)]
+//! # enum Step { AskName, AskAge { name: String } }
+//! # let mut bot = tbot::Bot::new(String::new())
+//! #     .stateful_event_loop(tbot::state::Dialogues::<Step>::per_chat());
+//! use std::sync::Arc;
+//! use tbot::{
+//!     contexts::{Command, Text},
+//!     prelude::*,
+//!     state::dialogues::{self, Transition},
+//! };
+//!
+//! bot.start(|context, dialogues| async move {
+//!     dialogues.start(&*context, Step::AskName);
+//!     let _ = context.send_message("What's your name?").call().await;
+//! });
+//!
+//! bot.text(dialogues::on(
+//!     Step::AskName,
+//!     |context: Arc<Text>, _, _| async move {
+//!         let _ = context.send_message("How old are you?").call().await;
+//!         Transition::Next(Step::AskAge {
+//!             name: context.text.value.clone(),
+//!         })
+//!     },
+//! ));
+//!
+//! bot.text(dialogues::on_match(
+//!     |step| matches!(step, Step::AskAge { .. }),
+//!     |context: Arc<Text>, _, step| async move {
+//!         if let Step::AskAge { name } = step {
+//!             let message = format!("Nice to meet you, {}!", name);
+//!             let _ = context.send_message(&message).call().await;
+//!         }
+//!         Transition::Finish
+//!     },
+//! ));
+//!
+//! bot.command(
+//!     "cancel",
+//!     dialogues::cancel(|context: Arc<Command>, _, step| async move {
+//!         if step.is_some() {
+//!             let _ = context.send_message("Cancelled.").call().await;
+//!         }
+//!     }),
+//! );
+//! ```
+//!
+//! As the handlers are constructed before they're passed to the event loop,
+//! Rust can't infer the type of the context, so you need to annotate it.
+//!
+//! Note that a bound handler checks the dialogue's step when an update is
+//! dispatched, before any handler is run. Thus, if one handler moves the
+//! dialogue to the next step, the handler of that step won't be triggered
+//! by the same update. Also, if the dialogue is cancelled or restarted while
+//! a handler is running, the transition returned by the handler is ignored.
+//!
+//! [`Dialogues`]: ./struct.Dialogues.html
+//! [`Dialogues::start`]: ./struct.Dialogues.html#method.start
+//! [`on`]: ./fn.on.html
+//! [`on_match`]: ./fn.on_match.html
+//! [`Transition`]: ./enum.Transition.html
+
+use crate::{
+    contexts::fields::Message,
+    types::{chat, message, user},
+};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Identifies a dialogue.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[must_use]
+pub struct Key {
+    /// The ID of the chat where the dialogue takes place.
+    pub chat_id: chat::Id,
+    /// The ID of the user the dialogue is with. It is `None` if dialogues
+    /// are stored per chat, or if the message wasn't sent by a user.
+    pub user_id: Option<user::Id>,
+}
+
+/// Configures what a dialogue is bound to.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Scope {
+    /// There's one dialogue per chat, shared by all its members.
+    Chat,
+    /// Each user in a chat has their own dialogue.
+    User,
+}
+
+/// Describes what happens to a dialogue after a handler is run.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Transition<D> {
+    /// Moves the dialogue to the next step.
+    Next(D),
+    /// Keeps the dialogue at the current step.
+    Stay,
+    /// Finishes the dialogue.
+    Finish,
+}
+
+#[derive(Debug)]
+struct Entry<D> {
+    step: D,
+    last_activity: Instant,
+    generation: u64,
+}
+
+#[derive(Debug)]
+struct Inner<D> {
+    entries: HashMap<Key, Entry<D>>,
+    next_generation: u64,
+}
+
+/// A storage of dialogues. See [the module's docs] to learn how to use it.
+///
+/// [the module's docs]: ./index.html
+#[derive(Debug)]
+pub struct Dialogues<D> {
+    scope: Scope,
+    timeout: Option<Duration>,
+    inner: Mutex<Inner<D>>,
+}
+
+impl<D> Dialogues<D> {
+    /// Constructs a new dialogue storage with the given scope.
+    #[must_use]
+    pub fn new(scope: Scope) -> Self {
+        Self {
+            scope,
+            timeout: None,
+            inner: Mutex::new(Inner {
+                entries: HashMap::new(),
+                next_generation: 0,
+            }),
+        }
+    }
+
+    /// Constructs a new dialogue storage where there's one dialogue per chat.
+    #[must_use]
+    pub fn per_chat() -> Self {
+        Self::new(Scope::Chat)
+    }
+
+    /// Constructs a new dialogue storage where each user in a chat has
+    /// their own dialogue.
+    #[must_use]
+    pub fn per_user() -> Self {
+        Self::new(Scope::User)
+    }
+
+    /// Configures after how much time of inactivity a dialogue is reset.
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the scope of the storage.
+    #[must_use]
+    pub const fn scope(&self) -> Scope {
+        self.scope
+    }
+
+    /// Infers the key of a dialogue from the context.
+    pub fn key<C>(&self, context: &C) -> Key
+    where
+        C: Message,
+    {
+        let user_id = match (self.scope, context.from()) {
+            (Scope::User, Some(message::From::User(user))) => Some(user.id),
+            _ => None,
+        };
+
+        Key {
+            chat_id: context.chat().id,
+            user_id,
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner<D>> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn is_expired(&self, entry: &Entry<D>) -> bool {
+        self.timeout
+            .map_or(false, |timeout| entry.last_activity.elapsed() >= timeout)
+    }
+
+    fn with_entry<T>(
+        &self,
+        key: Key,
+        f: impl FnOnce(&mut Entry<D>) -> T,
+    ) -> Option<T> {
+        let mut inner = self.lock();
+
+        if let Some(entry) = inner.entries.get(&key) {
+            if self.is_expired(entry) {
+                inner.entries.remove(&key);
+                return None;
+            }
+        }
+
+        inner.entries.get_mut(&key).map(f)
+    }
+
+    /// Starts or restarts a dialogue by its key. Returns the previous step.
+    pub fn start_by_key(&self, key: Key, step: D) -> Option<D> {
+        let mut inner = self.lock();
+        let generation = inner.next_generation;
+        inner.next_generation += 1;
+
+        let entry = Entry {
+            step,
+            last_activity: Instant::now(),
+            generation,
+        };
+
+        inner
+            .entries
+            .insert(key, entry)
+            .filter(|entry| !self.is_expired(entry))
+            .map(|entry| entry.step)
+    }
+
+    /// Starts or restarts a dialogue, inferring its key from the context.
+    /// Returns the previous step.
+    pub fn start<C>(&self, context: &C, step: D) -> Option<D>
+    where
+        C: Message,
+    {
+        self.start_by_key(self.key(context), step)
+    }
+
+    /// Finishes a dialogue by its key. Returns the step it was at.
+    pub fn finish_by_key(&self, key: Key) -> Option<D> {
+        self.lock()
+            .entries
+            .remove(&key)
+            .filter(|entry| !self.is_expired(entry))
+            .map(|entry| entry.step)
+    }
+
+    /// Finishes a dialogue, inferring its key from the context. Returns
+    /// the step it was at.
+    pub fn finish<C>(&self, context: &C) -> Option<D>
+    where
+        C: Message,
+    {
+        self.finish_by_key(self.key(context))
+    }
+
+    /// Checks if a dialogue is active by its key.
+    #[must_use]
+    pub fn is_active_by_key(&self, key: Key) -> bool {
+        self.with_entry(key, |_| ()).is_some()
+    }
+
+    /// Checks if a dialogue is active, inferring its key from the context.
+    #[must_use]
+    pub fn is_active<C>(&self, context: &C) -> bool
+    where
+        C: Message,
+    {
+        self.is_active_by_key(self.key(context))
+    }
+
+    /// Applies a transition to a dialogue by its key.
+    pub fn transition_by_key(&self, key: Key, transition: Transition<D>) {
+        self.apply(key, None, transition);
+    }
+
+    /// Applies a transition to a dialogue, inferring its key from
+    /// the context.
+    pub fn transition<C>(&self, context: &C, transition: Transition<D>)
+    where
+        C: Message,
+    {
+        self.transition_by_key(self.key(context), transition);
+    }
+
+    /// Applies a transition to a dialogue. If `generation` is provided,
+    /// the transition is applied only if the dialogue hasn't been restarted
+    /// or finished since `generation` was observed.
+    fn apply(
+        &self,
+        key: Key,
+        generation: Option<u64>,
+        transition: Transition<D>,
+    ) {
+        let mut inner = self.lock();
+        let entry = match inner.entries.get_mut(&key) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        if self.is_expired(entry) {
+            inner.entries.remove(&key);
+            return;
+        }
+
+        let is_stale = generation
+            .map_or(false, |generation| generation != entry.generation);
+        if is_stale {
+            return;
+        }
+
+        match transition {
+            Transition::Next(step) => {
+                entry.step = step;
+                entry.last_activity = Instant::now();
+            }
+            Transition::Stay => entry.last_activity = Instant::now(),
+            Transition::Finish => {
+                inner.entries.remove(&key);
+            }
+        }
+    }
+
+    /// Removes all dialogues which have timed out.
+    pub fn remove_expired(&self) {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return,
+        };
+
+        self.lock()
+            .entries
+            .retain(|_, entry| entry.last_activity.elapsed() < timeout);
+    }
+
+    /// Returns how many dialogues are active.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.remove_expired();
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if there are no active dialogues.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finishes all dialogues.
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+}
+
+impl<D: Clone> Dialogues<D> {
+    /// Gets the current step of a dialogue by its key.
+    #[must_use]
+    pub fn get_by_key(&self, key: Key) -> Option<D> {
+        self.with_entry(key, |entry| entry.step.clone())
+    }
+
+    /// Gets the current step of a dialogue, inferring its key from
+    /// the context.
+    #[must_use]
+    pub fn get<C>(&self, context: &C) -> Option<D>
+    where
+        C: Message,
+    {
+        self.get_by_key(self.key(context))
+    }
+
+    /// Returns the keys and the current steps of all active dialogues.
+    #[must_use]
+    pub fn snapshot(&self) -> Vec<(Key, D)> {
+        self.remove_expired();
+        self.lock()
+            .entries
+            .iter()
+            .map(|(&key, entry)| (key, entry.step.clone()))
+            .collect()
+    }
+}
+
+impl<D: PartialEq> Dialogues<D> {
+    /// Checks if a dialogue is at the given step by its key.
+    #[must_use]
+    pub fn is_in_by_key(&self, key: Key, step: &D) -> bool {
+        self.with_entry(key, |entry| entry.step == *step)
+            .unwrap_or(false)
+    }
+
+    /// Checks if a dialogue is at the given step, inferring its key from
+    /// the context.
+    #[must_use]
+    pub fn is_in<C>(&self, context: &C, step: &D) -> bool
+    where
+        C: Message,
+    {
+        self.is_in_by_key(self.key(context), step)
+    }
+}
+
+impl<D> AsRef<Self> for Dialogues<D> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
+/// Binds a handler to a dialogue's step. The handler is run only if
+/// the dialogue is at `step`, and the transition it returns is applied
+/// to the dialogue.
+///
+/// The handler receives the context, the state and the current step.
+pub fn on<C, S, D, H, F>(
+    step: D,
+    handler: H,
+) -> impl Fn(Arc<C>, Arc<S>) -> BoxFuture<'static, ()> + Send + Sync + 'static
+where
+    C: Message + Send + Sync + 'static,
+    S: AsRef<Dialogues<D>> + Send + Sync + 'static,
+    D: PartialEq + Clone + Send + Sync + 'static,
+    H: (Fn(Arc<C>, Arc<S>, D) -> F) + Send + Sync + 'static,
+    F: Future<Output = Transition<D>> + Send + 'static,
+{
+    on_match(move |current: &D| *current == step, handler)
+}
+
+/// Binds a handler to the dialogue's steps for which `matcher` returns `true`.
+/// It is useful if your steps carry data, so you can't compare them with
+/// a single value.
+///
+/// The handler receives the context, the state and the current step.
+pub fn on_match<C, S, D, M, H, F>(
+    matcher: M,
+    handler: H,
+) -> impl Fn(Arc<C>, Arc<S>) -> BoxFuture<'static, ()> + Send + Sync + 'static
+where
+    C: Message + Send + Sync + 'static,
+    S: AsRef<Dialogues<D>> + Send + Sync + 'static,
+    D: Clone + Send + Sync + 'static,
+    M: Fn(&D) -> bool + Send + Sync + 'static,
+    H: (Fn(Arc<C>, Arc<S>, D) -> F) + Send + Sync + 'static,
+    F: Future<Output = Transition<D>> + Send + 'static,
+{
+    move |context, state| {
+        let dialogues = (*state).as_ref();
+        let key = dialogues.key(&*context);
+        let current = dialogues.with_entry(key, |entry| {
+            if matcher(&entry.step) {
+                Some((entry.step.clone(), entry.generation))
+            } else {
+                None
+            }
+        });
+
+        let (step, generation) = match current.flatten() {
+            Some(current) => current,
+            None => return Box::pin(async {}),
+        };
+
+        let future = handler(context, Arc::clone(&state), step);
+        Box::pin(async move {
+            let transition = future.await;
+            (*state).as_ref().apply(key, Some(generation), transition);
+        })
+    }
+}
+
+/// Constructs a handler which finishes the dialogue, e.g. for
+/// the `/cancel` command.
+///
+/// The handler receives the context, the state and the step the dialogue
+/// was at, or `None` if there was no active dialogue.
+pub fn cancel<C, S, D, H, F>(
+    handler: H,
+) -> impl Fn(Arc<C>, Arc<S>) -> BoxFuture<'static, ()> + Send + Sync + 'static
+where
+    C: Message + Send + Sync + 'static,
+    S: AsRef<Dialogues<D>> + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+    H: (Fn(Arc<C>, Arc<S>, Option<D>) -> F) + Send + Sync + 'static,
+    F: Future<Output = ()> + Send + 'static,
+{
+    move |context, state| {
+        let step = (*state).as_ref().finish(&*context);
+        Box::pin(handler(context, state, step))
+    }
+}
+
+/// Checks if the dialogue is at the given step.
+pub fn is_in<C, S, D>(
+    step: D,
+) -> impl Fn(Arc<C>, Arc<S>) -> BoxFuture<'static, bool> + Send + Sync + 'static
+where
+    C: Message + Send + Sync + 'static,
+    S: AsRef<Dialogues<D>> + Send + Sync + 'static,
+    D: PartialEq + Send + Sync + 'static,
+{
+    move |context, state| {
+        let is_in = (*state).as_ref().is_in(&*context, &step);
+        Box::pin(async move { is_in })
+    }
+}
+
+/// Checks if there's an active dialogue.
+pub fn is_active<C, S, D>(
+) -> impl Fn(Arc<C>, Arc<S>) -> BoxFuture<'static, bool> + Send + Sync + 'static
+where
+    C: Message + Send + Sync + 'static,
+    S: AsRef<Dialogues<D>> + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    move |context, state| {
+        let is_active = (*state).as_ref().is_active(&*context);
+        Box::pin(async move { is_active })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: Key = Key {
+        chat_id: chat::Id(1),
+        user_id: None,
+    };
+
+    #[test]
+    fn applies_transitions() {
+        let dialogues = Dialogues::per_chat();
+        assert!(!dialogues.is_active_by_key(KEY));
+
+        dialogues.start_by_key(KEY, 1);
+        assert!(dialogues.is_in_by_key(KEY, &1));
+
+        dialogues.transition_by_key(KEY, Transition::Next(2));
+        assert_eq!(dialogues.get_by_key(KEY), Some(2));
+
+        dialogues.transition_by_key(KEY, Transition::Stay);
+        assert_eq!(dialogues.get_by_key(KEY), Some(2));
+
+        dialogues.transition_by_key(KEY, Transition::Finish);
+        assert_eq!(dialogues.get_by_key(KEY), None);
+    }
+
+    #[test]
+    fn ignores_transitions_after_restart() {
+        let dialogues = Dialogues::per_chat();
+        dialogues.start_by_key(KEY, 1);
+        let generation = dialogues.lock().entries[&KEY].generation;
+
+        dialogues.start_by_key(KEY, 10);
+        dialogues.apply(KEY, Some(generation), Transition::Next(2));
+        assert_eq!(dialogues.get_by_key(KEY), Some(10));
+    }
+
+    #[test]
+    fn resets_stale_dialogues() {
+        let dialogues = Dialogues::per_chat().timeout(Duration::from_secs(0));
+        dialogues.start_by_key(KEY, 1);
+
+        assert_eq!(dialogues.get_by_key(KEY), None);
+        assert!(dialogues.is_empty());
+    }
+}
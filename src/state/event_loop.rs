@@ -1,7 +1,13 @@
-use super::Polling;
-use crate::event_loop::{EventLoop, Webhook};
-use crate::{contexts, errors};
-use std::{future::Future, sync::Arc};
+use super::{
+    storage::{load, Persistence, Persistent, Storage},
+    Polling,
+};
+use crate::event_loop::{CommandDescription, EventLoop, Webhook};
+use crate::{
+    contexts, errors,
+    util::callback_data::{CallbackData, Codec},
+};
+use std::{future::Future, sync::Arc, time::Duration};
 
 macro_rules! handler {
     (
@@ -63,6 +69,7 @@ macro_rules! handler {
 pub struct StatefulEventLoop<S> {
     inner: EventLoop,
     state: Arc<S>,
+    persistence: Option<Persistence<S>>,
 }
 
 #[allow(clippy::use_self)] // https://github.com/rust-lang/rust-clippy/issues/4143
@@ -71,6 +78,7 @@ impl<S> StatefulEventLoop<S> {
         Self {
             inner,
             state: Arc::new(state),
+            persistence: None,
         }
     }
 
@@ -90,6 +98,7 @@ impl<S> StatefulEventLoop<S> {
 
     /// Turns this event loop into another with other state. Handlers added on
     /// this event loop are still kept and will receive the previous state.
+    /// The configured storage, if any, doesn't persist the other state.
     pub fn with_other_state<T>(self, other_state: T) -> StatefulEventLoop<T>
     where
         T: Send + Sync + 'static,
@@ -97,6 +106,7 @@ impl<S> StatefulEventLoop<S> {
         StatefulEventLoop {
             inner: self.inner,
             state: Arc::new(other_state),
+            persistence: None,
         }
     }
 
@@ -108,9 +118,21 @@ impl<S> StatefulEventLoop<S> {
         self.inner.username(username);
     }
 
+    /// Sets the codec for typed callback data.
+    ///
+    /// The codec is used by all handlers added with
+    /// [`message_data_callback_typed`] and [`inline_data_callback_typed`],
+    /// including the ones added before this call.
+    ///
+    /// [`message_data_callback_typed`]: #method.message_data_callback_typed
+    /// [`inline_data_callback_typed`]: #method.inline_data_callback_typed
+    pub fn callback_data_codec(&mut self, codec: Codec) {
+        self.inner.callback_data_codec(codec);
+    }
+
     /// Starts polling configuration.
     pub fn polling(self) -> Polling<S> {
-        Polling::new(self.inner, Arc::clone(&self.state))
+        Polling::new(self.inner, Arc::clone(&self.state), self.persistence)
     }
 
     /// Starts webhook configuration.
@@ -123,6 +145,40 @@ impl<S> StatefulEventLoop<S> {
     }
 }
 
+impl<S> StatefulEventLoop<S>
+where
+    S: Persistent + Send + Sync + 'static,
+{
+    /// Configures a storage for the state and loads the state from it.
+    ///
+    /// The state is then saved to the storage every `autosave_interval` for
+    /// as long as the event loop or its handlers are alive, both with
+    /// polling and webhooks. Polling started with [`Polling::start_until`]
+    /// also saves the state on shutdown. See [`storage`] to learn more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `autosave_interval` is zero.
+    ///
+    /// [`Polling::start_until`]: ./struct.Polling.html#method.start_until
+    /// [`storage`]: ./storage/index.html
+    #[allow(clippy::future_not_send)] // `S: Send + Sync` is guaranteed
+    pub async fn storage(
+        &mut self,
+        storage: impl Storage + 'static,
+        autosave_interval: Duration,
+    ) -> Result<(), errors::Storage> {
+        let storage: Arc<dyn Storage> = Arc::new(storage);
+        load(&*storage, &*self.state).await?;
+
+        let persistence = Persistence::new(storage);
+        persistence.autosave(Arc::downgrade(&self.state), autosave_interval);
+        self.persistence = Some(persistence);
+
+        Ok(())
+    }
+}
+
 impl<S> StatefulEventLoop<S>
 where
     S: Send + Sync + 'static,
@@ -157,7 +213,7 @@ where
     pub fn command_with_description<H, F>(
         &mut self,
         command: &'static str,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>, Arc<S>) -> F) + Send + Sync + 'static,
@@ -216,7 +272,7 @@ where
     pub fn command_with_description_if<H, HF, P, PF>(
         &mut self,
         command: &'static str,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -309,7 +365,7 @@ where
     /// Adds a new handler for the `/start` command and sets its description.
     pub fn start_with_description<H, F>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>, Arc<S>) -> F) + Send + Sync + 'static,
@@ -344,7 +400,7 @@ where
     /// if the predicate returns true. Also sets the command's description.
     pub fn start_with_description_if<H, HF, P, PF>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -373,7 +429,7 @@ where
     /// Adds a new handler for the `/help` command and sets its description.
     pub fn help_with_description<H, F>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>, Arc<S>) -> F) + Send + Sync + 'static,
@@ -408,7 +464,7 @@ where
     /// returns true. Also sets the command's description.
     pub fn help_with_description_if<H, HF, P, PF>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -437,7 +493,7 @@ where
     /// Adds a new handler for the `/settings` command and sets its description.
     pub fn settings_with_description<H, F>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         handler: H,
     ) where
         H: (Fn(Arc<contexts::Command>, Arc<S>) -> F) + Send + Sync + 'static,
@@ -472,7 +528,7 @@ where
     /// if the predicate returns true. Also sets the command's description.
     pub fn settings_with_description_if<H, HF, P, PF>(
         &mut self,
-        description: &'static str,
+        description: impl Into<CommandDescription>,
         predicate: P,
         handler: H,
     ) where
@@ -612,6 +668,15 @@ where
         audio_if,
     }
 
+    handler! {
+        contexts::AutoDeleteTimer,
+        /// Adds a new handler for changed auto-delete timers.
+        auto_delete_timer,
+        /// Adds a new handler for changed auto-delete timers which is run if
+        /// the predicate returns true.
+        auto_delete_timer_if,
+    }
+
     handler! {
         contexts::Update,
         /// Adds a new handler which is run before handling an update.
@@ -630,6 +695,15 @@ where
         chosen_inline_if,
     }
 
+    handler! {
+        contexts::ClosedForumTopic,
+        /// Adds a new handler for closed forum topics.
+        closed_forum_topic,
+        /// Adds a new handler for closed forum topics which is run if the
+        /// predicate returns true.
+        closed_forum_topic_if,
+    }
+
     handler! {
         contexts::Contact,
         /// Adds a new handler for contacts.
@@ -648,6 +722,15 @@ where
         connected_website_if,
     }
 
+    handler! {
+        contexts::CreatedForumTopic,
+        /// Adds a new handler for created forum topics.
+        created_forum_topic,
+        /// Adds a new handler for created forum topics which is run if the
+        /// predicate returns true.
+        created_forum_topic_if,
+    }
+
     handler! {
         contexts::CreatedGroup,
         /// Adds a new handler for created groups.
@@ -675,6 +758,47 @@ where
         inline_data_callback_if,
     }
 
+    /// Adds a new handler for data callbacks from chat messages which is run
+    /// only if the data decodes to `T` with the event loop's
+    /// [codec][callback_data_codec].
+    ///
+    /// [callback_data_codec]: #method.callback_data_codec
+    pub fn message_data_callback_typed<T, H, F>(&mut self, handler: H)
+    where
+        T: CallbackData + Send + 'static,
+        H: (Fn(Arc<contexts::MessageDataCallback>, T, Arc<S>) -> F)
+            + Send
+            + Sync
+            + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let state = Arc::clone(&self.state);
+        self.inner
+            .message_data_callback_typed(move |context, data| {
+                handler(context, data, Arc::clone(&state))
+            });
+    }
+
+    /// Adds a new handler for data callbacks from inline messages which is
+    /// run only if the data decodes to `T` with the event loop's
+    /// [codec][callback_data_codec].
+    ///
+    /// [callback_data_codec]: #method.callback_data_codec
+    pub fn inline_data_callback_typed<T, H, F>(&mut self, handler: H)
+    where
+        T: CallbackData + Send + 'static,
+        H: (Fn(Arc<contexts::InlineDataCallback>, T, Arc<S>) -> F)
+            + Send
+            + Sync
+            + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let state = Arc::clone(&self.state);
+        self.inner.inline_data_callback_typed(move |context, data| {
+            handler(context, data, Arc::clone(&state))
+        });
+    }
+
     handler! {
         contexts::DeletedChatPhoto,
         /// Adds a new handler for deleted chat photos.
@@ -765,6 +889,15 @@ where
         edited_video_if,
     }
 
+    handler! {
+        contexts::EndedVoiceChat,
+        /// Adds a new handler for ended voice chats.
+        ended_voice_chat,
+        /// Adds a new handler for ended voice chats which is run if the
+        /// predicate returns true.
+        ended_voice_chat_if,
+    }
+
     handler! {
         contexts::MessageGameCallback,
         /// Adds a new handler for game callbacks from chat messages.
@@ -918,6 +1051,33 @@ where
         pre_checkout_if,
     }
 
+    handler! {
+        contexts::ProximityAlert,
+        /// Adds a new handler for triggered proximity alerts.
+        proximity_alert,
+        /// Adds a new handler for triggered proximity alerts which is run if
+        /// the predicate returns true.
+        proximity_alert_if,
+    }
+
+    handler! {
+        contexts::ReopenedForumTopic,
+        /// Adds a new handler for reopened forum topics.
+        reopened_forum_topic,
+        /// Adds a new handler for reopened forum topics which is run if the
+        /// predicate returns true.
+        reopened_forum_topic_if,
+    }
+
+    handler! {
+        contexts::ScheduledVoiceChat,
+        /// Adds a new handler for scheduled voice chats.
+        scheduled_voice_chat,
+        /// Adds a new handler for scheduled voice chats which is run if the
+        /// predicate returns true.
+        scheduled_voice_chat_if,
+    }
+
     handler! {
         contexts::Shipping,
         /// Adds a new handler for shipping queries.
@@ -927,6 +1087,15 @@ where
         shipping_if,
     }
 
+    handler! {
+        contexts::StartedVoiceChat,
+        /// Adds a new handler for started voice chats.
+        started_voice_chat,
+        /// Adds a new handler for started voice chats which is run if the
+        /// predicate returns true.
+        started_voice_chat_if,
+    }
+
     handler! {
         contexts::Sticker,
         /// Adds a new handler for stickers.
@@ -1007,6 +1176,15 @@ where
         /// predicate returns true.
         voice_if,
     }
+
+    handler! {
+        contexts::VoiceChatInvitation,
+        /// Adds a new handler for invitations to voice chats.
+        voice_chat_invitation,
+        /// Adds a new handler for invitations to voice chats which is run if
+        /// the predicate returns true.
+        voice_chat_invitation_if,
+    }
 }
 
 impl<S> StatefulEventLoop<S> {
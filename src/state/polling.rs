@@ -1,9 +1,14 @@
+use super::storage::Persistence;
 use crate::{
     errors,
     event_loop::{self, EventLoop},
     types::parameters::UpdateKind,
 };
-use std::{convert::Infallible, num::NonZeroUsize, sync::Arc, time::Duration};
+use futures::future::{select, Either};
+use std::{
+    convert::Infallible, future::Future, num::NonZeroUsize, sync::Arc,
+    time::Duration,
+};
 
 /// Configures and starts polling for the stateful event loop.
 ///
@@ -14,14 +19,20 @@ use std::{convert::Infallible, num::NonZeroUsize, sync::Arc, time::Duration};
 pub struct Polling<S> {
     inner: event_loop::Polling,
     state: Arc<S>,
+    persistence: Option<Persistence<S>>,
 }
 
 #[allow(clippy::use_self)] // https://github.com/rust-lang/rust-clippy/issues/4143
 impl<S> Polling<S> {
-    pub(crate) fn new(event_loop: EventLoop, state: Arc<S>) -> Self {
+    pub(crate) fn new(
+        event_loop: EventLoop,
+        state: Arc<S>,
+        persistence: Option<Persistence<S>>,
+    ) -> Self {
         Self {
             inner: event_loop::Polling::new(event_loop),
             state,
+            persistence,
         }
     }
 
@@ -33,7 +44,8 @@ impl<S> Polling<S> {
         self.inner
     }
 
-    /// Turns this polling into another with other state.
+    /// Turns this polling into another with other state. The configured
+    /// storage, if any, doesn't persist the other state.
     pub fn with_other_state<T>(self, other_state: T) -> Polling<T>
     where
         T: Send + Sync + 'static,
@@ -41,6 +53,7 @@ impl<S> Polling<S> {
         Polling {
             inner: self.inner,
             state: Arc::new(other_state),
+            persistence: None,
         }
     }
 
@@ -107,6 +120,36 @@ impl<S> Polling<S> {
     pub async fn start(self) -> Result<Infallible, errors::PollingSetup> {
         self.inner.start().await
     }
+
+    /// Starts the event loop and runs it until `shutdown` resolves. If
+    /// a storage is configured, the state is saved to it on shutdown.
+    #[allow(clippy::future_not_send)] // `S: Send + Sync` is guaranteed
+    pub async fn start_until<F>(
+        self,
+        shutdown: F,
+    ) -> Result<(), errors::StatefulPolling>
+    where
+        F: Future<Output = ()>,
+    {
+        let Self {
+            inner,
+            state,
+            persistence,
+        } = self;
+
+        let shutdown = Box::pin(shutdown);
+        if let Either::Left((result, _)) =
+            select(Box::pin(inner.start()), shutdown).await
+        {
+            result?;
+        }
+
+        if let Some(persistence) = persistence {
+            persistence.save(&state).await?;
+        }
+
+        Ok(())
+    }
 }
 
 impl<S> Polling<S>
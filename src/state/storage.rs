@@ -0,0 +1,373 @@
+//! Persistent storages for state.
+//!
+//! [`Chats`] and [`Messages`] live in memory, so restarting the bot wipes
+//! them. To keep them between restarts, configure a [`Storage`] on the
+//! stateful event loop:
+//!
+//! ```no_run
+//! # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+//! use std::{sync::Mutex, time::Duration};
+//! use tbot::state::{storage, Chats};
+//!
+//! let mut bot = tbot::from_env!("BOT_TOKEN")
+//!     .stateful_event_loop(Mutex::new(Chats::<String>::new()));
+//! let storage = storage::File::open("state.json").await?;
+//! bot.storage(storage, Duration::from_secs(60)).await?;
+//!
+//! let shutdown = async { /* e.g. wait for Ctrl-C */ };
+//! bot.polling().start_until(shutdown).await.unwrap();
+//! # Ok(()) }
+//! ```
+//!
+//! Configuring a storage loads the state from it right away. After that,
+//! the state is saved to the storage periodically for as long as the event
+//! loop is alive, no matter if it runs polling or a webhook. If you start
+//! polling with [`start_until`], the state is also saved once the shutdown
+//! future resolves. If you need to save the state at other moments, use
+//! [`load`] and [`save`] manually.
+//!
+//! The state must implement [`Persistent`]. `tbot` implements it for
+//! `std`'s `Mutex` and `RwLock` containing [`Chats`] or [`Messages`], and you
+//! can implement it for your own state, e.g. if you use `tokio`'s locks or
+//! combine several storages.
+//!
+//! `tbot` ships a [`File`] storage, which keeps a snapshot and a log of
+//! changes since the snapshot, and a [`Sled`] storage, which is backed by
+//! the embedded [`sled`] database and requires the `sled` feature.
+//!
+//! [`Chats`]: ../struct.Chats.html
+//! [`Messages`]: ../struct.Messages.html
+//! [`Storage`]: ./trait.Storage.html
+//! [`Persistent`]: ./trait.Persistent.html
+//! [`start_until`]: ../struct.Polling.html#method.start_until
+//! [`load`]: ./fn.load.html
+//! [`save`]: ./fn.save.html
+//! [`File`]: ./struct.File.html
+//! [`Sled`]: ./struct.Sled.html
+//! [`sled`]: https://docs.rs/sled
+
+use super::{messages::MessageId, Chats, Messages};
+use crate::{
+    errors,
+    types::{chat, message},
+};
+use futures::{future::BoxFuture, lock::Mutex as AsyncMutex};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    sync::{Arc, Mutex, PoisonError, RwLock, Weak},
+    time::Duration,
+};
+use tokio::time::{interval_at, Instant};
+use tracing::error;
+
+mod file;
+#[cfg(feature = "sled")]
+mod sled;
+
+#[cfg(feature = "sled")]
+pub use self::sled::Sled;
+pub use file::File;
+
+/// A stored key and its value.
+pub type Entry = (String, Vec<u8>);
+
+/// An asynchronous key-value storage.
+pub trait Storage: Send + Sync {
+    /// Gets the value of a key.
+    fn get<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Vec<u8>>, errors::Storage>>;
+
+    /// Sets the value of a key.
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        value: Vec<u8>,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>>;
+
+    /// Removes a key.
+    fn remove<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>>;
+
+    /// Returns all entries whose keys start with `prefix`, sorted by key.
+    fn scan<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Entry>, errors::Storage>>;
+
+    /// Makes sure that all changes are written to disk.
+    fn flush(&self) -> BoxFuture<'_, Result<(), errors::Storage>>;
+}
+
+/// A state which can be converted to key-value entries and back.
+pub trait Snapshot: Sized {
+    /// Converts the state to entries.
+    fn to_entries(&self) -> Result<Vec<Entry>, errors::Storage>;
+
+    /// Restores the state from entries.
+    fn from_entries(entries: Vec<Entry>) -> Result<Self, errors::Storage>;
+}
+
+impl<S> Snapshot for Chats<S>
+where
+    S: Serialize + DeserializeOwned,
+{
+    fn to_entries(&self) -> Result<Vec<Entry>, errors::Storage> {
+        self.iter()
+            .map(|(id, state)| {
+                Ok((id.0.to_string(), serde_json::to_vec(state)?))
+            })
+            .collect()
+    }
+
+    fn from_entries(entries: Vec<Entry>) -> Result<Self, errors::Storage> {
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let id = key
+                    .parse()
+                    .map_err(|_| errors::Storage::InvalidKey(key.clone()))?;
+                Ok((chat::Id(id), serde_json::from_slice(&value)?))
+            })
+            .collect()
+    }
+}
+
+impl<S> Snapshot for Messages<S>
+where
+    S: Serialize + DeserializeOwned,
+{
+    fn to_entries(&self) -> Result<Vec<Entry>, errors::Storage> {
+        self.iter()
+            .map(|(id, state)| {
+                let key = format!("{}/{}", id.chat_id.0, id.message_id.0);
+                Ok((key, serde_json::to_vec(state)?))
+            })
+            .collect()
+    }
+
+    fn from_entries(entries: Vec<Entry>) -> Result<Self, errors::Storage> {
+        entries
+            .into_iter()
+            .map(|(key, value)| {
+                let id = parse_message_id(&key)
+                    .ok_or_else(|| errors::Storage::InvalidKey(key.clone()))?;
+                Ok((id, serde_json::from_slice(&value)?))
+            })
+            .collect()
+    }
+}
+
+fn parse_message_id(key: &str) -> Option<MessageId> {
+    let mut parts = key.splitn(2, '/');
+    let chat_id = parts.next()?.parse().ok()?;
+    let message_id = parts.next()?.parse().ok()?;
+
+    Some(MessageId {
+        chat_id: chat::Id(chat_id),
+        message_id: message::Id(message_id),
+    })
+}
+
+/// A state of a stateful event loop which can be persisted.
+///
+/// As the event loop only has shared access to its state, both methods take
+/// `&self` and rely on interior mutability.
+pub trait Persistent {
+    /// Converts the state to entries.
+    fn dump(&self) -> Result<Vec<Entry>, errors::Storage>;
+
+    /// Replaces the state with the one restored from entries.
+    fn restore(&self, entries: Vec<Entry>) -> Result<(), errors::Storage>;
+}
+
+impl<T: Snapshot> Persistent for Mutex<T> {
+    fn dump(&self) -> Result<Vec<Entry>, errors::Storage> {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_entries()
+    }
+
+    fn restore(&self, entries: Vec<Entry>) -> Result<(), errors::Storage> {
+        let state = T::from_entries(entries)?;
+        *self.lock().unwrap_or_else(PoisonError::into_inner) = state;
+        Ok(())
+    }
+}
+
+impl<T: Snapshot> Persistent for RwLock<T> {
+    fn dump(&self) -> Result<Vec<Entry>, errors::Storage> {
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_entries()
+    }
+
+    fn restore(&self, entries: Vec<Entry>) -> Result<(), errors::Storage> {
+        let state = T::from_entries(entries)?;
+        *self.write().unwrap_or_else(PoisonError::into_inner) = state;
+        Ok(())
+    }
+}
+
+/// Loads the state from the storage.
+pub async fn load<S>(
+    storage: &dyn Storage,
+    state: &S,
+) -> Result<(), errors::Storage>
+where
+    S: Persistent + Sync + ?Sized,
+{
+    let entries = storage.scan("").await?;
+    state.restore(entries)
+}
+
+/// Saves the state to the storage, removing keys which are no longer
+/// present in the state, and flushes the storage.
+pub async fn save<S>(
+    storage: &dyn Storage,
+    state: &S,
+) -> Result<(), errors::Storage>
+where
+    S: Persistent + Sync + ?Sized,
+{
+    let mut entries = state.dump()?;
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    for (key, _) in storage.scan("").await? {
+        let is_present = entries
+            .binary_search_by(|(present, _)| present.as_str().cmp(&key))
+            .is_ok();
+
+        if !is_present {
+            storage.remove(&key).await?;
+        }
+    }
+
+    for (key, value) in entries {
+        storage.set(&key, value).await?;
+    }
+
+    storage.flush().await
+}
+
+/// A storage configured on a stateful event loop, along with the function
+/// to save its state.
+pub(crate) struct Persistence<S> {
+    storage: Arc<dyn Storage>,
+    save: for<'a> fn(
+        &'a dyn Storage,
+        &'a S,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>>,
+    lock: Arc<AsyncMutex<()>>,
+}
+
+impl<S> Persistence<S>
+where
+    S: Persistent + Send + Sync,
+{
+    pub(crate) fn new(storage: Arc<dyn Storage>) -> Self {
+        Self {
+            storage,
+            save: |storage, state| Box::pin(save(storage, state)),
+            lock: Arc::new(AsyncMutex::new(())),
+        }
+    }
+}
+
+impl<S> Persistence<S> {
+    /// Saves the state. Saves never overlap, so an older snapshot can't
+    /// overwrite a newer one.
+    #[allow(clippy::future_not_send)] // `S: Send + Sync` is guaranteed
+    pub(crate) async fn save(&self, state: &S) -> Result<(), errors::Storage> {
+        let _lock = self.lock.lock().await;
+        (self.save)(&*self.storage, state).await
+    }
+}
+
+impl<S> Persistence<S>
+where
+    S: Send + Sync + 'static,
+{
+    /// Saves the state every `interval` until the state is dropped.
+    pub(crate) fn autosave(&self, state: Weak<S>, interval: Duration) {
+        let persistence = self.clone();
+        tokio::spawn(async move {
+            let mut ticks = interval_at(Instant::now() + interval, interval);
+            loop {
+                ticks.tick().await;
+                let state = match state.upgrade() {
+                    Some(state) => state,
+                    None => return,
+                };
+
+                if let Err(error) = persistence.save(&state).await {
+                    error!(?error, "failed to save the state");
+                }
+            }
+        });
+    }
+}
+
+impl<S> Clone for Persistence<S> {
+    fn clone(&self) -> Self {
+        Self {
+            storage: Arc::clone(&self.storage),
+            save: self.save,
+            lock: Arc::clone(&self.lock),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn saves_and_loads_chats() {
+        let path = std::env::temp_dir()
+            .join(format!("tbot-storage-test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let state = Mutex::new(Chats::new());
+        state
+            .lock()
+            .unwrap()
+            .insert_by_id(chat::Id(1), "one".to_string());
+        state
+            .lock()
+            .unwrap()
+            .insert_by_id(chat::Id(-2), "two".to_string());
+
+        let storage = File::open(&path).await.unwrap();
+        save(&storage, &state).await.unwrap();
+
+        state.lock().unwrap().remove_by_id(chat::Id(1));
+        storage.set("3", b"\"three\"".to_vec()).await.unwrap();
+        save(&storage, &state).await.unwrap();
+        drop(storage);
+
+        let storage = File::open(&path).await.unwrap();
+        let loaded = Mutex::new(Chats::<String>::new());
+        load(&storage, &loaded).await.unwrap();
+
+        assert_eq!(*loaded.lock().unwrap(), *state.lock().unwrap());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn converts_messages_to_entries_and_back() {
+        let id = MessageId {
+            chat_id: chat::Id(-100),
+            message_id: message::Id(42),
+        };
+        let mut messages = Messages::new();
+        messages.insert_by_id(id, 7_u8);
+
+        let entries = messages.to_entries().unwrap();
+        assert_eq!(entries, [("-100/42".to_string(), b"7".to_vec())]);
+        assert_eq!(Messages::from_entries(entries).unwrap(), messages);
+    }
+}
@@ -0,0 +1,240 @@
+use super::{Entry, Storage};
+use crate::errors;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tokio::task::spawn_blocking;
+
+/// A change written to the log.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Record {
+    Set(String, Vec<u8>),
+    Remove(String),
+}
+
+#[derive(Debug)]
+struct Inner {
+    entries: BTreeMap<String, Vec<u8>>,
+    path: PathBuf,
+    log_path: PathBuf,
+    log: fs::File,
+}
+
+impl Inner {
+    fn open(path: PathBuf) -> Result<Self, errors::Storage> {
+        let log_path = with_suffix(&path, ".log");
+
+        let mut entries = match fs::read(&path) {
+            Ok(snapshot) => serde_json::from_slice(&snapshot)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                BTreeMap::new()
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        match fs::File::open(&log_path) {
+            Ok(log) => replay(&mut entries, log)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => (),
+            Err(error) => return Err(error.into()),
+        }
+
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)?;
+
+        let inner = Self {
+            entries,
+            path,
+            log_path,
+            log,
+        };
+
+        // Compacting right away also gets rid of a partially written record
+        // which may be left if the bot crashed while writing to the log.
+        inner.compact()?;
+        Ok(inner)
+    }
+
+    fn append(&self, record: &Record) -> Result<(), errors::Storage> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        (&self.log).write_all(&line)?;
+        Ok(())
+    }
+
+    fn compact(&self) -> Result<(), errors::Storage> {
+        let temporary_path = with_suffix(&self.path, ".tmp");
+
+        let mut snapshot = fs::File::create(&temporary_path)?;
+        snapshot.write_all(&serde_json::to_vec(&self.entries)?)?;
+        snapshot.sync_all()?;
+        fs::rename(&temporary_path, &self.path)?;
+
+        self.log.set_len(0)?;
+        self.log.sync_all()?;
+        Ok(())
+    }
+}
+
+fn replay(
+    entries: &mut BTreeMap<String, Vec<u8>>,
+    log: fs::File,
+) -> Result<(), errors::Storage> {
+    let mut lines = BufReader::new(log).lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let record = match serde_json::from_str(&line?) {
+            Ok(record) => record,
+            // Only the last record may be incomplete.
+            Err(_) if lines.peek().is_none() => break,
+            Err(error) => return Err(error.into()),
+        };
+
+        match record {
+            Record::Set(key, value) => {
+                entries.insert(key, value);
+            }
+            Record::Remove(key) => {
+                entries.remove(&key);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(suffix);
+    path.into()
+}
+
+/// A storage which keeps all entries in memory and persists them in a file.
+///
+/// Each change is appended to a log file next to the snapshot file (with
+/// the `.log` suffix). When the storage is opened or flushed, the log is
+/// merged into the snapshot.
+#[derive(Debug, Clone)]
+pub struct File {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl File {
+    /// Opens the storage at `path`, creating it if it doesn't exist.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, errors::Storage> {
+        let path = path.as_ref().to_owned();
+        let inner = blocking(move || Inner::open(path)).await?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(inner)),
+        })
+    }
+
+    /// Returns the path to the snapshot file.
+    #[must_use]
+    pub fn path(&self) -> PathBuf {
+        self.lock().path.clone()
+    }
+
+    /// Returns the path to the log file.
+    #[must_use]
+    pub fn log_path(&self) -> PathBuf {
+        self.lock().log_path.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        lock(&self.inner)
+    }
+
+    async fn write(&self, record: Record) -> Result<(), errors::Storage> {
+        let inner = Arc::clone(&self.inner);
+
+        blocking(move || {
+            let mut inner = lock(&inner);
+            inner.append(&record)?;
+
+            match record {
+                Record::Set(key, value) => {
+                    inner.entries.insert(key, value);
+                }
+                Record::Remove(key) => {
+                    inner.entries.remove(&key);
+                }
+            }
+            drop(inner);
+
+            Ok(())
+        })
+        .await
+    }
+}
+
+impl Storage for File {
+    fn get<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Vec<u8>>, errors::Storage>> {
+        let value = self.lock().entries.get(key).cloned();
+        Box::pin(async move { Ok(value) })
+    }
+
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        value: Vec<u8>,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>> {
+        Box::pin(self.write(Record::Set(key.to_owned(), value)))
+    }
+
+    fn remove<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>> {
+        Box::pin(self.write(Record::Remove(key.to_owned())))
+    }
+
+    fn scan<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Entry>, errors::Storage>> {
+        let entries = self
+            .lock()
+            .entries
+            .range(prefix.to_owned()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+
+        Box::pin(async move { Ok(entries) })
+    }
+
+    fn flush(&self) -> BoxFuture<'_, Result<(), errors::Storage>> {
+        let inner = Arc::clone(&self.inner);
+        Box::pin(blocking(move || lock(&inner).compact()))
+    }
+}
+
+fn lock(inner: &Mutex<Inner>) -> MutexGuard<'_, Inner> {
+    inner.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Runs blocking file operations outside of the async runtime's threads.
+async fn blocking<T, F>(operation: F) -> T
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    match spawn_blocking(operation).await {
+        Ok(result) => result,
+        Err(error) => std::panic::resume_unwind(error.into_panic()),
+    }
+}
@@ -0,0 +1,91 @@
+use super::{Entry, Storage};
+use crate::errors;
+use futures::future::BoxFuture;
+use std::path::Path;
+
+/// A storage backed by the embedded [`sled`] database.
+///
+/// Requires the `sled` feature.
+///
+/// [`sled`]: https://docs.rs/sled
+#[derive(Debug, Clone)]
+pub struct Sled {
+    tree: sled::Tree,
+}
+
+impl Sled {
+    /// Opens a database at `path`, creating it if it doesn't exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, errors::Storage> {
+        let database = sled::open(path)?;
+        Ok(Self::from_tree((*database).clone()))
+    }
+
+    /// Uses an already opened tree. This way, the state can be stored
+    /// in the same database with your other data.
+    #[must_use]
+    pub const fn from_tree(tree: sled::Tree) -> Self {
+        Self { tree }
+    }
+}
+
+impl Storage for Sled {
+    fn get<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<Option<Vec<u8>>, errors::Storage>> {
+        Box::pin(
+            async move { Ok(self.tree.get(key)?.map(|value| value.to_vec())) },
+        )
+    }
+
+    fn set<'a>(
+        &'a self,
+        key: &'a str,
+        value: Vec<u8>,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>> {
+        Box::pin(async move {
+            self.tree.insert(key, value)?;
+            Ok(())
+        })
+    }
+
+    fn remove<'a>(
+        &'a self,
+        key: &'a str,
+    ) -> BoxFuture<'a, Result<(), errors::Storage>> {
+        Box::pin(async move {
+            self.tree.remove(key)?;
+            Ok(())
+        })
+    }
+
+    fn scan<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<Entry>, errors::Storage>> {
+        Box::pin(async move {
+            self.tree
+                .scan_prefix(prefix)
+                .map(|entry| {
+                    let (key, value) = entry?;
+                    let key =
+                        String::from_utf8(key.to_vec()).map_err(|error| {
+                            errors::Storage::InvalidKey(
+                                String::from_utf8_lossy(error.as_bytes())
+                                    .into_owned(),
+                            )
+                        })?;
+
+                    Ok((key, value.to_vec()))
+                })
+                .collect()
+        })
+    }
+
+    fn flush(&self) -> BoxFuture<'_, Result<(), errors::Storage>> {
+        Box::pin(async move {
+            self.tree.flush_async().await?;
+            Ok(())
+        })
+    }
+}
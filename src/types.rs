@@ -12,6 +12,7 @@ mod contact;
 pub mod dice;
 mod document;
 pub mod file;
+pub mod forum_topic;
 pub mod game;
 pub mod inline_message_id;
 pub mod inline_query;
@@ -30,6 +31,7 @@ pub mod passport;
 mod photo_size;
 pub mod poll;
 pub mod pre_checkout_query;
+mod proximity_alert;
 pub mod shipping;
 pub mod sticker;
 mod successful_payment;
@@ -39,6 +41,7 @@ mod venue;
 mod video;
 mod video_note;
 mod voice;
+pub mod voice_chat;
 pub mod webhook_info;
 
 pub use animation::Animation;
@@ -64,6 +67,7 @@ pub use order_info::OrderInfo;
 pub use photo_size::PhotoSize;
 pub use poll::Poll;
 pub use pre_checkout_query::PreCheckoutQuery;
+pub use proximity_alert::ProximityAlert;
 pub use sticker::Sticker;
 pub use successful_payment::SuccessfulPayment;
 pub use update::Update;
@@ -7,12 +7,13 @@ mod id;
 mod kind;
 mod location;
 pub mod member;
+mod member_updated;
 mod permissions;
 mod photo;
 
 pub use {
     action::Action, id::Id, kind::Kind, location::Location, member::Member,
-    permissions::Permissions, photo::Photo,
+    member_updated::MemberUpdated, permissions::Permissions, photo::Photo,
 };
 
 /// Represents a [`Chat`].
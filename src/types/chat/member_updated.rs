@@ -0,0 +1,23 @@
+use super::{Chat, Member};
+use crate::types::User;
+use serde::Deserialize;
+
+/// Represents a [`ChatMemberUpdated`].
+///
+/// [`ChatMemberUpdated`]: https://core.telegram.org/bots/api#chatmemberupdated
+#[derive(Debug, PartialEq, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct MemberUpdated {
+    /// The chat the member belongs to.
+    pub chat: Chat,
+    /// The user who changed the member's status.
+    pub from: User,
+    /// The timestamp of the change.
+    pub date: i64,
+    /// The member before the change.
+    #[serde(rename = "old_chat_member")]
+    pub old_member: Member,
+    /// The member after the change.
+    #[serde(rename = "new_chat_member")]
+    pub new_member: Member,
+}
@@ -88,13 +88,13 @@ impl Permissions {
 
     /// Configures if the user can invite new users to the chat.
     pub const fn can_invite_users(mut self, can_invite: bool) -> Self {
-        self.can_change_info = Some(can_invite);
+        self.can_invite_users = Some(can_invite);
         self
     }
 
     /// Configures if the user can pin messages.
     pub const fn can_pin_messages(mut self, can_pin: bool) -> Self {
-        self.can_change_info = Some(can_pin);
+        self.can_pin_messages = Some(can_pin);
         self
     }
 }
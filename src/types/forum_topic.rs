@@ -0,0 +1,17 @@
+//! Types related to forum topics.
+
+use serde::Deserialize;
+
+/// Represents a [`ForumTopicCreated`].
+///
+/// [`ForumTopicCreated`]: https://core.telegram.org/bots/api#forumtopiccreated
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct Created {
+    /// The name of the topic.
+    pub name: String,
+    /// The color of the topic's icon in RGB format.
+    pub icon_color: u32,
+    /// The ID of the custom emoji shown as the topic's icon.
+    pub icon_custom_emoji_id: Option<String>,
+}
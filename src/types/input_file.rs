@@ -19,6 +19,7 @@ mod thumb;
 mod video;
 mod video_note;
 mod voice;
+mod webm_sticker;
 
 pub(crate) use media_group::{Album, AnyGroupMedia};
 pub use {
@@ -38,6 +39,7 @@ pub use {
     video::Video,
     video_note::VideoNote,
     voice::Voice,
+    webm_sticker::WebmSticker,
 };
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
@@ -1,14 +1,20 @@
-use super::{PngSticker, TgsSticker};
+use super::{PngSticker, TgsSticker, WebmSticker};
 use crate::types::InteriorBorrow;
 use is_macro::Is;
 
 /// Represents a sticker that can be added to a sticker set.
+///
+/// The sticker is sent as the `png_sticker`, `tgs_sticker` or `webm_sticker`
+/// parameter, as in Bot API 5.7.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Is)]
+#[non_exhaustive]
 pub enum StickerForStickerSet<'a> {
     /// A PNG sticker.
     Png(PngSticker<'a>),
     /// A TGS (animated) sticker.
     Tgs(TgsSticker<'a>),
+    /// A WEBM (video) sticker.
+    Webm(WebmSticker<'a>),
 }
 
 impl<'a> From<PngSticker<'a>> for StickerForStickerSet<'a> {
@@ -23,11 +29,20 @@ impl<'a> From<TgsSticker<'a>> for StickerForStickerSet<'a> {
     }
 }
 
+impl<'a> From<WebmSticker<'a>> for StickerForStickerSet<'a> {
+    fn from(sticker: WebmSticker<'a>) -> Self {
+        Self::Webm(sticker)
+    }
+}
+
 impl<'a> InteriorBorrow<'a> for StickerForStickerSet<'a> {
     fn borrow_inside(&'a self) -> Self {
         match self {
             Self::Png(png_sticker) => Self::Png(png_sticker.borrow_inside()),
             Self::Tgs(tgs_sticker) => Self::Tgs(tgs_sticker.borrow_inside()),
+            Self::Webm(webm_sticker) => {
+                Self::Webm(webm_sticker.borrow_inside())
+            }
         }
     }
 }
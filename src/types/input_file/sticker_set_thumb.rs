@@ -30,6 +30,14 @@ impl<'a> StickerSetThumb<'a> {
         })
     }
 
+    /// Constructs a `StickerSetThumb` from bytes of `.webm` video.
+    pub fn webm_bytes(bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+        Self::new(InputFile::File {
+            filename: "thumb.webm".into(),
+            bytes: bytes.into(),
+        })
+    }
+
     /// Constructs a `StickerSetThumb` from a file ID.
     ///
     /// # Panics
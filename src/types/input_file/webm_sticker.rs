@@ -0,0 +1,32 @@
+use super::InputFile;
+use crate::types::InteriorBorrow;
+use std::borrow::Cow;
+
+/// Represents a WEBM (video) sticker to be uploaded in a sticker set.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[must_use]
+pub struct WebmSticker<'a> {
+    pub(crate) media: InputFile<'a>,
+}
+
+impl<'a> WebmSticker<'a> {
+    const fn new(media: InputFile<'a>) -> Self {
+        Self { media }
+    }
+
+    /// Constructs a `WebmSticker` from bytes.
+    pub fn with_bytes(bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+        Self::new(InputFile::File {
+            filename: "sticker.webm".into(),
+            bytes: bytes.into(),
+        })
+    }
+}
+
+impl<'a> InteriorBorrow<'a> for WebmSticker<'a> {
+    fn borrow_inside(&'a self) -> Self {
+        Self {
+            media: self.media.borrow_inside(),
+        }
+    }
+}
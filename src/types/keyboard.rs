@@ -4,5 +4,6 @@ mod any;
 mod force_reply;
 pub mod inline;
 pub mod reply;
+mod rows;
 
 pub use {any::Any, force_reply::ForceReply};
@@ -31,6 +31,12 @@ impl<'a> From<inline::Markup<'a>> for Any<'a> {
     }
 }
 
+impl<'a, 'b: 'a> From<&'a inline::OwnedKeyboard<'b>> for Any<'a> {
+    fn from(keyboard: &'a inline::OwnedKeyboard<'b>) -> Self {
+        Any::Inline(keyboard.as_keyboard())
+    }
+}
+
 impl<'a> From<reply::Keyboard<'a>> for Any<'a> {
     fn from(keyboard: reply::Keyboard<'a>) -> Self {
         Any::Reply(keyboard)
@@ -43,6 +49,12 @@ impl<'a> From<reply::Markup<'a>> for Any<'a> {
     }
 }
 
+impl<'a, 'b: 'a> From<&'a reply::OwnedKeyboard<'b>> for Any<'a> {
+    fn from(keyboard: &'a reply::OwnedKeyboard<'b>) -> Self {
+        Any::Reply(keyboard.as_keyboard())
+    }
+}
+
 impl<'a> From<reply::Remove> for Any<'a> {
     fn from(keyboard: reply::Remove) -> Self {
         Any::RemoveReply(keyboard)
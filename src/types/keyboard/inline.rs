@@ -1,9 +1,10 @@
 //! Types representing inline keyboards.
 
+use super::rows::{self, Rows};
 use crate::types::{callback::Game, InteriorBorrow, LoginUrl};
 use is_macro::Is;
 use serde::{ser::SerializeMap, Serialize};
-use std::borrow::Cow;
+use std::{borrow::Cow, num::NonZeroUsize};
 
 /// A shorthand for inline markup.
 pub type Markup<'a> = &'a [&'a [Button<'a>]];
@@ -111,13 +112,58 @@ pub struct Button<'a> {
 
 /// Represents an [`InlineKeyboardMarkup`].
 ///
+/// A keyboard borrows its buttons either from a [`Markup`] or from
+/// an [`OwnedKeyboard`].
+///
 /// [`InlineKeyboardMarkup`]: https://core.telegram.org/bots/api#inlinekeyboardmarkup
+/// [`Markup`]: ./type.Markup.html
+/// [`OwnedKeyboard`]: ./struct.OwnedKeyboard.html
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
 #[must_use]
 pub struct Keyboard<'a> {
-    inline_keyboard: Markup<'a>,
+    inline_keyboard: Rows<'a, Button<'a>>,
+}
+
+/// An inline keyboard which owns its buttons, e.g. one built at runtime.
+///
+/// Use [`as_keyboard`] to get a [`Keyboard`] borrowing the buttons, or pass
+/// a reference to it where a [`keyboard::Any`] is expected.
+///
+/// [`as_keyboard`]: #method.as_keyboard
+/// [`Keyboard`]: ./struct.Keyboard.html
+/// [`keyboard::Any`]: ../enum.Any.html
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+#[must_use]
+pub struct OwnedKeyboard<'a> {
+    buttons: Vec<Vec<Button<'a>>>,
 }
 
+/// Builds an inline [`OwnedKeyboard`].
+///
+/// ```
+/// use tbot::types::keyboard::inline::{
+///     Builder, Button, ButtonKind, OwnedKeyboard,
+/// };
+/// use std::num::NonZeroUsize;
+///
+/// fn pages(count: u32) -> OwnedKeyboard<'static> {
+///     Builder::new()
+///         .columns(NonZeroUsize::new(5).unwrap())
+///         .buttons((1..=count).map(|page| {
+///             let data = ButtonKind::with_callback_data(page.to_string());
+///             Button::new(page.to_string(), data)
+///         }))
+///         .row()
+///         .button(Button::new("Close", ButtonKind::with_callback_data("close")))
+///         .build()
+/// }
+/// ```
+///
+/// [`OwnedKeyboard`]: ./struct.OwnedKeyboard.html
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Builder<'a>(rows::Builder<Button<'a>>);
+
 impl<'a> Button<'a> {
     /// Constructs an inline `Button`.
     pub fn new(text: impl Into<Cow<'a, str>>, kind: ButtonKind<'a>) -> Self {
@@ -171,7 +217,7 @@ impl<'a> Keyboard<'a> {
     /// Constructs an inline `Keyboard`.
     pub const fn new(buttons: Markup<'a>) -> Self {
         Self {
-            inline_keyboard: buttons,
+            inline_keyboard: Rows::Markup(buttons),
         }
     }
 }
@@ -182,8 +228,89 @@ impl<'a> From<Markup<'a>> for Keyboard<'a> {
     }
 }
 
+impl<'a, 'b: 'a> From<&'a OwnedKeyboard<'b>> for Keyboard<'a> {
+    fn from(keyboard: &'a OwnedKeyboard<'b>) -> Self {
+        keyboard.as_keyboard()
+    }
+}
+
 impl<'a> InteriorBorrow<'a> for Keyboard<'a> {
     fn borrow_inside(&'a self) -> Self {
         Self { ..*self }
     }
 }
+
+impl<'a> OwnedKeyboard<'a> {
+    /// Constructs an `OwnedKeyboard`.
+    pub const fn new(buttons: Vec<Vec<Button<'a>>>) -> Self {
+        Self { buttons }
+    }
+
+    /// Returns a [`Keyboard`] borrowing the buttons.
+    ///
+    /// [`Keyboard`]: ./struct.Keyboard.html
+    pub fn as_keyboard(&self) -> Keyboard<'_> {
+        Keyboard {
+            inline_keyboard: Rows::Owned(&self.buttons),
+        }
+    }
+}
+
+impl<'a> From<Vec<Vec<Button<'a>>>> for OwnedKeyboard<'a> {
+    fn from(buttons: Vec<Vec<Button<'a>>>) -> Self {
+        Self::new(buttons)
+    }
+}
+
+impl<'a> Builder<'a> {
+    /// Constructs an empty `Builder`.
+    pub const fn new() -> Self {
+        Self(rows::Builder::new())
+    }
+
+    /// Configures how many buttons fit in a row. Once a row is full,
+    /// the next button starts a new row. By default, rows are unlimited.
+    pub fn columns(mut self, columns: NonZeroUsize) -> Self {
+        self.0.columns(columns);
+        self
+    }
+
+    /// Starts a new row.
+    pub fn row(mut self) -> Self {
+        self.0.row();
+        self
+    }
+
+    /// Adds a button to the current row.
+    pub fn button(mut self, button: Button<'a>) -> Self {
+        self.0.button(button);
+        self
+    }
+
+    /// Adds several buttons to the current row, wrapping them if the number
+    /// of columns is configured.
+    pub fn buttons(
+        mut self,
+        buttons: impl IntoIterator<Item = Button<'a>>,
+    ) -> Self {
+        buttons.into_iter().for_each(|button| self.0.button(button));
+        self
+    }
+
+    /// Builds the keyboard. Empty rows are skipped.
+    pub fn build(self) -> OwnedKeyboard<'a> {
+        OwnedKeyboard::new(self.0.build())
+    }
+}
+
+impl Default for Builder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> From<Builder<'a>> for OwnedKeyboard<'a> {
+    fn from(builder: Builder<'a>) -> Self {
+        builder.build()
+    }
+}
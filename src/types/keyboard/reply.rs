@@ -1,9 +1,10 @@
 //! Types representing reply keyboards.
 
+use super::rows::{self, Rows};
 use crate::types::InteriorBorrow;
 use is_macro::Is;
 use serde::{ser::SerializeMap, Serialize};
-use std::borrow::Cow;
+use std::{borrow::Cow, num::NonZeroUsize};
 
 /// A shorthand for reply markup.
 pub type Markup<'a> = &'a [&'a [Button<'a>]];
@@ -66,11 +67,16 @@ pub struct Button<'a> {
 
 /// Represents a [`ReplyKeyboardMarkup`].
 ///
+/// A keyboard borrows its buttons either from a [`Markup`] or from
+/// an [`OwnedKeyboard`].
+///
 /// [`ReplyKeyboardMarkup`]: https://core.telegram.org/bots/api#replykeyboardmarkup
+/// [`Markup`]: ./type.Markup.html
+/// [`OwnedKeyboard`]: ./struct.OwnedKeyboard.html
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
 #[must_use]
 pub struct Keyboard<'a> {
-    keyboard: Markup<'a>,
+    keyboard: Rows<'a, Button<'a>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     resize_keyboard: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -79,6 +85,43 @@ pub struct Keyboard<'a> {
     selective: Option<bool>,
 }
 
+/// A reply keyboard which owns its buttons, e.g. one built at runtime.
+///
+/// Use [`as_keyboard`] to get a [`Keyboard`] borrowing the buttons, or pass
+/// a reference to it where a [`keyboard::Any`] is expected.
+///
+/// [`as_keyboard`]: #method.as_keyboard
+/// [`Keyboard`]: ./struct.Keyboard.html
+/// [`keyboard::Any`]: ../enum.Any.html
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+#[must_use]
+pub struct OwnedKeyboard<'a> {
+    keyboard: Vec<Vec<Button<'a>>>,
+    resize_keyboard: Option<bool>,
+    one_time_keyboard: Option<bool>,
+    selective: Option<bool>,
+}
+
+/// Builds a reply [`OwnedKeyboard`].
+///
+/// ```
+/// use tbot::types::keyboard::reply::{Builder, Button, OwnedKeyboard};
+/// use std::num::NonZeroUsize;
+///
+/// fn options(options: &[String]) -> OwnedKeyboard<'static> {
+///     Builder::new()
+///         .columns(NonZeroUsize::new(2).unwrap())
+///         .buttons(options.iter().map(|option| Button::new(option.clone())))
+///         .build()
+///         .is_one_time(true)
+/// }
+/// ```
+///
+/// [`OwnedKeyboard`]: ./struct.OwnedKeyboard.html
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Builder<'a>(rows::Builder<Button<'a>>);
+
 /// Represents a [`ReplyKeyboardRemove`].
 ///
 /// [`ReplyKeyboardRemove`]: https://core.telegram.org/bots/api#replykeyboardremove
@@ -143,7 +186,7 @@ impl<'a> Keyboard<'a> {
     /// Constructs a reply `Keyboard`.
     pub const fn new(keyboard: Markup<'a>) -> Self {
         Self {
-            keyboard,
+            keyboard: Rows::Markup(keyboard),
             resize_keyboard: None,
             one_time_keyboard: None,
             selective: None,
@@ -181,6 +224,113 @@ impl<'a> From<Markup<'a>> for Keyboard<'a> {
     }
 }
 
+impl<'a, 'b: 'a> From<&'a OwnedKeyboard<'b>> for Keyboard<'a> {
+    fn from(keyboard: &'a OwnedKeyboard<'b>) -> Self {
+        keyboard.as_keyboard()
+    }
+}
+
+impl<'a> OwnedKeyboard<'a> {
+    /// Constructs an `OwnedKeyboard`.
+    pub const fn new(keyboard: Vec<Vec<Button<'a>>>) -> Self {
+        Self {
+            keyboard,
+            resize_keyboard: None,
+            one_time_keyboard: None,
+            selective: None,
+        }
+    }
+
+    /// Configures `resize_keyboard`.
+    pub const fn is_resizable(mut self, is_resized: bool) -> Self {
+        self.resize_keyboard = Some(is_resized);
+        self
+    }
+
+    /// Configures `one_time_keyboard`.
+    pub const fn is_one_time(mut self, is_one_time: bool) -> Self {
+        self.one_time_keyboard = Some(is_one_time);
+        self
+    }
+
+    /// Configures `selective`.
+    pub const fn is_selective(mut self, is_selective: bool) -> Self {
+        self.selective = Some(is_selective);
+        self
+    }
+
+    /// Returns a [`Keyboard`] borrowing the buttons.
+    ///
+    /// [`Keyboard`]: ./struct.Keyboard.html
+    pub fn as_keyboard(&self) -> Keyboard<'_> {
+        Keyboard {
+            keyboard: Rows::Owned(&self.keyboard),
+            resize_keyboard: self.resize_keyboard,
+            one_time_keyboard: self.one_time_keyboard,
+            selective: self.selective,
+        }
+    }
+}
+
+impl<'a> From<Vec<Vec<Button<'a>>>> for OwnedKeyboard<'a> {
+    fn from(keyboard: Vec<Vec<Button<'a>>>) -> Self {
+        Self::new(keyboard)
+    }
+}
+
+impl<'a> Builder<'a> {
+    /// Constructs an empty `Builder`.
+    pub const fn new() -> Self {
+        Self(rows::Builder::new())
+    }
+
+    /// Configures how many buttons fit in a row. Once a row is full,
+    /// the next button starts a new row. By default, rows are unlimited.
+    pub fn columns(mut self, columns: NonZeroUsize) -> Self {
+        self.0.columns(columns);
+        self
+    }
+
+    /// Starts a new row.
+    pub fn row(mut self) -> Self {
+        self.0.row();
+        self
+    }
+
+    /// Adds a button to the current row.
+    pub fn button(mut self, button: Button<'a>) -> Self {
+        self.0.button(button);
+        self
+    }
+
+    /// Adds several buttons to the current row, wrapping them if the number
+    /// of columns is configured.
+    pub fn buttons(
+        mut self,
+        buttons: impl IntoIterator<Item = Button<'a>>,
+    ) -> Self {
+        buttons.into_iter().for_each(|button| self.0.button(button));
+        self
+    }
+
+    /// Builds the keyboard. Empty rows are skipped.
+    pub fn build(self) -> OwnedKeyboard<'a> {
+        OwnedKeyboard::new(self.0.build())
+    }
+}
+
+impl Default for Builder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> From<Builder<'a>> for OwnedKeyboard<'a> {
+    fn from(builder: Builder<'a>) -> Self {
+        builder.build()
+    }
+}
+
 impl Remove {
     /// Constructs a `reply::Remove`.
     pub const fn new() -> Self {
@@ -0,0 +1,126 @@
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+use std::{
+    hash::{Hash, Hasher},
+    num::NonZeroUsize,
+};
+
+/// Rows of a keyboard, borrowed either from a `Markup` or from an owned
+/// keyboard. Both are references, so keyboards stay cheap to copy and can be
+/// used in `const fn`s.
+#[derive(Debug)]
+pub enum Rows<'a, B> {
+    /// Rows of the borrowed `Markup` API.
+    Markup(&'a [&'a [B]]),
+    /// Rows of an owned keyboard.
+    Owned(&'a [Vec<B>]),
+}
+
+impl<B> Rows<'_, B> {
+    fn iter(&self) -> Box<dyn Iterator<Item = &[B]> + '_> {
+        match *self {
+            Self::Markup(rows) => Box::new(rows.iter().copied()),
+            Self::Owned(rows) => Box::new(rows.iter().map(Vec::as_slice)),
+        }
+    }
+}
+
+impl<B> Clone for Rows<'_, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B> Copy for Rows<'_, B> {}
+
+impl<B: PartialEq> PartialEq for Rows<'_, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<B: Eq> Eq for Rows<'_, B> {}
+
+impl<B: Hash> Hash for Rows<'_, B> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.iter().for_each(|row| row.hash(state));
+    }
+}
+
+impl<B: Serialize> Serialize for Rows<'_, B> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(None)?;
+        for row in self.iter() {
+            seq.serialize_element(row)?;
+        }
+        seq.end()
+    }
+}
+
+/// The logic shared between inline and reply keyboard builders.
+#[derive(Debug, Clone)]
+pub struct Builder<B> {
+    rows: Vec<Vec<B>>,
+    columns: Option<NonZeroUsize>,
+}
+
+impl<B> Builder<B> {
+    pub const fn new() -> Self {
+        Self {
+            rows: Vec::new(),
+            columns: None,
+        }
+    }
+
+    pub fn columns(&mut self, columns: NonZeroUsize) {
+        self.columns = Some(columns);
+    }
+
+    pub fn row(&mut self) {
+        if self.rows.last().map_or(true, |row| !row.is_empty()) {
+            self.rows.push(Vec::new());
+        }
+    }
+
+    pub fn button(&mut self, button: B) {
+        let is_full = match (self.rows.last(), self.columns) {
+            (None, _) => true,
+            (Some(row), Some(columns)) => row.len() >= columns.get(),
+            (Some(_), None) => false,
+        };
+
+        if is_full {
+            self.rows.push(Vec::new());
+        }
+
+        if let Some(row) = self.rows.last_mut() {
+            row.push(button);
+        }
+    }
+
+    pub fn build(mut self) -> Vec<Vec<B>> {
+        self.rows.retain(|row| !row.is_empty());
+        self.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_buttons_by_columns() {
+        let mut builder = Builder::new();
+        builder.columns(NonZeroUsize::new(2).unwrap());
+        (1..=3).for_each(|button| builder.button(button));
+        builder.row();
+        builder.row();
+        builder.button(4);
+
+        let markup: &[&[i32]] = &[&[1, 2], &[3], &[4]];
+        assert_eq!(Rows::Owned(&builder.build()), Rows::Markup(markup));
+        assert_eq!(
+            serde_json::to_string(&Rows::Markup(markup)).unwrap(),
+            "[[1,2],[3],[4]]",
+        );
+    }
+}
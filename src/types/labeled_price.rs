@@ -21,6 +21,18 @@ impl<'a> LabeledPrice<'a> {
             amount,
         }
     }
+
+    /// Returns the label of the price.
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the amount in the currency's minor units.
+    #[must_use]
+    pub const fn amount(&self) -> u32 {
+        self.amount
+    }
 }
 
 impl<'a> InteriorBorrow<'a> for LabeledPrice<'a> {
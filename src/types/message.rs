@@ -1,6 +1,7 @@
 //! Types related to messages.
 
 use crate::types::{Chat, User};
+use serde::de::IgnoredAny;
 
 pub mod forward;
 mod from;
@@ -141,9 +142,28 @@ const INVOICE: &str = "invoice";
 const SUCCESSFUL_PAYMENT: &str = "successful_payment";
 const CONNECTED_WEBSITE: &str = "connected_website";
 const PASSPORT_DATA: &str = "passport_data";
+const PROXIMITY_ALERT_TRIGGERED: &str = "proximity_alert_triggered";
+const MESSAGE_AUTO_DELETE_TIMER_CHANGED: &str =
+    "message_auto_delete_timer_changed";
+const VOICE_CHAT_SCHEDULED: &str = "voice_chat_scheduled";
+const VOICE_CHAT_STARTED: &str = "voice_chat_started";
+const VOICE_CHAT_ENDED: &str = "voice_chat_ended";
+const VOICE_CHAT_PARTICIPANTS_INVITED: &str = "voice_chat_participants_invited";
+const VIDEO_CHAT_SCHEDULED: &str = "video_chat_scheduled";
+const VIDEO_CHAT_STARTED: &str = "video_chat_started";
+const VIDEO_CHAT_ENDED: &str = "video_chat_ended";
+const VIDEO_CHAT_PARTICIPANTS_INVITED: &str = "video_chat_participants_invited";
+const FORUM_TOPIC_CREATED: &str = "forum_topic_created";
+const FORUM_TOPIC_CLOSED: &str = "forum_topic_closed";
+const FORUM_TOPIC_REOPENED: &str = "forum_topic_reopened";
 const REPLY_MARKUP: &str = "reply_markup";
 const VIA_BOT: &str = "via_bot";
 
+#[derive(serde::Deserialize)]
+struct AutoDeleteTimerChanged {
+    message_auto_delete_time: u32,
+}
+
 struct MessageVisitor;
 
 impl<'v> serde::de::Visitor<'v> for MessageVisitor {
@@ -207,6 +227,15 @@ impl<'v> serde::de::Visitor<'v> for MessageVisitor {
         let mut successful_payment = None;
         let mut connected_website = None;
         let mut passport_data = None;
+        let mut proximity_alert_triggered = None;
+        let mut message_auto_delete_timer_changed = None;
+        let mut voice_chat_scheduled = None;
+        let mut voice_chat_started = false;
+        let mut voice_chat_ended = None;
+        let mut voice_chat_participants_invited = None;
+        let mut forum_topic_created = None;
+        let mut forum_topic_closed = false;
+        let mut forum_topic_reopened = false;
         let mut reply_markup = None;
         let mut via_bot = None;
 
@@ -280,6 +309,39 @@ impl<'v> serde::de::Visitor<'v> for MessageVisitor {
                     connected_website = Some(map.next_value()?)
                 }
                 PASSPORT_DATA => passport_data = Some(map.next_value()?),
+                PROXIMITY_ALERT_TRIGGERED => {
+                    proximity_alert_triggered = Some(map.next_value()?)
+                }
+                MESSAGE_AUTO_DELETE_TIMER_CHANGED => {
+                    let timer: AutoDeleteTimerChanged = map.next_value()?;
+                    message_auto_delete_timer_changed =
+                        Some(timer.message_auto_delete_time);
+                }
+                VOICE_CHAT_SCHEDULED | VIDEO_CHAT_SCHEDULED => {
+                    voice_chat_scheduled = Some(map.next_value()?)
+                }
+                VOICE_CHAT_STARTED | VIDEO_CHAT_STARTED => {
+                    map.next_value::<IgnoredAny>()?;
+                    voice_chat_started = true;
+                }
+                VOICE_CHAT_ENDED | VIDEO_CHAT_ENDED => {
+                    voice_chat_ended = Some(map.next_value()?)
+                }
+                VOICE_CHAT_PARTICIPANTS_INVITED
+                | VIDEO_CHAT_PARTICIPANTS_INVITED => {
+                    voice_chat_participants_invited = Some(map.next_value()?)
+                }
+                FORUM_TOPIC_CREATED => {
+                    forum_topic_created = Some(map.next_value()?)
+                }
+                FORUM_TOPIC_CLOSED => {
+                    map.next_value::<IgnoredAny>()?;
+                    forum_topic_closed = true;
+                }
+                FORUM_TOPIC_REOPENED => {
+                    map.next_value::<IgnoredAny>()?;
+                    forum_topic_reopened = true;
+                }
                 REPLY_MARKUP => reply_markup = Some(map.next_value()?),
                 VIA_BOT => via_bot = Some(map.next_value()?),
                 _ => {
@@ -403,6 +465,24 @@ impl<'v> serde::de::Visitor<'v> for MessageVisitor {
             Kind::PassportData(passport_data)
         } else if let Some(dice) = dice {
             Kind::Dice(dice)
+        } else if let Some(alert) = proximity_alert_triggered {
+            Kind::ProximityAlert(alert)
+        } else if let Some(time) = message_auto_delete_timer_changed {
+            Kind::AutoDeleteTimerChanged(time)
+        } else if let Some(scheduled) = voice_chat_scheduled {
+            Kind::VoiceChatScheduled(scheduled)
+        } else if voice_chat_started {
+            Kind::VoiceChatStarted
+        } else if let Some(ended) = voice_chat_ended {
+            Kind::VoiceChatEnded(ended)
+        } else if let Some(invited) = voice_chat_participants_invited {
+            Kind::VoiceChatParticipantsInvited(invited)
+        } else if let Some(created) = forum_topic_created {
+            Kind::ForumTopicCreated(created)
+        } else if forum_topic_closed {
+            Kind::ForumTopicClosed
+        } else if forum_topic_reopened {
+            Kind::ForumTopicReopened
         } else {
             Kind::Unknown
         };
@@ -490,6 +570,19 @@ impl<'de> serde::Deserialize<'de> for Message {
                 SUCCESSFUL_PAYMENT,
                 CONNECTED_WEBSITE,
                 PASSPORT_DATA,
+                PROXIMITY_ALERT_TRIGGERED,
+                MESSAGE_AUTO_DELETE_TIMER_CHANGED,
+                VOICE_CHAT_SCHEDULED,
+                VOICE_CHAT_STARTED,
+                VOICE_CHAT_ENDED,
+                VOICE_CHAT_PARTICIPANTS_INVITED,
+                VIDEO_CHAT_SCHEDULED,
+                VIDEO_CHAT_STARTED,
+                VIDEO_CHAT_ENDED,
+                VIDEO_CHAT_PARTICIPANTS_INVITED,
+                FORUM_TOPIC_CREATED,
+                FORUM_TOPIC_CLOSED,
+                FORUM_TOPIC_REOPENED,
                 REPLY_MARKUP,
                 VIA_BOT,
             ],
@@ -497,3 +590,42 @@ impl<'de> serde::Deserialize<'de> for Message {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::voice_chat;
+
+    fn kind(service: &str) -> Kind {
+        let message = format!(
+            r#"{{
+                "message_id": 1,
+                "date": 0,
+                "chat": {{ "id": -1, "type": "group", "title": "Rust" }},
+                {}
+            }}"#,
+            service,
+        );
+        serde_json::from_str::<Message>(&message).unwrap().kind
+    }
+
+    #[test]
+    fn parses_video_chats_as_voice_chats() {
+        assert_eq!(
+            kind(r#""video_chat_scheduled": { "start_date": 60 }"#),
+            Kind::VoiceChatScheduled(voice_chat::Scheduled { start_date: 60 }),
+        );
+        assert_eq!(kind(r#""video_chat_started": {}"#), Kind::VoiceChatStarted);
+        assert_eq!(
+            kind(r#""video_chat_ended": { "duration": 30 }"#),
+            Kind::VoiceChatEnded(voice_chat::Ended { duration: 30 }),
+        );
+        assert_eq!(
+            kind(r#""video_chat_participants_invited": { "users": [] }"#),
+            Kind::VoiceChatParticipantsInvited(
+                voice_chat::ParticipantsInvited { users: Vec::new() }
+            ),
+        );
+        assert_eq!(kind(r#""voice_chat_started": {}"#), Kind::VoiceChatStarted);
+    }
+}
@@ -1,8 +1,8 @@
 use super::{Message, Text};
 use crate::types::{
-    chat, passport, Animation, Audio, Contact, Dice, Document, Game, Invoice,
-    Location, PhotoSize, Poll, Sticker, SuccessfulPayment, User, Venue, Video,
-    VideoNote, Voice,
+    chat, forum_topic, passport, voice_chat, Animation, Audio, Contact, Dice,
+    Document, Game, Invoice, Location, PhotoSize, Poll, ProximityAlert,
+    Sticker, SuccessfulPayment, User, Venue, Video, VideoNote, Voice,
 };
 use is_macro::Is;
 
@@ -108,6 +108,27 @@ pub enum Kind {
     ConnectedWebsite(String),
     /// Passport data.
     PassportData(passport::Data),
+    /// A service message that a user in the chat triggered another user's
+    /// proximity alert.
+    ProximityAlert(ProximityAlert),
+    /// A service message that the auto-delete timer was changed. The value
+    /// is the new auto-delete time in seconds, or `0` if auto-deletion
+    /// was disabled.
+    AutoDeleteTimerChanged(u32),
+    /// A service message that a voice chat was scheduled.
+    VoiceChatScheduled(voice_chat::Scheduled),
+    /// A service message that a voice chat was started.
+    VoiceChatStarted,
+    /// A service message that a voice chat ended.
+    VoiceChatEnded(voice_chat::Ended),
+    /// A service message that users were invited to a voice chat.
+    VoiceChatParticipantsInvited(voice_chat::ParticipantsInvited),
+    /// A service message that a forum topic was created.
+    ForumTopicCreated(forum_topic::Created),
+    /// A service message that a forum topic was closed.
+    ForumTopicClosed,
+    /// A service message that a forum topic was reopened.
+    ForumTopicReopened,
     /// Some unkonwn message kind. Probably means `tbot` is outdated.
     Unknown,
 }
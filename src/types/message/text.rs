@@ -1,6 +1,10 @@
 //! Types representing text.
 
-use crate::types::User;
+use crate::{
+    markup::{html, markdown_v2},
+    types::{parameters, User},
+    util::entities,
+};
 use is_macro::Is;
 use serde::de::{Deserialize, Deserializer, Error, Visitor};
 use std::fmt::{self, Formatter};
@@ -15,6 +19,20 @@ pub struct Text {
     pub entities: Vec<Entity>,
 }
 
+impl Text {
+    /// Renders the text with its formatting as HTML, e.g. to repost
+    /// a received message. All special characters are escaped.
+    pub fn to_html(&self) -> parameters::Text<'static> {
+        html(entities(self)).into()
+    }
+
+    /// Renders the text with its formatting as MarkdownV2, e.g. to repost
+    /// a received message. All special characters are escaped.
+    pub fn to_markdown_v2(&self) -> parameters::Text<'static> {
+        markdown_v2(entities(self)).into()
+    }
+}
+
 /// Represents an entity's kind.
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Is)]
 #[non_exhaustive]
@@ -1,8 +1,10 @@
 //! Types used as parameters, mainly for methods.
 
 mod bot_command;
+mod bot_command_scope;
 mod callback_action;
 mod chat_id;
+pub mod invoice;
 mod photo;
 pub mod poll;
 mod text;
@@ -11,8 +13,10 @@ mod update_kind;
 pub(crate) use text::ParseMode;
 pub use {
     bot_command::BotCommand,
+    bot_command_scope::BotCommandScope,
     callback_action::CallbackAction,
     chat_id::{ChatId, ImplicitChatId},
+    invoice::Invoice,
     photo::Photo,
     text::Text,
     update_kind::UpdateKind,
@@ -0,0 +1,87 @@
+use crate::types::{parameters::ChatId, user, InteriorBorrow};
+use is_macro::Is;
+use serde::Serialize;
+
+/// Represents a [`BotCommandScope`][docs], i.e. the set of users for whom
+/// a list of the bot's commands is shown.
+///
+/// [docs]: https://core.telegram.org/bots/api#botcommandscope
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Is)]
+#[serde(rename_all = "snake_case", tag = "type")]
+#[non_exhaustive]
+#[must_use]
+pub enum BotCommandScope<'a> {
+    /// The default scope, used if no commands are set for a narrower scope.
+    Default,
+    /// Covers all private chats.
+    AllPrivateChats,
+    /// Covers all group and supergroup chats.
+    AllGroupChats,
+    /// Covers all group and supergroup chat administrators.
+    AllChatAdministrators,
+    /// Covers a specific chat.
+    Chat {
+        /// The chat which the commands are shown in.
+        chat_id: ChatId<'a>,
+    },
+    /// Covers all administrators of a specific group or supergroup chat.
+    ChatAdministrators {
+        /// The chat whose administrators see the commands.
+        chat_id: ChatId<'a>,
+    },
+    /// Covers a specific member of a group or supergroup chat.
+    ChatMember {
+        /// The chat where the member sees the commands.
+        chat_id: ChatId<'a>,
+        /// The member who sees the commands.
+        user_id: user::Id,
+    },
+}
+
+impl<'a> BotCommandScope<'a> {
+    /// Constructs a scope covering a specific chat.
+    pub fn chat(chat_id: impl Into<ChatId<'a>>) -> Self {
+        Self::Chat {
+            chat_id: chat_id.into(),
+        }
+    }
+
+    /// Constructs a scope covering all administrators of a specific chat.
+    pub fn chat_administrators(chat_id: impl Into<ChatId<'a>>) -> Self {
+        Self::ChatAdministrators {
+            chat_id: chat_id.into(),
+        }
+    }
+
+    /// Constructs a scope covering a specific member of a chat.
+    pub fn chat_member(
+        chat_id: impl Into<ChatId<'a>>,
+        user_id: user::Id,
+    ) -> Self {
+        Self::ChatMember {
+            chat_id: chat_id.into(),
+            user_id,
+        }
+    }
+}
+
+impl<'a> InteriorBorrow<'a> for BotCommandScope<'a> {
+    fn borrow_inside(&'a self) -> Self {
+        match self {
+            Self::Default => Self::Default,
+            Self::AllPrivateChats => Self::AllPrivateChats,
+            Self::AllGroupChats => Self::AllGroupChats,
+            Self::AllChatAdministrators => Self::AllChatAdministrators,
+            Self::Chat { chat_id } => Self::Chat {
+                chat_id: chat_id.borrow_inside(),
+            },
+            Self::ChatAdministrators { chat_id } => Self::ChatAdministrators {
+                chat_id: chat_id.borrow_inside(),
+            },
+            Self::ChatMember { chat_id, user_id } => Self::ChatMember {
+                chat_id: chat_id.borrow_inside(),
+                user_id: *user_id,
+            },
+        }
+    }
+}
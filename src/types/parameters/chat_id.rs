@@ -1,10 +1,10 @@
 use crate::types::{chat, user, InteriorBorrow};
 use is_macro::Is;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 
 /// Represents possible ways to specify the destination chat.
-#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Is)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize, Is)]
 #[serde(untagged)]
 #[non_exhaustive]
 #[must_use]
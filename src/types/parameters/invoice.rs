@@ -0,0 +1,427 @@
+//! Types related to invoices.
+
+use super::Photo;
+use crate::{errors, types::LabeledPrice};
+use serde::Serialize;
+use std::borrow::Cow;
+
+/// Currencies supported by Telegram, along with the number of fractional
+/// digits in their minor units.
+const CURRENCIES: &[(&str, u32)] = &[
+    ("AED", 2),
+    ("AFN", 2),
+    ("ALL", 2),
+    ("AMD", 2),
+    ("ARS", 2),
+    ("AUD", 2),
+    ("AZN", 2),
+    ("BAM", 2),
+    ("BDT", 2),
+    ("BGN", 2),
+    ("BND", 2),
+    ("BOB", 2),
+    ("BRL", 2),
+    ("BYN", 2),
+    ("CAD", 2),
+    ("CHF", 2),
+    ("CLP", 0),
+    ("CNY", 2),
+    ("COP", 2),
+    ("CRC", 2),
+    ("CZK", 2),
+    ("DKK", 2),
+    ("DOP", 2),
+    ("DZD", 2),
+    ("EGP", 2),
+    ("ETB", 2),
+    ("EUR", 2),
+    ("GBP", 2),
+    ("GEL", 2),
+    ("GHS", 2),
+    ("GTQ", 2),
+    ("HKD", 2),
+    ("HNL", 2),
+    ("HUF", 2),
+    ("IDR", 2),
+    ("ILS", 2),
+    ("INR", 2),
+    ("IQD", 2),
+    ("ISK", 0),
+    ("JMD", 2),
+    ("JPY", 0),
+    ("KES", 2),
+    ("KGS", 2),
+    ("KRW", 0),
+    ("KZT", 2),
+    ("LBP", 2),
+    ("LKR", 2),
+    ("MAD", 2),
+    ("MDL", 2),
+    ("MMK", 2),
+    ("MNT", 2),
+    ("MOP", 2),
+    ("MUR", 2),
+    ("MVR", 2),
+    ("MXN", 2),
+    ("MYR", 2),
+    ("MZN", 2),
+    ("NGN", 2),
+    ("NIO", 2),
+    ("NOK", 2),
+    ("NPR", 2),
+    ("NZD", 2),
+    ("PAB", 2),
+    ("PEN", 2),
+    ("PHP", 2),
+    ("PKR", 2),
+    ("PLN", 2),
+    ("PYG", 0),
+    ("QAR", 2),
+    ("RON", 2),
+    ("RSD", 2),
+    ("RUB", 2),
+    ("SAR", 2),
+    ("SEK", 2),
+    ("SGD", 2),
+    ("SYP", 2),
+    ("THB", 2),
+    ("TJS", 2),
+    ("TRY", 2),
+    ("TTD", 2),
+    ("TWD", 2),
+    ("TZS", 2),
+    ("UAH", 2),
+    ("UGX", 0),
+    ("USD", 2),
+    ("UYU", 2),
+    ("UZS", 2),
+    ("VND", 0),
+    ("XTR", 0),
+    ("YER", 2),
+    ("ZAR", 2),
+];
+
+const MAX_SUGGESTED_TIPS: usize = 4;
+
+/// Returns the number of fractional digits in the currency's minor units,
+/// e.g. `2` for `USD` and `0` for `JPY`. Returns `None` if Telegram doesn't
+/// support the currency.
+#[must_use]
+pub fn exponent(currency: &str) -> Option<u32> {
+    CURRENCIES
+        .binary_search_by_key(&currency, |(code, _)| code)
+        .ok()
+        .map(|index| CURRENCIES[index].1)
+}
+
+/// Parses a decimal amount, e.g. `"9.99"`, into the currency's minor units,
+/// which [`LabeledPrice`] expects.
+///
+/// ```
+/// use tbot::types::parameters::invoice::parse_amount;
+///
+/// assert_eq!(parse_amount("USD", "9.99"), Ok(999));
+/// assert_eq!(parse_amount("USD", "10"), Ok(1000));
+/// assert_eq!(parse_amount("JPY", "500"), Ok(500));
+/// assert!(parse_amount("JPY", "4.5").is_err());
+/// ```
+///
+/// [`LabeledPrice`]: ../../struct.LabeledPrice.html
+pub fn parse_amount(
+    currency: &str,
+    amount: &str,
+) -> Result<u32, errors::Invoice> {
+    let exponent = exponent(currency)
+        .ok_or_else(|| errors::Invoice::UnknownCurrency(currency.to_owned()))?;
+    let invalid = || errors::Invoice::InvalidAmount(amount.to_owned());
+
+    let (integer, fraction) = amount
+        .find('.')
+        .map_or((amount, ""), |dot| (&amount[..dot], &amount[dot + 1..]));
+    let is_number = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if integer.is_empty()
+        || !is_number(integer)
+        || !is_number(fraction)
+        || (amount.contains('.') && fraction.is_empty())
+    {
+        return Err(invalid());
+    }
+
+    if fraction.len() > exponent as usize {
+        return Err(errors::Invoice::TooManyFractionDigits { exponent });
+    }
+
+    let mut digits = String::with_capacity(integer.len() + exponent as usize);
+    digits.push_str(integer);
+    digits.push_str(fraction);
+    for _ in fraction.len()..exponent as usize {
+        digits.push('0');
+    }
+
+    digits.parse().map_err(|_| invalid())
+}
+
+/// Represents an invoice to be sent with [`SendInvoice`] or turned into
+/// a link with [`CreateInvoiceLink`].
+///
+/// [`SendInvoice`]: ../../../methods/struct.SendInvoice.html
+/// [`CreateInvoiceLink`]: ../../../methods/struct.CreateInvoiceLink.html
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
+#[must_use]
+pub struct Invoice<'a> {
+    title: Cow<'a, str>,
+    description: Cow<'a, str>,
+    payload: Cow<'a, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    provider_token: Option<Cow<'a, str>>,
+    currency: Cow<'a, str>,
+    prices: Cow<'a, [LabeledPrice<'a>]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tip_amount: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggested_tip_amounts: Option<Cow<'a, [u32]>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) provider_data: Option<Cow<'a, str>>,
+    #[serde(skip_serializing_if = "Option::is_none", flatten)]
+    pub(crate) photo: Option<Photo<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) need_name: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) need_phone_number: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) need_email: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) need_shipping_address: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) send_phone_number_to_provider: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) send_email_to_provider: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) is_flexible: Option<bool>,
+}
+
+impl<'a> Invoice<'a> {
+    /// Constructs a new `Invoice`. `payload` is not shown to the user and
+    /// comes back in shipping and pre-checkout queries, and `prices` are
+    /// in the currency's minor units.
+    pub fn new(
+        title: impl Into<Cow<'a, str>>,
+        description: impl Into<Cow<'a, str>>,
+        payload: impl Into<Cow<'a, str>>,
+        currency: impl Into<Cow<'a, str>>,
+        prices: impl Into<Cow<'a, [LabeledPrice<'a>]>>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            description: description.into(),
+            payload: payload.into(),
+            provider_token: None,
+            currency: currency.into(),
+            prices: prices.into(),
+            max_tip_amount: None,
+            suggested_tip_amounts: None,
+            provider_data: None,
+            photo: None,
+            need_name: None,
+            need_phone_number: None,
+            need_email: None,
+            need_shipping_address: None,
+            send_phone_number_to_provider: None,
+            send_email_to_provider: None,
+            is_flexible: None,
+        }
+    }
+
+    /// Configures the token of your payment provider, which you get from
+    /// `BotFather`. Reflects the `provider_token` parameter.
+    pub fn provider_token(mut self, token: impl Into<Cow<'a, str>>) -> Self {
+        self.provider_token = Some(token.into());
+        self
+    }
+
+    /// Configures the maximum tip amount in the currency's minor units.
+    /// Reflects the `max_tip_amount` parameter.
+    pub const fn max_tip_amount(mut self, amount: u32) -> Self {
+        self.max_tip_amount = Some(amount);
+        self
+    }
+
+    /// Configures up to four suggested tip amounts in the currency's minor
+    /// units. Reflects the `suggested_tip_amounts` parameter.
+    pub fn suggested_tip_amounts(
+        mut self,
+        amounts: impl Into<Cow<'a, [u32]>>,
+    ) -> Self {
+        self.suggested_tip_amounts = Some(amounts.into());
+        self
+    }
+
+    /// Configures data for your payment provider, serializing it to JSON.
+    /// Reflects the `provider_data` parameter.
+    pub fn provider_data<T>(mut self, data: &T) -> serde_json::Result<Self>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.provider_data = Some(serde_json::to_string(data)?.into());
+        Ok(self)
+    }
+
+    /// Configures data for your payment provider which is already serialized
+    /// to JSON. Reflects the `provider_data` parameter.
+    pub fn raw_provider_data(mut self, data: impl Into<Cow<'a, str>>) -> Self {
+        self.provider_data = Some(data.into());
+        self
+    }
+
+    /// Configures a photo for the invoice. Reflects the `photo_url`,
+    /// `photo_size`, `photo_width` and `photo_height` parameters.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn photo(mut self, photo: Photo<'a>) -> Self {
+        self.photo = Some(photo);
+        self
+    }
+
+    /// Configures whether the user must specify their name.
+    /// Reflects the `need_name` parameter.
+    pub const fn is_name_needed(mut self, is_needed: bool) -> Self {
+        self.need_name = Some(is_needed);
+        self
+    }
+
+    /// Configures whether the user must specify their phone number.
+    /// Reflects the `need_phone_number` parameter.
+    pub const fn is_phone_number_needed(mut self, is_needed: bool) -> Self {
+        self.need_phone_number = Some(is_needed);
+        self
+    }
+
+    /// Configures whether the user must specify their email.
+    /// Reflects the `need_email` parameter.
+    pub const fn is_email_needed(mut self, is_needed: bool) -> Self {
+        self.need_email = Some(is_needed);
+        self
+    }
+
+    /// Configures whether the user must specify their shipping address.
+    /// Reflects the `need_shipping_address` parameter.
+    pub const fn is_shipping_address_needed(mut self, is_needed: bool) -> Self {
+        self.need_shipping_address = Some(is_needed);
+        self
+    }
+
+    /// Configures whether the user's phone must be sent to your payment
+    /// provider. Reflects the `send_phone_number_to_provider` parameter.
+    pub const fn should_send_phone_number_to_provider(
+        mut self,
+        must_send: bool,
+    ) -> Self {
+        self.send_phone_number_to_provider = Some(must_send);
+        self
+    }
+
+    /// Configures whether the user's email must be sent to your payment
+    /// provider. Reflects the `send_email_to_provider` parameter.
+    pub const fn should_send_email_to_provider(
+        mut self,
+        must_send: bool,
+    ) -> Self {
+        self.send_email_to_provider = Some(must_send);
+        self
+    }
+
+    /// Configures whether the final price depends on the shipping method.
+    /// Reflects the `is_flexible` parameter.
+    pub const fn is_flexible(mut self, is_flexible: bool) -> Self {
+        self.is_flexible = Some(is_flexible);
+        self
+    }
+
+    /// Returns the total price of the invoice in the currency's minor units.
+    pub fn total(&self) -> Result<u32, errors::Invoice> {
+        self.prices.iter().try_fold(0_u32, |total, price| {
+            total
+                .checked_add(price.amount())
+                .ok_or(errors::Invoice::TotalOverflow)
+        })
+    }
+
+    /// Checks the invoice against Telegram's rules: the currency must be
+    /// supported, there must be at least one price, and suggested tips must
+    /// be at most four positive, strictly increasing amounts not exceeding
+    /// the maximum tip amount.
+    ///
+    /// `tbot` doesn't call this method when sending the invoice, so call it
+    /// yourself if the invoice is built from user input.
+    pub fn validate(&self) -> Result<(), errors::Invoice> {
+        if exponent(&self.currency).is_none() {
+            return Err(errors::Invoice::UnknownCurrency(
+                self.currency.clone().into_owned(),
+            ));
+        }
+
+        if self.prices.is_empty() {
+            return Err(errors::Invoice::NoPrices);
+        }
+        self.total()?;
+
+        let tips = match &self.suggested_tip_amounts {
+            Some(tips) if !tips.is_empty() => tips,
+            _ => return Ok(()),
+        };
+
+        if tips.len() > MAX_SUGGESTED_TIPS {
+            return Err(errors::Invoice::TooManySuggestedTips);
+        }
+
+        let max = self
+            .max_tip_amount
+            .ok_or(errors::Invoice::MissingMaxTipAmount)?;
+
+        if tips[0] == 0 || tips.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(errors::Invoice::UnorderedSuggestedTips);
+        }
+
+        match tips.iter().find(|&&amount| amount > max) {
+            Some(&amount) => {
+                Err(errors::Invoice::SuggestedTipTooLarge { amount })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_tips() {
+        let prices: &[_] = &[LabeledPrice::new("Crab", 1_00)];
+        let invoice = Invoice::new("Crab", "A crab", "crab", "USD", prices);
+
+        assert_eq!(invoice.clone().validate(), Ok(()));
+        assert_eq!(
+            invoice.clone().suggested_tip_amounts(vec![50]).validate(),
+            Err(errors::Invoice::MissingMaxTipAmount),
+        );
+        assert_eq!(
+            invoice
+                .clone()
+                .max_tip_amount(100)
+                .suggested_tip_amounts(vec![50, 50])
+                .validate(),
+            Err(errors::Invoice::UnorderedSuggestedTips),
+        );
+        assert_eq!(
+            invoice
+                .max_tip_amount(100)
+                .suggested_tip_amounts(vec![50, 150])
+                .validate(),
+            Err(errors::Invoice::SuggestedTipTooLarge { amount: 150 }),
+        );
+
+        let yen = Invoice::new("Crab", "A crab", "crab", "JPY", Vec::new());
+        assert_eq!(yen.validate(), Err(errors::Invoice::NoPrices));
+        assert!(CURRENCIES.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+}
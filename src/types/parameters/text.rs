@@ -1,3 +1,4 @@
+use crate::{errors, markup::parse, types::message};
 use is_macro::Is;
 use serde::Serialize;
 use std::borrow::Cow;
@@ -87,6 +88,22 @@ impl<'a> Text<'a> {
     pub fn is_html(&self) -> bool {
         self.parse_mode == Some(ParseMode::Html)
     }
+
+    /// Parses the markup into plain text and entities like Telegram would.
+    /// See [`markup::parse`] for details.
+    ///
+    /// [`markup::parse`]: ../../markup/parse/index.html
+    pub fn parse(&self) -> Result<message::Text, errors::ParseEntities> {
+        match self.parse_mode {
+            None => Ok(message::Text {
+                value: self.text.to_string(),
+                entities: Vec::new(),
+            }),
+            Some(ParseMode::Markdown) => parse::markdown(&self.text),
+            Some(ParseMode::MarkdownV2) => parse::markdown_v2(&self.text),
+            Some(ParseMode::Html) => parse::html(&self.text),
+        }
+    }
 }
 
 impl<'a> From<&'a str> for Text<'a> {
@@ -34,4 +34,9 @@ pub enum UpdateKind {
     Poll,
     /// Handles poll answer updates.
     PollAnswer,
+    /// Handles changes of the bot's status in chats.
+    MyChatMember,
+    /// Handles changes of members' statuses in chats. Telegram doesn't send
+    /// these updates unless they're requested explicitly.
+    ChatMember,
 }
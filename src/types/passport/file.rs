@@ -8,11 +8,15 @@ use serde::Deserialize;
 #[non_exhaustive]
 pub struct File {
     /// The ID of the file.
+    #[serde(rename = "file_id")]
     pub id: file::Id<'static>,
     /// The unique ID of the file.
+    #[serde(rename = "file_unique_id")]
     pub unique_id: String,
     /// The size of the file.
+    #[serde(rename = "file_size")]
     pub size: usize,
     /// The date of the file.
+    #[serde(rename = "file_date")]
     pub date: i64,
 }
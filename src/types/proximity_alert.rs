@@ -0,0 +1,16 @@
+use crate::types::User;
+use serde::Deserialize;
+
+/// Represents a [`ProximityAlertTriggered`].
+///
+/// [`ProximityAlertTriggered`]: https://core.telegram.org/bots/api#proximityalerttriggered
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct ProximityAlert {
+    /// The user who triggered the alert.
+    pub traveler: User,
+    /// The user who set the alert.
+    pub watcher: User,
+    /// The distance between the users, in meters.
+    pub distance: u32,
+}
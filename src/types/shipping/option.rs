@@ -26,6 +26,27 @@ impl<'a> Option<'a> {
             prices: prices.into(),
         }
     }
+
+    /// Returns the ID of the option.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the prices of the option.
+    pub fn prices(&self) -> &[LabeledPrice<'a>] {
+        &self.prices
+    }
+
+    /// Borrows the option for a shorter lifetime, which `borrow_inside`
+    /// can't do because `Option` is invariant over its lifetime.
+    pub(crate) fn reborrow(&self) -> Option<'_> {
+        Option {
+            id: Cow::Borrowed(&self.id),
+            title: Cow::Borrowed(&self.title),
+            prices: Cow::Borrowed(&self.prices),
+        }
+    }
 }
 
 impl<'a> InteriorBorrow<'a> for Option<'a> {
@@ -16,10 +16,12 @@ pub use {mask_position::MaskPosition, set::Set};
 #[derive(Debug, PartialEq, Clone, Copy, Is)]
 #[non_exhaustive]
 pub enum Kind {
-    /// The sticker is neither animated nor a mask.
+    /// The sticker is neither animated, a video nor a mask.
     Plain,
     /// The sticker is animated.
     Animated,
+    /// The sticker is a video.
+    Video,
     /// The sticker is a mask.
     Mask(MaskPosition),
 }
@@ -48,6 +50,11 @@ pub struct Sticker {
     pub file_size: Option<u32>,
     /// The kind of the sticker.
     pub kind: Kind,
+    /// `true` if the sticker is a video. Unlike [`Kind::Video`], it's also
+    /// set for video masks.
+    ///
+    /// [`Kind::Video`]: ./enum.Kind.html#variant.Video
+    pub is_video: bool,
 }
 
 const FILE_ID: &str = "file_id";
@@ -55,6 +62,7 @@ const FILE_UNIQUE_ID: &str = "file_unique_id";
 const WIDTH: &str = "width";
 const HEIGHT: &str = "height";
 const IS_ANIMATED: &str = "is_animated";
+const IS_VIDEO: &str = "is_video";
 const THUMB: &str = "thumb";
 const EMOJI: &str = "emoji";
 const SET_NAME: &str = "set_name";
@@ -79,6 +87,7 @@ impl<'v> Visitor<'v> for StickerVisitor {
         let mut width = None;
         let mut height = None;
         let mut is_animated = None;
+        let mut is_video = None;
         let mut thumb = None;
         let mut emoji = None;
         let mut set_name = None;
@@ -92,6 +101,7 @@ impl<'v> Visitor<'v> for StickerVisitor {
                 WIDTH => width = Some(map.next_value()?),
                 HEIGHT => height = Some(map.next_value()?),
                 IS_ANIMATED => is_animated = Some(map.next_value()?),
+                IS_VIDEO => is_video = Some(map.next_value()?),
                 THUMB => thumb = Some(map.next_value()?),
                 EMOJI => emoji = Some(map.next_value()?),
                 SET_NAME => set_name = Some(map.next_value()?),
@@ -107,6 +117,8 @@ impl<'v> Visitor<'v> for StickerVisitor {
             Kind::Mask(mask_position)
         } else if is_animated == Some(true) {
             Kind::Animated
+        } else if is_video == Some(true) {
+            Kind::Video
         } else {
             Kind::Plain
         };
@@ -126,6 +138,7 @@ impl<'v> Visitor<'v> for StickerVisitor {
             set_name,
             file_size,
             kind,
+            is_video: is_video == Some(true),
         })
     }
 }
@@ -143,6 +156,7 @@ impl<'de> Deserialize<'de> for Sticker {
                 WIDTH,
                 HEIGHT,
                 IS_ANIMATED,
+                IS_VIDEO,
                 THUMB,
                 EMOJI,
                 SET_NAME,
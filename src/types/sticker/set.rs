@@ -12,10 +12,13 @@ use std::fmt::{self, Formatter};
 #[derive(Debug, PartialEq, Clone, Copy, Is)]
 #[non_exhaustive]
 pub enum Kind {
-    /// The stickers in the sticker set are neither animated nor masks.
+    /// The stickers in the sticker set are neither animated, videos
+    /// nor masks.
     Plain,
     /// The sticker set contains animated stickers.
     Animated,
+    /// The sticker set contains video stickers.
+    Video,
     /// The sticker set contains masks.
     Masks,
 }
@@ -32,6 +35,11 @@ pub struct Set {
     pub title: String,
     /// The kind of the sticker set.
     pub kind: Kind,
+    /// `true` if the sticker set contains video stickers. Unlike
+    /// [`Kind::Video`], it's also set for sets of video masks.
+    ///
+    /// [`Kind::Video`]: ./enum.Kind.html#variant.Video
+    pub is_video: bool,
     /// The stickers from this set.
     pub stickers: Vec<Sticker>,
     /// The thumb of the sticker set.
@@ -41,6 +49,7 @@ pub struct Set {
 const NAME: &str = "name";
 const TITLE: &str = "title";
 const IS_ANIMATED: &str = "is_animated";
+const IS_VIDEO: &str = "is_video";
 const CONTAINS_MASKS: &str = "contains_masks";
 const STICKERS: &str = "stickers";
 const THUMB: &str = "thumb";
@@ -61,6 +70,7 @@ impl<'v> Visitor<'v> for SetVisitor {
         let mut name = None;
         let mut title = None;
         let mut is_animated = None;
+        let mut is_video = None;
         let mut contains_masks = None;
         let mut stickers = None;
         let mut thumb = None;
@@ -70,6 +80,7 @@ impl<'v> Visitor<'v> for SetVisitor {
                 NAME => name = Some(map.next_value()?),
                 TITLE => title = Some(map.next_value()?),
                 IS_ANIMATED => is_animated = Some(map.next_value()?),
+                IS_VIDEO => is_video = Some(map.next_value()?),
                 CONTAINS_MASKS => contains_masks = Some(map.next_value()?),
                 STICKERS => stickers = Some(map.next_value()?),
                 THUMB => thumb = Some(map.next_value()?),
@@ -83,6 +94,8 @@ impl<'v> Visitor<'v> for SetVisitor {
             Kind::Masks
         } else if is_animated == Some(true) {
             Kind::Animated
+        } else if is_video == Some(true) {
+            Kind::Video
         } else {
             Kind::Plain
         };
@@ -95,6 +108,7 @@ impl<'v> Visitor<'v> for SetVisitor {
                 .ok_or_else(|| serde::de::Error::missing_field(STICKERS))?,
             thumb,
             kind,
+            is_video: is_video == Some(true),
         })
     }
 }
@@ -106,7 +120,15 @@ impl<'de> Deserialize<'de> for Set {
     {
         deserializer.deserialize_struct(
             "sticker::Set",
-            &[NAME, TITLE, IS_ANIMATED, CONTAINS_MASKS, STICKERS, THUMB],
+            &[
+                NAME,
+                TITLE,
+                IS_ANIMATED,
+                IS_VIDEO,
+                CONTAINS_MASKS,
+                STICKERS,
+                THUMB,
+            ],
             SetVisitor,
         )
     }
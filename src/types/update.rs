@@ -1,8 +1,8 @@
 //! Types related to updates.
 
 use super::{
-    callback, poll::Answer, shipping, ChosenInlineResult, InlineQuery, Message,
-    Poll, PreCheckoutQuery,
+    callback, chat, poll::Answer, shipping, ChosenInlineResult, InlineQuery,
+    Message, Poll, PreCheckoutQuery,
 };
 use is_macro::Is;
 use serde::{
@@ -48,6 +48,11 @@ pub enum Kind {
     ShippingQuery(shipping::Query),
     /// A pre-checkout query.
     PreCheckoutQuery(PreCheckoutQuery),
+    /// The bot's own status in a chat changed.
+    MyChatMember(chat::MemberUpdated),
+    /// A member's status in a chat changed. Telegram only sends these
+    /// updates if they're listed in the allowed updates.
+    ChatMember(chat::MemberUpdated),
     /// Unknown update kind.
     Unknown,
 }
@@ -91,6 +96,8 @@ const SHIPPING_QUERY: &str = "shipping_query";
 const PRE_CHECKOUT_QUERY: &str = "pre_checkout_query";
 const POLL: &str = "poll";
 const POLL_ANSWER: &str = "poll_answer";
+const MY_CHAT_MEMBER: &str = "my_chat_member";
+const CHAT_MEMBER: &str = "chat_member";
 
 struct RawUpdateVisitor;
 
@@ -131,6 +138,8 @@ impl<'v> Visitor<'v> for RawUpdateVisitor {
                 }
                 POLL => map.next_value().map(Kind::Poll),
                 POLL_ANSWER => map.next_value().map(Kind::PollAnswer),
+                MY_CHAT_MEMBER => map.next_value().map(Kind::MyChatMember),
+                CHAT_MEMBER => map.next_value().map(Kind::ChatMember),
                 _ => {
                     let _: IgnoredAny = map.next_value()?;
                     Ok(Kind::Unknown)
@@ -164,6 +173,8 @@ impl<'de> Deserialize<'de> for RawUpdate {
                 PRE_CHECKOUT_QUERY,
                 POLL,
                 POLL_ANSWER,
+                MY_CHAT_MEMBER,
+                CHAT_MEMBER,
             ],
             RawUpdateVisitor,
         )
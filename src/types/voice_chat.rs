@@ -0,0 +1,35 @@
+//! Types related to voice chats.
+
+use crate::types::User;
+use serde::Deserialize;
+
+/// Represents a [`VoiceChatScheduled`].
+///
+/// [`VoiceChatScheduled`]: https://core.telegram.org/bots/api#voicechatscheduled
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[non_exhaustive]
+pub struct Scheduled {
+    /// The timestamp when the voice chat is supposed to be started.
+    pub start_date: i64,
+}
+
+/// Represents a [`VoiceChatEnded`].
+///
+/// [`VoiceChatEnded`]: https://core.telegram.org/bots/api#voicechatended
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize)]
+#[non_exhaustive]
+pub struct Ended {
+    /// The duration of the voice chat, in seconds.
+    pub duration: u32,
+}
+
+/// Represents a [`VoiceChatParticipantsInvited`].
+///
+/// [`VoiceChatParticipantsInvited`]: https://core.telegram.org/bots/api#voicechatparticipantsinvited
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+#[non_exhaustive]
+pub struct ParticipantsInvited {
+    /// The users who were invited to the voice chat.
+    #[serde(default)]
+    pub users: Vec<User>,
+}
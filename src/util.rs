@@ -1,7 +1,28 @@
 //! A few useful utilities.
+//!
+//! Utilities which keep state, such as [`callback_data::Codec`], are cheap to
+//! clone, and their clones share that state, so you can move a clone into
+//! each handler.
+//!
+//! [`callback_data::Codec`]: ./callback_data/struct.Codec.html
 
+pub mod admins;
+pub mod broadcast;
+pub mod callback_data;
 mod chat_action_loop;
+pub mod chosen_inline;
+pub mod conversation;
+pub mod directory;
 pub mod entities;
+pub mod inline_results;
+pub mod live_location;
+pub mod long_message;
+pub mod moderation;
+pub mod pagination;
+pub mod passport;
+pub mod payments;
+pub mod sticker_set;
 
 pub use chat_action_loop::{ChatActionLoop, ChatActionLoopBotExt};
+pub use conversation::Conversation;
 pub use entities::entities;
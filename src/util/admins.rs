@@ -0,0 +1,280 @@
+//! A cache of chat administrators shared by a bot and its event loop.
+//!
+//! Checking if a user is an admin requires calling `getChatAdministrators`.
+//! Doing it for every message is slow and quickly runs into rate limits,
+//! so a bot can keep the lists of administrators in a [`Registry`]. It's
+//! opt-in and enabled with [`bot::Builder::admin_registry`]:
+//!
+//! ```no_run
+//! # async fn foo() {
+//! use std::time::Duration;
+//! use tbot::{bot, prelude::*};
+//!
+//! let bot = bot::Builder::with_env_token("BOT_TOKEN")
+//!     .admin_registry(Duration::from_secs(10 * 60))
+//!     .build();
+//! let mut bot = bot.event_loop();
+//!
+//! bot.command("settings", |context| async move {
+//!     if let Ok(true) = context.is_sender_admin().await {
+//!         // ..
+//!     }
+//! });
+//! # }
+//! ```
+//!
+//! A cached list expires after the configured time. It is also dropped
+//! earlier when `tbot` learns that it might be outdated:
+//!
+//! - when the event loop receives a service message about new or left
+//!   members of the chat;
+//! - when the event loop receives a `my_chat_member` or `chat_member` update
+//!   for the chat. Note that Telegram sends `chat_member` updates only if
+//!   they're listed in the allowed updates;
+//! - after a successful `promoteChatMember`, `restrictChatMember`,
+//!   `kickChatMember` or `setChatAdministratorCustomTitle` call made
+//!   through `tbot`.
+//!
+//! Changes made outside of the bot can't be observed, so choose the time
+//! to live accordingly, or call [`Registry::invalidate`] yourself.
+//!
+//! [`Registry`]: ./struct.Registry.html
+//! [`bot::Builder::admin_registry`]: ../../bot/struct.Builder.html#method.admin_registry
+//! [`Registry::invalidate`]: ./struct.Registry.html#method.invalidate
+
+use crate::{
+    errors,
+    types::{chat, message::From, parameters::ChatId, update, user, Chat},
+    Bot,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+struct Entry {
+    fetched_at: Instant,
+    administrators: Arc<[chat::Member]>,
+}
+
+/// Caches administrators of chats. See [the module's docs] to learn how
+/// to enable and use it.
+///
+/// [the module's docs]: ./index.html
+pub struct Registry {
+    ttl: Duration,
+    chats: Mutex<HashMap<chat::Id, Entry>>,
+}
+
+impl Registry {
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            chats: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<chat::Id, Entry>> {
+        self.chats.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Returns for how long administrators are cached.
+    #[must_use]
+    pub const fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Returns the cached administrators of the chat if they haven't
+    /// expired yet.
+    #[must_use]
+    pub fn get(&self, chat_id: chat::Id) -> Option<Arc<[chat::Member]>> {
+        self.lock()
+            .get(&chat_id)
+            .filter(|entry| entry.fetched_at.elapsed() < self.ttl)
+            .map(|entry| Arc::clone(&entry.administrators))
+    }
+
+    pub(crate) fn insert(
+        &self,
+        chat_id: chat::Id,
+        administrators: Arc<[chat::Member]>,
+    ) {
+        let entry = Entry {
+            fetched_at: Instant::now(),
+            administrators,
+        };
+        let mut chats = self.lock();
+        chats.retain(|_, entry| entry.fetched_at.elapsed() < self.ttl);
+        chats.insert(chat_id, entry);
+    }
+
+    /// Drops the cached administrators of the chat.
+    pub fn invalidate(&self, chat_id: chat::Id) {
+        self.lock().remove(&chat_id);
+    }
+
+    /// Drops the cached administrators of all chats.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Drops the cached administrators of the chat a method was called on.
+    /// As usernames can't be mapped to IDs, they drop the whole cache.
+    pub(crate) fn invalidate_chat_id(&self, chat_id: &ChatId<'_>) {
+        match chat_id {
+            ChatId::Id(id) => self.invalidate(*id),
+            ChatId::Username(..) => self.clear(),
+        }
+    }
+
+    /// Drops the cached administrators if the update means they might have
+    /// changed.
+    pub(crate) fn observe(&self, update: &update::Kind) {
+        match update {
+            update::Kind::Message(message)
+            | update::Kind::ChannelPost(message)
+                if message.kind.is_new_chat_members()
+                    || message.kind.is_left_chat_member() =>
+            {
+                self.invalidate(message.chat.id);
+            }
+            update::Kind::MyChatMember(updated)
+            | update::Kind::ChatMember(updated) => {
+                self.invalidate(updated.chat.id);
+            }
+            _ => (),
+        }
+    }
+}
+
+impl Debug for Registry {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Registry")
+            .field("ttl", &self.ttl)
+            .field("chats", &self.lock().len())
+            .finish()
+    }
+}
+
+/// Gets the administrators of the chat, using the bot's registry if it's
+/// enabled.
+pub(crate) async fn administrators(
+    bot: &Bot,
+    chat_id: chat::Id,
+) -> Result<Arc<[chat::Member]>, errors::MethodCall> {
+    let registry = bot.admin_registry();
+    if let Some(administrators) =
+        registry.and_then(|registry| registry.get(chat_id))
+    {
+        return Ok(administrators);
+    }
+
+    let administrators: Arc<[chat::Member]> =
+        bot.get_chat_administrators(chat_id).call().await?.into();
+    if let Some(registry) = registry {
+        registry.insert(chat_id, Arc::clone(&administrators));
+    }
+
+    Ok(administrators)
+}
+
+/// Checks if the author of a message in the chat is its admin. Messages
+/// sent on behalf of the chat itself, e.g. by anonymous admins, are
+/// considered to be sent by an admin.
+pub(crate) async fn is_sender_admin(
+    bot: &Bot,
+    chat: &Chat,
+    from: Option<&From>,
+) -> Result<bool, errors::MethodCall> {
+    let user_id: user::Id = match from {
+        Some(From::User(user)) => user.id,
+        Some(From::Chat(sender)) => return Ok(sender.id == chat.id),
+        None => return Ok(false),
+    };
+
+    if chat.kind.is_private() {
+        return Ok(false);
+    }
+
+    let administrators = administrators(bot, chat.id).await?;
+    Ok(administrators
+        .iter()
+        .any(|administrator| administrator.user.id == user_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Update;
+
+    const USER: &str = r#"{"id":1,"is_bot":false,"first_name":"A"}"#;
+
+    fn update(kind: &str, value: &str) -> update::Kind {
+        let update = format!(r#"{{"update_id":1,"{}":{}}}"#, kind, value);
+        serde_json::from_str::<Update>(&update).unwrap().kind
+    }
+
+    fn chat(id: i64) -> String {
+        format!(r#"{{"id":{},"type":"group","title":"Rust"}}"#, id)
+    }
+
+    #[test]
+    fn registry_expires_and_invalidates() {
+        let registry = Registry::new(Duration::from_secs(60));
+        registry.insert(chat::Id(1), Arc::from(Vec::new()));
+        registry.insert(chat::Id(2), Arc::from(Vec::new()));
+        registry.insert(chat::Id(3), Arc::from(Vec::new()));
+        assert!(registry.get(chat::Id(1)).is_some());
+
+        let left = format!(
+            r#"{{"message_id":1,"date":0,"chat":{},"left_chat_member":{}}}"#,
+            chat(1),
+            USER,
+        );
+        registry.observe(&update("message", &left));
+        assert!(registry.get(chat::Id(1)).is_none());
+        assert!(registry.get(chat::Id(2)).is_some());
+
+        let member = format!(r#"{{"user":{},"status":"member"}}"#, USER);
+        let promoted = format!(
+            r#"{{
+                "user": {},
+                "status": "administrator",
+                "can_be_edited": true,
+                "can_change_info": false,
+                "can_delete_messages": false,
+                "can_invite_users": false,
+                "can_restrict_members": false,
+                "can_promote_members": false,
+                "is_anonymous": false
+            }}"#,
+            USER,
+        );
+        let updated = format!(
+            r#"{{
+                "chat": {},
+                "from": {},
+                "date": 0,
+                "old_chat_member": {},
+                "new_chat_member": {}
+            }}"#,
+            chat(2),
+            USER,
+            member,
+            promoted,
+        );
+        registry.observe(&update("chat_member", &updated));
+        assert!(registry.get(chat::Id(2)).is_none());
+        assert!(registry.get(chat::Id(3)).is_some());
+
+        registry.invalidate_chat_id(&ChatId::Username("@group".into()));
+        assert!(registry.get(chat::Id(3)).is_none());
+
+        let registry = Registry::new(Duration::from_secs(0));
+        registry.insert(chat::Id(1), Arc::from(Vec::new()));
+        assert!(registry.get(chat::Id(1)).is_none());
+    }
+}
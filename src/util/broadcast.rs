@@ -0,0 +1,756 @@
+//! Sending a message to many chats.
+//!
+//! Announcing something to all users of a bot means sending the same
+//! message to thousands of chats. [`Broadcast`] does it while respecting
+//! Telegram's limits, reporting chats the message couldn't be delivered
+//! to, and saving its progress so that it can be resumed after a crash:
+//!
+//! ```no_run
+//! # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+//! use futures::stream;
+//! use tbot::{
+//!     state::storage::File,
+//!     util::broadcast::{Broadcast, Template},
+//! };
+//!
+//! let bot = tbot::from_env!("BOT_TOKEN");
+//! let storage = File::open("broadcasts.json").await?;
+//! let subscribers: Vec<i64> = vec![/* .. */];
+//!
+//! let broadcast = Broadcast::new(
+//!     &bot,
+//!     stream::iter(subscribers),
+//!     Template::text("A new version is out!"),
+//! )
+//! .checkpoint(&storage, "release-announcement");
+//!
+//! // Call `cancel` from elsewhere, e.g. from a `/cancel` command handler
+//! let cancel_handle = broadcast.cancel_handle();
+//!
+//! let report = broadcast.call().await?;
+//! for chat_id in report.unreachable() {
+//!     // remove the chat from the subscribers
+//! }
+//! # Ok(()) }
+//! ```
+//!
+//! # Checkpoints
+//!
+//! If a [`Storage`] is configured, the broadcast periodically saves how many
+//! chats it has processed. When a broadcast with the same key is started
+//! again, it skips that many chats, so the chats must come in the same
+//! order. Failures and migrations are saved too, so the final report covers
+//! all runs.
+//! A broadcast which was cancelled or interrupted by an error keeps its
+//! checkpoint, while a finished one removes it.
+//!
+//! Messages sent after the last checkpoint before a crash are sent again
+//! when the broadcast is resumed, so make checkpoints more frequent with
+//! [`checkpoint_interval`] if duplicates are undesirable.
+//!
+//! # Errors
+//!
+//! Only errors which are specific to a recipient, such as a blocked bot
+//! or a deleted account, are reported as [`Failure`]s. If a group was
+//! upgraded to a supergroup, the message is sent to the supergroup, and
+//! the new ID is reported in [`Report::migrated`]. Any other error, e.g.
+//! a network error or a message Telegram refuses to send, stops
+//! the broadcast, as it would most likely fail for every chat.
+//!
+//! [`Broadcast`]: ./struct.Broadcast.html
+//! [`Failure`]: ./enum.Failure.html
+//! [`Report::migrated`]: ./struct.Report.html#structfield.migrated
+//! [`Storage`]: ../../state/storage/trait.Storage.html
+//! [`checkpoint_interval`]: ./struct.Broadcast.html#method.checkpoint_interval
+
+use crate::{
+    errors,
+    state::storage::Storage,
+    types::{
+        chat, message,
+        parameters::{ChatId, ImplicitChatId, Text},
+    },
+    Bot,
+};
+use futures::{Stream, StreamExt};
+use is_macro::Is;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Formatter},
+    num::{NonZeroU32, NonZeroU64},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::time::{sleep, sleep_until, Instant};
+
+const DEFAULT_RATE: u32 = 25;
+const DEFAULT_CHECKPOINT_INTERVAL: u64 = 100;
+const FAILURES: &str = "/failed/";
+const MIGRATIONS: &str = "/migrated/";
+
+/// Represents the message which is broadcast.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum Template<'a> {
+    /// A text message.
+    Text(Text<'a>),
+    /// A copy of an existing message, sent with `copyMessage`.
+    Copy {
+        /// The chat where the message is.
+        from_chat_id: ChatId<'a>,
+        /// The ID of the message.
+        message_id: message::Id,
+    },
+}
+
+impl<'a> Template<'a> {
+    /// Constructs a text template.
+    pub fn text(text: impl Into<Text<'a>>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Constructs a template copying an existing message.
+    pub fn copy(
+        from_chat_id: impl ImplicitChatId<'a>,
+        message_id: message::Id,
+    ) -> Self {
+        Self::Copy {
+            from_chat_id: from_chat_id.into(),
+            message_id,
+        }
+    }
+}
+
+/// Represents the reason a message couldn't be delivered to a chat.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize, Is)]
+#[non_exhaustive]
+pub enum Failure {
+    /// The user blocked the bot.
+    Blocked,
+    /// The user deleted their account, or the group was deactivated.
+    Deactivated,
+    /// The chat doesn't exist, or the bot has never talked to the user.
+    ChatNotFound,
+    /// The bot was removed from the group or the channel.
+    Kicked,
+    /// The bot can't start a conversation with the user, because the user
+    /// has never started the bot or is a bot too.
+    CantInitiate,
+    /// The bot has no rights to send messages to the chat.
+    Forbidden,
+}
+
+impl Failure {
+    /// Classifies an error if it's specific to the recipient.
+    fn classify(error: &errors::MethodCall) -> Option<Self> {
+        let (description, error_code) = match error {
+            errors::MethodCall::RequestError {
+                description,
+                error_code,
+                ..
+            } => (description.to_lowercase(), *error_code),
+            _ => return None,
+        };
+
+        if description.contains("blocked by the user") {
+            Some(Self::Blocked)
+        } else if description.contains("user is deactivated")
+            || description.contains("chat was deactivated")
+        {
+            Some(Self::Deactivated)
+        } else if description.contains("chat not found") {
+            Some(Self::ChatNotFound)
+        } else if description.contains("kicked")
+            || description.contains("not a member")
+        {
+            Some(Self::Kicked)
+        } else if description.contains("can't initiate conversation")
+            || description.contains("can't send messages to bots")
+        {
+            Some(Self::CantInitiate)
+        } else if error_code == 403
+            || description.contains("have no rights to send")
+            || description.contains("chat_write_forbidden")
+        {
+            Some(Self::Forbidden)
+        } else {
+            None
+        }
+    }
+
+    /// Checks if the bot can't send messages to the chat anymore, so it may
+    /// be removed from the list of recipients.
+    #[must_use]
+    pub const fn is_unreachable(&self) -> bool {
+        matches!(
+            self,
+            Self::Blocked
+                | Self::Deactivated
+                | Self::ChatNotFound
+                | Self::Kicked
+                | Self::CantInitiate
+        )
+    }
+}
+
+/// Describes how the message was delivered to a chat.
+struct Delivery {
+    /// The ID of the supergroup the group was upgraded to.
+    migrated_to: Option<chat::Id>,
+    failure: Option<Failure>,
+}
+
+/// Describes what a broadcast has done.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Report {
+    /// How many messages were delivered.
+    pub sent: u64,
+    /// The chats the message couldn't be delivered to, with the reasons.
+    pub failed: Vec<(ChatId<'static>, Failure)>,
+    /// The groups which were upgraded to supergroups, with the IDs of
+    /// the supergroups. The message was sent to the supergroups instead.
+    pub migrated: Vec<(ChatId<'static>, chat::Id)>,
+    /// `true` if the broadcast was cancelled before all chats
+    /// were processed.
+    pub is_cancelled: bool,
+}
+
+impl Report {
+    /// Returns the chats the bot can't send messages to anymore.
+    pub fn unreachable(&self) -> impl Iterator<Item = &ChatId<'static>> {
+        self.failed
+            .iter()
+            .filter(|(_, failure)| failure.is_unreachable())
+            .map(|(chat_id, _)| chat_id)
+    }
+}
+
+/// Represents a step of a broadcast, reported as soon as it's done.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum Progress {
+    /// The message was delivered to the chat.
+    Sent {
+        /// The ID of the chat.
+        chat_id: ChatId<'static>,
+    },
+    /// The message couldn't be delivered to the chat.
+    Failed {
+        /// The ID of the chat.
+        chat_id: ChatId<'static>,
+        /// The reason of the failure.
+        failure: Failure,
+    },
+    /// The group was upgraded to a supergroup, and the message is sent
+    /// there instead.
+    Migrated {
+        /// The ID of the group.
+        chat_id: ChatId<'static>,
+        /// The ID of the supergroup.
+        migrated_to: chat::Id,
+    },
+    /// Telegram asked to wait before sending more messages.
+    FloodWait(Duration),
+    /// A checkpoint was saved.
+    Saved {
+        /// How many chats have been processed.
+        offset: u64,
+    },
+}
+
+/// Cancels a broadcast. The broadcast stops before sending the next
+/// message and returns a report.
+#[derive(Debug, Clone, Default)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Cancels the broadcast.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Checks if the broadcast was cancelled.
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    offset: u64,
+    sent: u64,
+}
+
+type OnProgress<'a> = dyn Fn(Progress) + Send + Sync + 'a;
+type Entries<T> = Vec<(ChatId<'static>, T)>;
+
+struct Settings<'a> {
+    bot: &'a Bot,
+    template: Template<'a>,
+    rate: u32,
+    is_notification_disabled: bool,
+    storage: Option<(&'a dyn Storage, Cow<'a, str>)>,
+    checkpoint_interval: u64,
+    cancel_handle: CancelHandle,
+    on_progress: Option<Box<OnProgress<'a>>>,
+}
+
+/// Sends a message to each chat from a stream. See [the module's docs]
+/// to learn how to use it.
+///
+/// [the module's docs]: ./index.html
+#[must_use = "the broadcast does nothing unless turned into a future"]
+pub struct Broadcast<'a, S> {
+    chats: S,
+    settings: Settings<'a>,
+}
+
+impl<'a, S> Broadcast<'a, S>
+where
+    S: Stream + Unpin,
+    S::Item: Into<ChatId<'static>>,
+{
+    /// Constructs a new `Broadcast`.
+    pub fn new(bot: &'a Bot, chats: S, template: Template<'a>) -> Self {
+        Self {
+            chats,
+            settings: Settings {
+                bot,
+                template,
+                rate: DEFAULT_RATE,
+                is_notification_disabled: false,
+                storage: None,
+                checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+                cancel_handle: CancelHandle::default(),
+                on_progress: None,
+            },
+        }
+    }
+
+    /// Runs the broadcast.
+    pub async fn call(self) -> Result<Report, errors::Broadcast> {
+        let Self {
+            mut chats,
+            settings,
+        } = self;
+        let (saved, failed, migrated) = settings.load().await?;
+        let mut checkpoint = Checkpoint {
+            offset: 0,
+            sent: saved.sent,
+        };
+        let mut report = Report {
+            sent: saved.sent,
+            failed,
+            migrated,
+            is_cancelled: false,
+        };
+        let mut next_send = Instant::now();
+        let mut unsaved = 0;
+
+        while let Some(chat_id) = chats.next().await {
+            if checkpoint.offset < saved.offset {
+                checkpoint.offset += 1;
+                continue;
+            }
+
+            if settings.cancel_handle.is_cancelled() {
+                report.is_cancelled = true;
+                break;
+            }
+
+            let chat_id = chat_id.into();
+            let delivery =
+                match settings.deliver(&chat_id, &mut next_send).await {
+                    Ok(delivery) => delivery,
+                    Err(error) => {
+                        settings.save(&checkpoint).await?;
+                        return Err(error.into());
+                    }
+                };
+
+            if let Some(migrated_to) = delivery.migrated_to {
+                let migrated = (chat_id.clone(), migrated_to);
+                settings
+                    .save_entry(MIGRATIONS, checkpoint.offset, &migrated)
+                    .await?;
+                settings.report(Progress::Migrated {
+                    chat_id: chat_id.clone(),
+                    migrated_to,
+                });
+                report.migrated.push(migrated);
+            }
+
+            match delivery.failure {
+                None => {
+                    report.sent += 1;
+                    settings.report(Progress::Sent { chat_id });
+                }
+                Some(failure) => {
+                    let failed = (chat_id, failure);
+                    settings
+                        .save_entry(FAILURES, checkpoint.offset, &failed)
+                        .await?;
+                    settings.report(Progress::Failed {
+                        chat_id: failed.0.clone(),
+                        failure: failed.1.clone(),
+                    });
+                    report.failed.push(failed);
+                }
+            }
+
+            checkpoint.offset += 1;
+            checkpoint.sent = report.sent;
+            unsaved += 1;
+            if unsaved >= settings.checkpoint_interval {
+                settings.save(&checkpoint).await?;
+                unsaved = 0;
+            }
+        }
+
+        if report.is_cancelled {
+            settings.save(&checkpoint).await?;
+        } else {
+            settings.clear().await?;
+        }
+
+        Ok(report)
+    }
+}
+
+impl<'a, S> Broadcast<'a, S> {
+    /// Configures how many messages are sent per second. Telegram allows
+    /// bots to send about 30 messages per second, and the default is 25.
+    pub const fn rate(mut self, messages_per_second: NonZeroU32) -> Self {
+        self.settings.rate = messages_per_second.get();
+        self
+    }
+
+    /// Configures if the messages are sent silently.
+    pub const fn is_notification_disabled(mut self, is_disabled: bool) -> Self {
+        self.settings.is_notification_disabled = is_disabled;
+        self
+    }
+
+    /// Configures the storage for checkpoints and the key under which they
+    /// are saved.
+    pub fn checkpoint(
+        mut self,
+        storage: &'a dyn Storage,
+        key: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.settings.storage = Some((storage, key.into()));
+        self
+    }
+
+    /// Configures after how many processed chats a checkpoint is saved.
+    /// By default, a checkpoint is saved after every 100 chats.
+    pub const fn checkpoint_interval(mut self, chats: NonZeroU64) -> Self {
+        self.settings.checkpoint_interval = chats.get();
+        self
+    }
+
+    /// Returns a handle which cancels the broadcast.
+    pub fn cancel_handle(&self) -> CancelHandle {
+        self.settings.cancel_handle.clone()
+    }
+
+    /// Configures a callback which is called after each step
+    /// of the broadcast.
+    pub fn on_progress(
+        mut self,
+        on_progress: impl Fn(Progress) + Send + Sync + 'a,
+    ) -> Self {
+        self.settings.on_progress = Some(Box::new(on_progress));
+        self
+    }
+}
+
+impl Settings<'_> {
+    fn report(&self, progress: Progress) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(progress);
+        }
+    }
+
+    async fn send(
+        &self,
+        chat_id: &ChatId<'_>,
+    ) -> Result<(), errors::MethodCall> {
+        match &self.template {
+            Template::Text(text) => self
+                .bot
+                .send_message(chat_id, text.clone())
+                .is_notification_disabled(self.is_notification_disabled)
+                .call()
+                .await
+                .map(drop),
+            Template::Copy {
+                from_chat_id,
+                message_id,
+            } => self
+                .bot
+                .copy_message(chat_id, from_chat_id, *message_id)
+                .is_notification_disabled(self.is_notification_disabled)
+                .call()
+                .await
+                .map(drop),
+        }
+    }
+
+    /// Sends the message, waiting for flood waits and following
+    /// the group's migration to a supergroup. Returns the failure if
+    /// the error is specific to the chat, and the error otherwise.
+    async fn deliver(
+        &self,
+        chat_id: &ChatId<'_>,
+        next_send: &mut Instant,
+    ) -> Result<Delivery, errors::MethodCall> {
+        let interval = Duration::from_secs(1) / self.rate;
+        let mut delivery = Delivery {
+            migrated_to: None,
+            failure: None,
+        };
+
+        loop {
+            sleep_until(*next_send).await;
+            *next_send = Instant::now() + interval;
+
+            let result = match delivery.migrated_to {
+                Some(migrated_to) => self.send(&migrated_to.into()).await,
+                None => self.send(chat_id).await,
+            };
+
+            match result {
+                Ok(()) => return Ok(delivery),
+                Err(errors::MethodCall::RequestError {
+                    retry_after: Some(seconds),
+                    ..
+                }) => {
+                    let delay = Duration::from_secs(seconds);
+                    self.report(Progress::FloodWait(delay));
+                    sleep(delay).await;
+                }
+                Err(errors::MethodCall::RequestError {
+                    migrate_to_chat_id: Some(migrated_to),
+                    ..
+                }) if delivery.migrated_to.is_none() => {
+                    delivery.migrated_to = Some(migrated_to);
+                }
+                Err(error) => {
+                    delivery.failure = Failure::classify(&error);
+                    return match delivery.failure {
+                        Some(..) => Ok(delivery),
+                        None => Err(error),
+                    };
+                }
+            }
+        }
+    }
+
+    async fn load(
+        &self,
+    ) -> Result<
+        (Checkpoint, Entries<Failure>, Entries<chat::Id>),
+        errors::Storage,
+    > {
+        let (storage, key) = match &self.storage {
+            Some(storage) => storage,
+            None => return Ok((Checkpoint::default(), Vec::new(), Vec::new())),
+        };
+
+        let checkpoint = match storage.get(key).await? {
+            Some(checkpoint) => serde_json::from_slice(&checkpoint)?,
+            None => Checkpoint::default(),
+        };
+        let failed = self.load_entries(FAILURES).await?;
+        let migrated = self.load_entries(MIGRATIONS).await?;
+
+        Ok((checkpoint, failed, migrated))
+    }
+
+    async fn load_entries<T>(
+        &self,
+        prefix: &str,
+    ) -> Result<Vec<T>, errors::Storage>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let (storage, key) = match &self.storage {
+            Some(storage) => storage,
+            None => return Ok(Vec::new()),
+        };
+
+        storage
+            .scan(&format!("{}{}", key, prefix))
+            .await?
+            .into_iter()
+            .map(|(_, entry)| Ok(serde_json::from_slice(&entry)?))
+            .collect()
+    }
+
+    async fn save(
+        &self,
+        checkpoint: &Checkpoint,
+    ) -> Result<(), errors::Storage> {
+        if let Some((storage, key)) = &self.storage {
+            storage.set(key, serde_json::to_vec(checkpoint)?).await?;
+            storage.flush().await?;
+            self.report(Progress::Saved {
+                offset: checkpoint.offset,
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn save_entry(
+        &self,
+        prefix: &str,
+        offset: u64,
+        entry: &(impl Serialize + Sync),
+    ) -> Result<(), errors::Storage> {
+        if let Some((storage, key)) = &self.storage {
+            let key = format!("{}{}{:020}", key, prefix, offset);
+            storage.set(&key, serde_json::to_vec(entry)?).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), errors::Storage> {
+        if let Some((storage, key)) = &self.storage {
+            for prefix in &[FAILURES, MIGRATIONS] {
+                let prefix = format!("{}{}", key, prefix);
+                for (entry, _) in storage.scan(&prefix).await? {
+                    storage.remove(&entry).await?;
+                }
+            }
+            storage.remove(key).await?;
+            storage.flush().await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S> Debug for Broadcast<'_, S> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let settings = &self.settings;
+        formatter
+            .debug_struct("Broadcast")
+            .field("template", &settings.template)
+            .field("rate", &settings.rate)
+            .field(
+                "is_notification_disabled",
+                &settings.is_notification_disabled,
+            )
+            .field("key", &settings.storage.as_ref().map(|(_, key)| key))
+            .field("checkpoint_interval", &settings.checkpoint_interval)
+            .field("cancel_handle", &settings.cancel_handle)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(
+        description: &str,
+        migrate_to_chat_id: Option<i64>,
+    ) -> errors::MethodCall {
+        let error_code = if description.starts_with("Forbidden") {
+            403
+        } else {
+            400
+        };
+
+        errors::MethodCall::RequestError {
+            description: description.to_owned(),
+            error_code,
+            migrate_to_chat_id: migrate_to_chat_id.map(chat::Id),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn classifies_failures() {
+        let classify = |description, migrate_to_chat_id| {
+            Failure::classify(&error(description, migrate_to_chat_id))
+        };
+
+        assert_eq!(
+            classify("Forbidden: bot was blocked by the user", None),
+            Some(Failure::Blocked),
+        );
+        assert_eq!(
+            classify("Forbidden: user is deactivated", None),
+            Some(Failure::Deactivated),
+        );
+        assert_eq!(
+            classify("Bad Request: chat not found", None),
+            Some(Failure::ChatNotFound),
+        );
+        assert_eq!(
+            classify("Forbidden: bot was kicked from the group chat", None),
+            Some(Failure::Kicked),
+        );
+        assert_eq!(
+            classify("Bad Request: group chat was deactivated", None),
+            Some(Failure::Deactivated),
+        );
+        assert_eq!(
+            classify(
+                "Forbidden: bot can't initiate conversation with a user",
+                None,
+            ),
+            Some(Failure::CantInitiate),
+        );
+        assert_eq!(
+            classify("Forbidden: bot can't send messages to bots", None),
+            Some(Failure::CantInitiate),
+        );
+        assert_eq!(
+            classify("Bad Request: have no rights to send a message", None),
+            Some(Failure::Forbidden),
+        );
+        assert_eq!(
+            classify("Bad Request: CHAT_WRITE_FORBIDDEN", None),
+            Some(Failure::Forbidden),
+        );
+        assert_eq!(
+            classify("Forbidden: bot is not a participant of the chat", None),
+            Some(Failure::Forbidden),
+        );
+        assert_eq!(classify("Bad Request: message is too long", None), None);
+        assert_eq!(
+            classify("Bad Request: can't parse entities: unclosed tag", None),
+            None,
+        );
+        assert_eq!(
+            classify("Bad Request: message to copy not found", None),
+            None,
+        );
+        assert_eq!(Failure::classify(&errors::MethodCall::OutOfService), None);
+
+        let unauthorized = errors::MethodCall::RequestError {
+            description: "Unauthorized".to_owned(),
+            error_code: 401,
+            migrate_to_chat_id: None,
+            retry_after: None,
+        };
+        assert_eq!(Failure::classify(&unauthorized), None);
+
+        let failed = (ChatId::Username("@channel".into()), Failure::Kicked);
+        let json = serde_json::to_vec(&failed).unwrap();
+        let restored: (ChatId<'static>, Failure) =
+            serde_json::from_slice(&json).unwrap();
+        assert_eq!(restored, failed);
+    }
+}
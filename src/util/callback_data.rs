@@ -0,0 +1,371 @@
+//! Typed callback data.
+//!
+//! Telegram limits callback data to 64 bytes of text. With this module,
+//! you can put any serializable value into a button and get it back when
+//! the user presses the button:
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use tbot::util::callback_data::{CallbackData, Codec};
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! enum Vote {
+//!     Up { post: u32 },
+//!     Down { post: u32 },
+//! }
+//!
+//! impl CallbackData for Vote {
+//!     const TAG: &'static str = "vote";
+//! }
+//!
+//! let codec = Codec::new().signed("a secret only the bot knows");
+//! let data = codec.encode(&Vote::Up { post: 42 }).unwrap();
+//! assert_eq!(codec.decode::<Vote>(&data).unwrap(), Vote::Up { post: 42 });
+//! ```
+//!
+//! The encoded data is the type's [tag] followed by the value serialized
+//! to JSON and encoded with base64. If the codec is [signed], a truncated
+//! HMAC-SHA256 signature is appended so that users can't forge buttons.
+//! If the data doesn't fit in 64 bytes, the payload is stored in memory
+//! and the data references it with a short key. Stored payloads are lost
+//! when the bot restarts, so prefer compact types for long-living buttons.
+//!
+//! To handle typed callbacks, configure the event loop's codec with
+//! [`EventLoop::callback_data_codec`] and use
+//! [`EventLoop::message_data_callback_typed`] or
+//! [`EventLoop::inline_data_callback_typed`].
+//!
+//! [tag]: ./trait.CallbackData.html#associatedconstant.TAG
+//! [signed]: ./struct.Codec.html#method.signed
+//! [`EventLoop::callback_data_codec`]: ../../event_loop/struct.EventLoop.html#method.callback_data_codec
+//! [`EventLoop::message_data_callback_typed`]: ../../event_loop/struct.EventLoop.html#method.message_data_callback_typed
+//! [`EventLoop::inline_data_callback_typed`]: ../../event_loop/struct.EventLoop.html#method.inline_data_callback_typed
+
+use crate::errors;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+use std::{
+    collections::{hash_map::RandomState, HashMap, VecDeque},
+    fmt::{self, Debug, Formatter},
+    hash::{BuildHasher, Hasher},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+const MAX_LENGTH: usize = 64;
+const SIGNATURE_LENGTH: usize = 12;
+const DEFAULT_CAPACITY: usize = 1024;
+
+const INLINE_SEPARATOR: char = ':';
+const STORED_SEPARATOR: char = '#';
+const SIGNATURE_SEPARATOR: char = '.';
+
+/// A type which can be put into callback data.
+pub trait CallbackData: Serialize + DeserializeOwned {
+    /// A short string which distinguishes this type's data from other
+    /// types' data. It must not contain `:`, `#` or `.`.
+    const TAG: &'static str;
+}
+
+#[derive(Debug)]
+struct Stored {
+    instance: u32,
+    next_key: u32,
+    capacity: usize,
+    payloads: HashMap<String, String>,
+    keys: HashMap<String, String>,
+    order: VecDeque<String>,
+}
+
+impl Stored {
+    fn new() -> Self {
+        // Keys must not repeat across restarts, otherwise old buttons could
+        // reference new payloads. `RandomState` is seeded randomly, so we use
+        // it to get a random prefix without depending on a random crate.
+        #[allow(clippy::cast_possible_truncation)]
+        let instance = RandomState::new().build_hasher().finish() as u32;
+
+        Self {
+            instance,
+            next_key: 0,
+            capacity: DEFAULT_CAPACITY,
+            payloads: HashMap::new(),
+            keys: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn store(&mut self, payload: String) -> String {
+        if let Some(key) = self.keys.get(&payload) {
+            return key.clone();
+        }
+
+        let key = format!("{:08x}{:x}", self.instance, self.next_key);
+        self.next_key = self.next_key.wrapping_add(1);
+
+        while self.order.len() >= self.capacity {
+            let evicted = match self.order.pop_front() {
+                Some(evicted) => evicted,
+                None => break,
+            };
+
+            if let Some(payload) = self.payloads.remove(&evicted) {
+                self.keys.remove(&payload);
+            }
+        }
+
+        self.payloads.insert(key.clone(), payload.clone());
+        self.keys.insert(payload, key.clone());
+        self.order.push_back(key.clone());
+
+        key
+    }
+}
+
+/// Encodes values into callback data and decodes them back. See
+/// [the module's docs] to learn how to use it.
+///
+/// [the module's docs]: ./index.html
+#[derive(Clone)]
+#[must_use]
+pub struct Codec {
+    key: Option<Arc<[u8]>>,
+    stored: Arc<Mutex<Stored>>,
+}
+
+impl Codec {
+    /// Constructs a codec which doesn't sign data.
+    pub fn new() -> Self {
+        Self {
+            key: None,
+            stored: Arc::new(Mutex::new(Stored::new())),
+        }
+    }
+
+    /// Makes the codec sign data with the key and reject data with
+    /// an invalid or missing signature.
+    pub fn signed(mut self, key: impl AsRef<[u8]>) -> Self {
+        self.key = Some(key.as_ref().into());
+        self
+    }
+
+    /// Configures how many long payloads are stored in memory. Once
+    /// the limit is reached, the oldest payloads are evicted and their
+    /// buttons stop working. By default, 1024 payloads are stored.
+    pub fn storage_capacity(self, capacity: usize) -> Self {
+        self.lock().capacity = capacity.max(1);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Stored> {
+        self.stored.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn signature(&self, data: &str) -> Option<Hmac<Sha256>> {
+        let key = self.key.as_ref()?;
+        let mut mac = Hmac::<Sha256>::new_from_slice(key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(data.as_bytes());
+
+        Some(mac)
+    }
+
+    fn sign(&self, data: String) -> String {
+        match self.signature(&data) {
+            Some(mac) => {
+                let signature = mac.finalize().into_bytes();
+                let signature =
+                    URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_LENGTH]);
+                format!("{}{}{}", data, SIGNATURE_SEPARATOR, signature)
+            }
+            None => data,
+        }
+    }
+
+    fn verify<'a>(
+        &self,
+        data: &'a str,
+    ) -> Result<&'a str, errors::CallbackData> {
+        if self.key.is_none() {
+            return Ok(data);
+        }
+
+        let separator = data
+            .rfind(SIGNATURE_SEPARATOR)
+            .ok_or(errors::CallbackData::MissingSignature)?;
+        let (data, signature) = data.split_at(separator);
+        let signature = URL_SAFE_NO_PAD
+            .decode(&signature[1..])
+            .map_err(|_| errors::CallbackData::InvalidSignature)?;
+
+        // `verify_truncated_left` accepts signatures of any length, which
+        // would make forging a short signature easy.
+        if signature.len() != SIGNATURE_LENGTH {
+            return Err(errors::CallbackData::InvalidSignature);
+        }
+
+        self.signature(data)
+            .expect("the codec has a key")
+            .verify_truncated_left(&signature)
+            .map_err(|_| errors::CallbackData::InvalidSignature)?;
+
+        Ok(data)
+    }
+
+    /// Encodes a value into callback data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T::TAG` contains `:`, `#` or `.`.
+    pub fn encode<T: CallbackData>(
+        &self,
+        value: &T,
+    ) -> Result<String, errors::CallbackData> {
+        assert!(
+            !T::TAG.contains(
+                &[INLINE_SEPARATOR, STORED_SEPARATOR, SIGNATURE_SEPARATOR][..]
+            ),
+            "[tbot] Callback data tags must not contain `:`, `#` or `.`",
+        );
+
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(value)?);
+        let data =
+            self.sign(format!("{}{}{}", T::TAG, INLINE_SEPARATOR, payload));
+
+        if data.len() <= MAX_LENGTH {
+            return Ok(data);
+        }
+
+        let key = self.lock().store(payload);
+        let data = self.sign(format!("{}{}{}", T::TAG, STORED_SEPARATOR, key));
+
+        if data.len() <= MAX_LENGTH {
+            Ok(data)
+        } else {
+            Err(errors::CallbackData::TooLong { length: data.len() })
+        }
+    }
+
+    /// Decodes callback data. Fails if the data was encoded for another type
+    /// or, if the codec is signed, if the signature is invalid.
+    pub fn decode<T: CallbackData>(
+        &self,
+        data: &str,
+    ) -> Result<T, errors::CallbackData> {
+        let data = self.verify(data)?;
+
+        let separator = data
+            .find(&[INLINE_SEPARATOR, STORED_SEPARATOR][..])
+            .ok_or(errors::CallbackData::Malformed)?;
+        let (tag, rest) = data.split_at(separator);
+
+        if tag != T::TAG {
+            return Err(errors::CallbackData::UnexpectedTag);
+        }
+
+        let payload = if rest.starts_with(STORED_SEPARATOR) {
+            self.lock()
+                .payloads
+                .get(&rest[1..])
+                .cloned()
+                .ok_or(errors::CallbackData::Expired)?
+        } else {
+            rest[1..].to_owned()
+        };
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| errors::CallbackData::Malformed)?;
+        Ok(serde_json::from_slice(&payload)?)
+    }
+
+    /// Checks if the data was encoded for `T`, without decoding it.
+    #[must_use]
+    pub fn is<T: CallbackData>(&self, data: &str) -> bool {
+        let data = match self.verify(data) {
+            Ok(data) => data,
+            Err(_) => return false,
+        };
+
+        data.strip_prefix(T::TAG).map_or(false, |rest| {
+            rest.starts_with(&[INLINE_SEPARATOR, STORED_SEPARATOR][..])
+        })
+    }
+}
+
+impl Default for Codec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Codec {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Codec")
+            .field("is_signed", &self.key.is_some())
+            .field("stored", &self.lock().payloads.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Page(u32);
+
+    impl CallbackData for Page {
+        const TAG: &'static str = "p";
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Long(String);
+
+    impl CallbackData for Long {
+        const TAG: &'static str = "l";
+    }
+
+    #[test]
+    fn rejects_forged_data() {
+        let codec = Codec::new().signed("secret");
+        let data = codec.encode(&Page(1)).unwrap();
+        assert_eq!(codec.decode::<Page>(&data).unwrap(), Page(1));
+
+        let forged = Codec::new().signed("guess").encode(&Page(2)).unwrap();
+        assert!(codec
+            .decode::<Page>(&forged)
+            .unwrap_err()
+            .is_invalid_signature());
+
+        let truncated = &data[..data.len() - 8];
+        assert!(codec
+            .decode::<Page>(truncated)
+            .unwrap_err()
+            .is_invalid_signature());
+
+        let unsigned = Codec::new().encode(&Page(2)).unwrap();
+        assert!(codec
+            .decode::<Page>(&unsigned)
+            .unwrap_err()
+            .is_missing_signature());
+        assert!(codec.decode::<Long>(&data).unwrap_err().is_unexpected_tag());
+    }
+
+    #[test]
+    fn stores_long_payloads() {
+        let codec = Codec::new().signed("secret").storage_capacity(1);
+        let long = Long("a".repeat(100));
+
+        let data = codec.encode(&long).unwrap();
+        assert!(data.len() <= MAX_LENGTH);
+        assert_eq!(codec.decode::<Long>(&data).unwrap(), long);
+        assert!(codec.is::<Long>(&data));
+
+        codec.encode(&Long("b".repeat(100))).unwrap();
+        assert!(codec.decode::<Long>(&data).unwrap_err().is_expired());
+    }
+}
@@ -0,0 +1,322 @@
+//! Correlating chosen inline results with the objects they represent.
+//!
+//! When a user picks an inline result, Telegram only sends its ID back.
+//! [`Registry`] remembers what each result ID referred to when you answered
+//! the query, gives the original object to your `chosen_inline` handler,
+//! and keeps the IDs of the sent inline messages so you can edit them later:
+//!
+//! ```no_run
+//! use tbot::{
+//!     types::inline_query::{self, result::Article},
+//!     types::input_message_content::Text,
+//!     types::keyboard::inline::{Button, ButtonKind, OwnedKeyboard},
+//!     util::chosen_inline::Registry,
+//! };
+//!
+//! #[derive(Debug)]
+//! struct Poll {
+//!     question: String,
+//! }
+//!
+//! let registry = Registry::new();
+//! let mut bot = tbot::from_env!("BOT_TOKEN").event_loop();
+//!
+//! bot.inline({
+//!     let registry = registry.clone();
+//!     move |context| {
+//!         let registry = registry.clone();
+//!         async move {
+//!             let result_id = format!("poll-{}", context.id.0);
+//!             let question = context.query.clone();
+//!             registry.remember(result_id.clone(), Poll { question });
+//!
+//!             // Telegram only reports the inline message's ID
+//!             // if the message has a keyboard.
+//!             let keyboard = OwnedKeyboard::new(vec![vec![Button::new(
+//!                 "Vote",
+//!                 ButtonKind::with_callback_data("vote"),
+//!             )]]);
+//!             let article = Article::new("Start a poll", Text::new("Poll"));
+//!             let result = inline_query::Result::new(result_id, article)
+//!                 .reply_markup(keyboard.as_keyboard());
+//!
+//!             let call_result = context.answer(vec![result]).call().await;
+//!             if let Err(err) = call_result {
+//!                 dbg!(err);
+//!             }
+//!         }
+//!     }
+//! });
+//!
+//! registry.register(&mut bot, |context, poll| async move {
+//!     let id = match &context.inline_message_id {
+//!         Some(id) => id.clone(),
+//!         None => return,
+//!     };
+//!
+//!     let call_result =
+//!         context.bot.edit_inline_text(id, poll.question.as_str()).call().await;
+//!     if let Err(err) = call_result {
+//!         dbg!(err);
+//!     }
+//! });
+//! ```
+//!
+//! Telegram only sends chosen inline results if you enable inline feedback
+//! with `BotFather`. Results which the registry doesn't know are ignored, so
+//! you can still handle them with your own `chosen_inline` handlers.
+//!
+//! [`Registry`]: ./struct.Registry.html
+
+use crate::{
+    contexts, event_loop::EventLoop, state::StatefulEventLoop,
+    types::InlineMessageId,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+const DEFAULT_CAPACITY: usize = 10_000;
+
+struct Record<T> {
+    value: Arc<T>,
+    messages: Vec<InlineMessageId<'static>>,
+}
+
+struct Records<T> {
+    capacity: usize,
+    by_id: HashMap<String, Record<T>>,
+    order: VecDeque<String>,
+}
+
+impl<T> Records<T> {
+    fn new() -> Self {
+        Self {
+            capacity: DEFAULT_CAPACITY,
+            by_id: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn insert(&mut self, result_id: String, value: Arc<T>) {
+        // Re-remembering a result refreshes it without losing the messages
+        // already sent with it.
+        if let Some(record) = self.by_id.get_mut(&result_id) {
+            record.value = value;
+            self.order.retain(|id| *id != result_id);
+        } else {
+            let record = Record {
+                value,
+                messages: Vec::new(),
+            };
+            self.by_id.insert(result_id.clone(), record);
+        }
+
+        self.order.push_back(result_id);
+        while self.order.len() > self.capacity {
+            match self.order.pop_front() {
+                Some(evicted) => self.by_id.remove(&evicted),
+                None => break,
+            };
+        }
+    }
+}
+
+/// Remembers the objects behind inline results. See [the module's docs]
+/// to learn how to use it.
+///
+/// The registry keeps the 10 000 most recently remembered results by default.
+///
+/// [the module's docs]: ./index.html
+#[must_use]
+pub struct Registry<T> {
+    records: Arc<Mutex<Records<T>>>,
+}
+
+impl<T> Registry<T> {
+    /// Constructs an empty registry.
+    pub fn new() -> Self {
+        Self {
+            records: Arc::new(Mutex::new(Records::new())),
+        }
+    }
+
+    /// Configures how many results the registry keeps. When the registry
+    /// is full, the oldest results are forgotten.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn capacity(self, capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "[tbot] The registry capacity must not be zero"
+        );
+        self.lock().capacity = capacity;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Records<T>> {
+        self.records.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Remembers what the result with this ID refers to. Call it for every
+    /// result you answer an inline query with.
+    pub fn remember(&self, result_id: impl Into<String>, value: T) {
+        self.lock().insert(result_id.into(), Arc::new(value));
+    }
+
+    /// Returns the object the result refers to.
+    #[must_use]
+    pub fn get(&self, result_id: &str) -> Option<Arc<T>> {
+        self.lock()
+            .by_id
+            .get(result_id)
+            .map(|record| Arc::clone(&record.value))
+    }
+
+    /// Returns the IDs of inline messages sent with the result, so that
+    /// they can be edited with [`Bot::edit_inline_text`] and similar methods.
+    ///
+    /// [`Bot::edit_inline_text`]: ../../struct.Bot.html#method.edit_inline_text
+    #[must_use]
+    pub fn inline_message_ids(
+        &self,
+        result_id: &str,
+    ) -> Vec<InlineMessageId<'static>> {
+        self.lock()
+            .by_id
+            .get(result_id)
+            .map(|record| record.messages.clone())
+            .unwrap_or_default()
+    }
+
+    /// Forgets the result.
+    pub fn forget(&self, result_id: &str) {
+        let mut records = self.lock();
+        if records.by_id.remove(result_id).is_some() {
+            records.order.retain(|id| id != result_id);
+        }
+    }
+
+    fn choose(&self, context: &contexts::ChosenInline) -> Option<Arc<T>> {
+        let mut records = self.lock();
+        let record = records.by_id.get_mut(&context.result_id)?;
+
+        if let Some(id) = &context.inline_message_id {
+            record.messages.push(id.clone());
+        }
+
+        let value = Arc::clone(&record.value);
+        drop(records);
+
+        Some(value)
+    }
+}
+
+impl<T: Send + Sync + 'static> Registry<T> {
+    /// Adds a `chosen_inline` handler to the event loop which receives
+    /// the object the chosen result refers to.
+    pub fn register<H, F>(&self, event_loop: &mut EventLoop, handler: H)
+    where
+        H: (Fn(Arc<contexts::ChosenInline>, Arc<T>) -> F)
+            + Send
+            + Sync
+            + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let registry = self.clone();
+        event_loop.chosen_inline(move |context| {
+            let value = registry.choose(&context);
+            let future = value.map(|value| handler(context, value));
+
+            async move {
+                if let Some(future) = future {
+                    future.await;
+                }
+            }
+        });
+    }
+
+    /// Adds a `chosen_inline` handler to the stateful event loop which
+    /// receives the object the chosen result refers to.
+    pub fn register_stateful<S, H, F>(
+        &self,
+        event_loop: &mut StatefulEventLoop<S>,
+        handler: H,
+    ) where
+        S: Send + Sync + 'static,
+        H: (Fn(Arc<contexts::ChosenInline>, Arc<T>, Arc<S>) -> F)
+            + Send
+            + Sync
+            + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let registry = self.clone();
+        event_loop.chosen_inline(move |context, state| {
+            let value = registry.choose(&context);
+            let future = value.map(|value| handler(context, value, state));
+
+            async move {
+                if let Some(future) = future {
+                    future.await;
+                }
+            }
+        });
+    }
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Registry<T> {
+    fn clone(&self) -> Self {
+        Self {
+            records: Arc::clone(&self.records),
+        }
+    }
+}
+
+impl<T> Debug for Registry<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let records = self.lock();
+        formatter
+            .debug_struct("Registry")
+            .field("capacity", &records.capacity)
+            .field("len", &records.by_id.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refreshes_and_forgets_results() {
+        let registry = Registry::new().capacity(2);
+        registry.remember("a", 1);
+        registry.remember("b", 2);
+        registry
+            .lock()
+            .by_id
+            .get_mut("a")
+            .unwrap()
+            .messages
+            .push("message".into());
+        registry.remember("a", 3);
+        registry.remember("c", 4);
+
+        assert_eq!(registry.get("b"), None);
+        assert_eq!(registry.get("a").as_deref(), Some(&3));
+        assert_eq!(registry.inline_message_ids("a"), vec!["message".into()]);
+        registry.forget("c");
+        assert_eq!(registry.get("c"), None);
+    }
+}
@@ -0,0 +1,505 @@
+//! Utilities for waiting for the next message from a user in a handler.
+
+use crate::{
+    contexts::fields::Message,
+    errors,
+    methods::SendMessage,
+    types::{self, chat, keyboard, message, parameters::Text, user},
+    Bot,
+};
+use futures::channel::oneshot;
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+use tokio::time::timeout;
+
+/// Decides which messages may resolve a pending [`NextMessage`].
+///
+/// [`NextMessage`]: ./struct.NextMessage.html
+#[derive(Clone)]
+#[must_use]
+pub struct Filter(Arc<dyn Fn(&types::Message) -> bool + Send + Sync>);
+
+impl Filter {
+    /// Constructs a filter from a function.
+    pub fn new<F>(filter: F) -> Self
+    where
+        F: Fn(&types::Message) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(filter))
+    }
+
+    /// Accepts any message.
+    pub fn any() -> Self {
+        Self::new(|_| true)
+    }
+
+    /// Accepts only text messages.
+    pub fn text() -> Self {
+        Self::new(|message| message.kind.is_text())
+    }
+
+    /// Accepts only photos.
+    pub fn photo() -> Self {
+        Self::new(|message| message.kind.is_photo())
+    }
+
+    /// Accepts only documents.
+    pub fn document() -> Self {
+        Self::new(|message| message.kind.is_document())
+    }
+
+    /// Accepts only locations.
+    pub fn location() -> Self {
+        Self::new(|message| message.kind.is_location())
+    }
+
+    /// Accepts only contacts.
+    pub fn contact() -> Self {
+        Self::new(|message| message.kind.is_contact())
+    }
+
+    /// Accepts only voice messages.
+    pub fn voice() -> Self {
+        Self::new(|message| message.kind.is_voice())
+    }
+
+    fn matches(&self, message: &types::Message) -> bool {
+        (self.0)(message)
+    }
+}
+
+impl Debug for Filter {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("Filter(..)")
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self::any()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct Key {
+    chat_id: chat::Id,
+    user_id: Option<user::Id>,
+}
+
+impl Key {
+    const fn from_message(message: &types::Message) -> Self {
+        let user_id = match &message.from {
+            Some(message::From::User(user)) => Some(user.id),
+            _ => None,
+        };
+
+        Self {
+            chat_id: message.chat.id,
+            user_id,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Waiter {
+    id: u64,
+    filter: Filter,
+    sender: oneshot::Sender<types::Message>,
+}
+
+#[derive(Debug, Default)]
+struct WaitersInner {
+    next_id: u64,
+    waiters: HashMap<Key, Vec<Waiter>>,
+}
+
+/// The registry of pending [`NextMessage`]s which is shared between a bot
+/// and its event loop.
+///
+/// [`NextMessage`]: ./struct.NextMessage.html
+#[derive(Debug, Default)]
+pub(crate) struct Waiters(Mutex<WaitersInner>);
+
+impl Waiters {
+    fn lock(&self) -> MutexGuard<'_, WaitersInner> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn register(
+        &self,
+        key: Key,
+        filter: Filter,
+    ) -> (u64, oneshot::Receiver<types::Message>) {
+        let (sender, receiver) = oneshot::channel();
+        let mut inner = self.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        inner.waiters.entry(key).or_default().push(Waiter {
+            id,
+            filter,
+            sender,
+        });
+        drop(inner);
+
+        (id, receiver)
+    }
+
+    fn remove(&self, key: Key, id: u64) {
+        let mut inner = self.lock();
+
+        if let Some(waiters) = inner.waiters.get_mut(&key) {
+            waiters.retain(|waiter| waiter.id != id);
+
+            if waiters.is_empty() {
+                inner.waiters.remove(&key);
+            }
+        }
+    }
+
+    /// Cancels the waiters for the key, as well as the waiters for anyone
+    /// in the same chat, since they would receive the user's messages too.
+    fn cancel(&self, key: Key) {
+        let chat_key = Key {
+            user_id: None,
+            ..key
+        };
+
+        let mut inner = self.lock();
+        inner.waiters.remove(&key);
+        inner.waiters.remove(&chat_key);
+    }
+
+    /// Passes the message to the first matching waiter. If there's no such
+    /// waiter, the message is returned back so that it can be handled
+    /// by the event loop's handlers.
+    pub(crate) fn resolve(
+        &self,
+        mut message: types::Message,
+    ) -> Option<types::Message> {
+        let mut inner = self.lock();
+        if inner.waiters.is_empty() {
+            return Some(message);
+        }
+
+        let user_key = Key::from_message(&message);
+        let chat_key = Key {
+            user_id: None,
+            ..user_key
+        };
+
+        for key in &[user_key, chat_key] {
+            let waiters = match inner.waiters.get_mut(key) {
+                Some(waiters) => waiters,
+                None => continue,
+            };
+
+            waiters.retain(|waiter| !waiter.sender.is_canceled());
+
+            while let Some(index) = waiters
+                .iter()
+                .position(|waiter| waiter.filter.matches(&message))
+            {
+                let waiter = waiters.remove(index);
+                match waiter.sender.send(message) {
+                    Ok(()) => {
+                        if waiters.is_empty() {
+                            inner.waiters.remove(key);
+                        }
+                        return None;
+                    }
+                    Err(returned) => message = returned,
+                }
+            }
+
+            if waiters.is_empty() {
+                inner.waiters.remove(key);
+            }
+        }
+        drop(inner);
+
+        Some(message)
+    }
+}
+
+/// Removes the waiter from the registry if the future is dropped before
+/// a message arrives.
+struct Guard<'a> {
+    waiters: &'a Waiters,
+    key: Key,
+    id: u64,
+}
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        self.waiters.remove(self.key, self.id);
+    }
+}
+
+/// Waits for the next message from a user.
+///
+/// To construct `NextMessage`, use [`Conversation::next_message`].
+///
+/// [`Conversation::next_message`]: ./trait.Conversation.html#method.next_message
+#[derive(Debug, Clone)]
+#[must_use = "`NextMessage` does nothing unless `call` is called"]
+pub struct NextMessage {
+    bot: Bot,
+    key: Key,
+    filter: Filter,
+    timeout: Option<Duration>,
+}
+
+impl NextMessage {
+    fn new<C: Message + ?Sized>(context: &C) -> Self {
+        let user_id = match context.from() {
+            Some(message::From::User(user)) => Some(user.id),
+            _ => None,
+        };
+
+        Self {
+            bot: context.bot().clone(),
+            key: Key {
+                chat_id: context.chat().id,
+                user_id,
+            },
+            filter: Filter::any(),
+            timeout: None,
+        }
+    }
+
+    /// Configures which messages are accepted. By default, any message
+    /// is accepted.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Configures for how long to wait for the message. By default, `tbot`
+    /// waits forever.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Accepts a message from anyone in the chat, not only from the author
+    /// of the incoming message.
+    pub const fn from_anyone(mut self) -> Self {
+        self.key.user_id = None;
+        self
+    }
+
+    fn register(&self) -> (Guard<'_>, oneshot::Receiver<types::Message>) {
+        let waiters = self.bot.waiters();
+        let (id, receiver) = waiters.register(self.key, self.filter.clone());
+        let guard = Guard {
+            waiters,
+            key: self.key,
+            id,
+        };
+
+        (guard, receiver)
+    }
+
+    async fn wait(
+        &self,
+        receiver: oneshot::Receiver<types::Message>,
+    ) -> Result<types::Message, errors::Conversation> {
+        let message = match self.timeout {
+            Some(duration) => timeout(duration, receiver).await?,
+            None => receiver.await,
+        };
+
+        message.map_err(|_| errors::Conversation::Cancelled)
+    }
+
+    /// Waits for the message.
+    pub async fn call(self) -> Result<types::Message, errors::Conversation> {
+        let (_guard, receiver) = self.register();
+        self.wait(receiver).await
+    }
+}
+
+/// Sends a question and waits for the user's answer.
+///
+/// To construct `Ask`, use [`Conversation::ask`].
+///
+/// [`Conversation::ask`]: ./trait.Conversation.html#method.ask
+#[derive(Debug, Clone)]
+#[must_use = "`Ask` does nothing unless `call` is called"]
+pub struct Ask<'a> {
+    question: SendMessage<'a>,
+    next_message: NextMessage,
+}
+
+impl<'a> Ask<'a> {
+    /// Configures which messages are accepted as the answer. By default,
+    /// any message is accepted.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.next_message = self.next_message.filter(filter);
+        self
+    }
+
+    /// Configures for how long to wait for the answer. By default, `tbot`
+    /// waits forever.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.next_message = self.next_message.timeout(timeout);
+        self
+    }
+
+    /// Accepts an answer from anyone in the chat, not only from the author
+    /// of the incoming message.
+    pub fn from_anyone(mut self) -> Self {
+        self.next_message = self.next_message.from_anyone();
+        self
+    }
+
+    /// Configures a keyboard for the question.
+    pub fn reply_markup(
+        mut self,
+        markup: impl Into<keyboard::Any<'a>>,
+    ) -> Self {
+        self.question = self.question.reply_markup(markup);
+        self
+    }
+
+    /// Sends the question and waits for the answer.
+    pub async fn call(self) -> Result<types::Message, errors::Conversation> {
+        // The waiter is registered before sending the question so that
+        // a quick answer isn't missed.
+        let (_guard, receiver) = self.next_message.register();
+        self.question.call().await?;
+        self.next_message.wait(receiver).await
+    }
+}
+
+/// An utility trait for message contexts with methods to wait for the next
+/// message from the user.
+///
+/// While a handler waits for a message, the matching message is passed
+/// to the handler instead of the event loop's handlers. Commands are never
+/// passed to waiting handlers, so that commands such as `/cancel` still
+/// reach their handlers.
+///
+/// ```no_run
+/// use tbot::{prelude::*, util::conversation::Filter};
+///
+/// let mut bot = tbot::from_env!("BOT_TOKEN").event_loop();
+///
+/// bot.start(|context| async move {
+///     let answer = context
+///         .ask("What's your name?")
+///         .filter(Filter::text())
+///         .call()
+///         .await;
+///
+///     if let Ok(answer) = answer {
+///         dbg!(answer.kind);
+///     }
+/// });
+/// ```
+pub trait Conversation: Message {
+    /// Sends a question to this chat and waits for the user's answer.
+    fn ask<'a>(&'a self, question: impl Into<Text<'a>>) -> Ask<'a> {
+        Ask {
+            question: self.bot().send_message(self.chat().id, question),
+            next_message: NextMessage::new(self),
+        }
+    }
+
+    /// Waits for the next message from the user in this chat.
+    fn next_message(&self) -> NextMessage {
+        NextMessage::new(self)
+    }
+
+    /// Cancels all handlers waiting for a message from the user in this chat,
+    /// including those which wait for a message from anyone.
+    fn cancel_waiting(&self) {
+        let key = NextMessage::new(self).key;
+        self.bot().waiters().cancel(key);
+    }
+}
+
+impl<T: Message> Conversation for T {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(user_id: i64, text: &str) -> types::Message {
+        let message = serde_json::json!({
+            "message_id": 1,
+            "date": 0,
+            "chat": { "id": 1, "type": "private", "first_name": "A" },
+            "from": { "id": user_id, "is_bot": false, "first_name": "A" },
+            "text": text,
+        });
+
+        serde_json::from_str(&message.to_string()).unwrap()
+    }
+
+    #[test]
+    fn resolves_only_matching_waiters() {
+        let waiters = Waiters::default();
+        let key = Key {
+            chat_id: chat::Id(1),
+            user_id: Some(user::Id(2)),
+        };
+        let (_, mut receiver) = waiters.register(
+            key,
+            Filter::new(|message| match &message.kind {
+                message::Kind::Text(text) => text.value == "yes",
+                _ => false,
+            }),
+        );
+
+        assert!(waiters.resolve(message(3, "yes")).is_some());
+        assert!(waiters.resolve(message(2, "no")).is_some());
+        assert!(waiters.resolve(message(2, "yes")).is_none());
+        assert!(receiver.try_recv().unwrap().is_some());
+        assert!(waiters.lock().waiters.is_empty());
+    }
+
+    #[test]
+    fn skips_dropped_waiters() {
+        let waiters = Waiters::default();
+        let key = Key {
+            chat_id: chat::Id(1),
+            user_id: None,
+        };
+        let (_, receiver) = waiters.register(key, Filter::any());
+        drop(receiver);
+
+        assert!(waiters.resolve(message(2, "hi")).is_some());
+        assert!(waiters.lock().waiters.is_empty());
+    }
+
+    #[test]
+    fn cancels_waiters_for_anyone() {
+        let waiters = Waiters::default();
+        let user_key = Key {
+            chat_id: chat::Id(1),
+            user_id: Some(user::Id(2)),
+        };
+        let chat_key = Key {
+            user_id: None,
+            ..user_key
+        };
+        let other_key = Key {
+            user_id: Some(user::Id(3)),
+            ..user_key
+        };
+        let _user = waiters.register(user_key, Filter::any());
+        let _chat = waiters.register(chat_key, Filter::any());
+        let _other = waiters.register(other_key, Filter::any());
+
+        waiters.cancel(user_key);
+        let inner = waiters.lock();
+        assert_eq!(inner.waiters.len(), 1);
+        assert!(inner.waiters.contains_key(&other_key));
+    }
+}
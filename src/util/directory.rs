@@ -0,0 +1,538 @@
+//! A directory of users and chats the bot has seen.
+//!
+//! The Bot API can't find a user by their username, so bots which accept
+//! `@username` arguments have to remember users themselves. A [`Directory`]
+//! records every user and chat it observes in updates: senders, forwards,
+//! mentioned users, new members, and so on. It's opt-in and enabled with
+//! [`bot::Builder::directory`], after which the event loop feeds it with all
+//! incoming updates:
+//!
+//! ```no_run
+//! # async fn foo() -> Result<(), tbot::errors::Storage> {
+//! use tbot::{
+//!     bot,
+//!     prelude::*,
+//!     state::storage::{self, File},
+//!     util::directory::{Directory, Found},
+//! };
+//!
+//! let storage = File::open("directory.json").await?;
+//! let directory = Directory::new();
+//! storage::load(&storage, &directory).await?;
+//!
+//! let bot = bot::Builder::with_env_token("BOT_TOKEN")
+//!     .directory(directory)
+//!     .build();
+//! let mut bot = bot.event_loop();
+//!
+//! bot.command("whois", |context| async move {
+//!     let directory = context.bot.directory().unwrap();
+//!     let reply = match directory.resolve(&context.text.value) {
+//!         Some(Found::User(user)) => format!("User {}", user.id.0),
+//!         Some(Found::Chat(chat)) => format!("Chat {}", chat.id.0),
+//!         None => "I haven't seen them yet".to_owned(),
+//!     };
+//!     context.send_message_in_reply(&reply).call().await.unwrap();
+//! });
+//! # Ok(()) }
+//! ```
+//!
+//! The directory lives in memory. It implements [`Persistent`], so it can be
+//! saved to and loaded from any [`Storage`] with [`storage::save`] and
+//! [`storage::load`].
+//!
+//! [`Directory`]: ./struct.Directory.html
+//! [`bot::Builder::directory`]: ../../bot/struct.Builder.html#method.directory
+//! [`Persistent`]: ../../state/storage/trait.Persistent.html
+//! [`Storage`]: ../../state/storage/trait.Storage.html
+//! [`storage::save`]: ../../state/storage/fn.save.html
+//! [`storage::load`]: ../../state/storage/fn.load.html
+
+use crate::{
+    errors,
+    state::storage::{Entry, Persistent},
+    types::{
+        self, callback, chat,
+        message::{self, forward, text::EntityKind, Message},
+        update, user,
+    },
+};
+use is_macro::Is;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+const USERS: &str = "users/";
+const CHATS: &str = "chats/";
+
+/// A user as last seen by the bot.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct User {
+    /// The ID of the user.
+    pub id: user::Id,
+    /// `true` if the user is a bot.
+    pub is_bot: bool,
+    /// The first name of the user.
+    pub first_name: String,
+    /// The last name of the user.
+    pub last_name: Option<String>,
+    /// The username of the user.
+    pub username: Option<String>,
+}
+
+impl From<&types::User> for User {
+    fn from(user: &types::User) -> Self {
+        Self {
+            id: user.id,
+            is_bot: user.is_bot,
+            first_name: user.first_name.clone(),
+            last_name: user.last_name.clone(),
+            username: user.username.clone(),
+        }
+    }
+}
+
+/// Represents kinds of chats kept in the directory. Private chats aren't
+/// kept as they're described by their users.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ChatKind {
+    /// The chat is a group.
+    Group,
+    /// The chat is a supergroup.
+    Supergroup,
+    /// The chat is a channel.
+    Channel,
+}
+
+/// A chat as last seen by the bot.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Chat {
+    /// The ID of the chat.
+    pub id: chat::Id,
+    /// The kind of the chat.
+    pub kind: ChatKind,
+    /// The title of the chat.
+    pub title: String,
+    /// The username of the chat.
+    pub username: Option<String>,
+}
+
+impl Chat {
+    fn from_chat(chat: &types::Chat) -> Option<Self> {
+        let (kind, title, username) = match &chat.kind {
+            chat::Kind::Private { .. } => return None,
+            chat::Kind::Group { title, .. } => (ChatKind::Group, title, None),
+            chat::Kind::Supergroup {
+                title, username, ..
+            } => (ChatKind::Supergroup, title, username.clone()),
+            chat::Kind::Channel {
+                title, username, ..
+            } => (ChatKind::Channel, title, username.clone()),
+        };
+
+        Some(Self {
+            id: chat.id,
+            kind,
+            title: title.clone(),
+            username,
+        })
+    }
+}
+
+/// A user or a chat found in the directory.
+#[derive(Debug, PartialEq, Eq, Clone, Is)]
+pub enum Found {
+    /// A user was found.
+    User(User),
+    /// A chat was found.
+    Chat(Chat),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Owner {
+    User(user::Id),
+    Chat(chat::Id),
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    users: HashMap<user::Id, User>,
+    chats: HashMap<chat::Id, Chat>,
+    usernames: HashMap<String, Owner>,
+}
+
+impl Inner {
+    fn update_username(
+        &mut self,
+        old: Option<&str>,
+        new: Option<&str>,
+        owner: Owner,
+    ) {
+        if let Some(old) = old {
+            let old = normalize(old);
+            if self.usernames.get(&old) == Some(&owner) {
+                self.usernames.remove(&old);
+            }
+        }
+        if let Some(new) = new {
+            self.usernames.insert(normalize(new), owner);
+        }
+    }
+
+    fn insert_user(&mut self, user: User) {
+        let old = self
+            .users
+            .get(&user.id)
+            .and_then(|old| old.username.clone());
+        self.update_username(
+            old.as_deref(),
+            user.username.as_deref(),
+            Owner::User(user.id),
+        );
+        self.users.insert(user.id, user);
+    }
+
+    fn insert_chat(&mut self, chat: Chat) {
+        let old = self
+            .chats
+            .get(&chat.id)
+            .and_then(|old| old.username.clone());
+        self.update_username(
+            old.as_deref(),
+            chat.username.as_deref(),
+            Owner::Chat(chat.id),
+        );
+        self.chats.insert(chat.id, chat);
+    }
+
+    fn observe_user(&mut self, user: &types::User) {
+        if self.users.get(&user.id) != Some(&User::from(user)) {
+            self.insert_user(user.into());
+        }
+    }
+
+    fn observe_chat(&mut self, chat: &types::Chat) {
+        if let Some(chat) = Chat::from_chat(chat) {
+            if self.chats.get(&chat.id) != Some(&chat) {
+                self.insert_chat(chat);
+            }
+        }
+    }
+
+    fn observe_text(&mut self, text: &message::Text) {
+        for entity in &text.entities {
+            if let EntityKind::TextMention(user) = &entity.kind {
+                self.observe_user(user);
+            }
+        }
+    }
+
+    fn observe_message(&mut self, message: &Message) {
+        self.observe_chat(&message.chat);
+        match &message.from {
+            Some(message::From::User(user)) => self.observe_user(user),
+            Some(message::From::Chat(chat)) => self.observe_chat(chat),
+            None => (),
+        }
+        match message.forward.as_ref().map(|forward| &forward.from) {
+            Some(forward::From::User(user)) => self.observe_user(user),
+            Some(forward::From::Channel { chat, .. }) => {
+                self.observe_chat(chat);
+            }
+            Some(forward::From::HiddenUser(..)) | None => (),
+        }
+        if let Some(via_bot) = &message.via_bot {
+            self.observe_user(via_bot);
+        }
+        if let Some(reply_to) = &message.reply_to {
+            self.observe_message(reply_to);
+        }
+
+        match &message.kind {
+            message::Kind::Text(text)
+            | message::Kind::Audio { caption: text, .. }
+            | message::Kind::Document { caption: text, .. }
+            | message::Kind::Photo { caption: text, .. }
+            | message::Kind::Video { caption: text, .. }
+            | message::Kind::Voice { caption: text, .. }
+            | message::Kind::Animation { caption: text, .. } => {
+                self.observe_text(text);
+            }
+            message::Kind::NewChatMembers(users) => {
+                for user in users {
+                    self.observe_user(user);
+                }
+            }
+            message::Kind::VoiceChatParticipantsInvited(invited) => {
+                for user in &invited.users {
+                    self.observe_user(user);
+                }
+            }
+            message::Kind::LeftChatMember(user) => self.observe_user(user),
+            message::Kind::ProximityAlert(alert) => {
+                self.observe_user(&alert.traveler);
+                self.observe_user(&alert.watcher);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Records users and chats observed in updates. See [the module's docs]
+/// to learn how to use it.
+///
+/// [the module's docs]: ./index.html
+#[derive(Default)]
+pub struct Directory(Mutex<Inner>);
+
+impl Directory {
+    /// Constructs an empty `Directory`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records all users and chats mentioned in the update. The event loop
+    /// calls this method for every update if the directory is configured
+    /// on the bot.
+    pub fn observe(&self, update: &update::Kind) {
+        let mut inner = self.lock();
+
+        match update {
+            update::Kind::Message(message)
+            | update::Kind::EditedMessage(message)
+            | update::Kind::ChannelPost(message)
+            | update::Kind::EditedChannelPost(message) => {
+                inner.observe_message(message);
+            }
+            update::Kind::CallbackQuery(query) => {
+                inner.observe_user(&query.from);
+                if let callback::Origin::Message(message) = &query.origin {
+                    inner.observe_message(message);
+                }
+            }
+            update::Kind::InlineQuery(query) => inner.observe_user(&query.from),
+            update::Kind::ChosenInlineResult(result) => {
+                inner.observe_user(&result.from);
+            }
+            update::Kind::ShippingQuery(query) => {
+                inner.observe_user(&query.from);
+            }
+            update::Kind::PreCheckoutQuery(query) => {
+                inner.observe_user(&query.from);
+            }
+            update::Kind::PollAnswer(answer) => {
+                inner.observe_user(&answer.user);
+            }
+            update::Kind::MyChatMember(updated)
+            | update::Kind::ChatMember(updated) => {
+                inner.observe_chat(&updated.chat);
+                inner.observe_user(&updated.from);
+                inner.observe_user(&updated.new_member.user);
+            }
+            update::Kind::Poll(..) | update::Kind::Unknown => (),
+        }
+    }
+
+    /// Records all users and chats mentioned in the message.
+    pub fn observe_message(&self, message: &Message) {
+        self.lock().observe_message(message);
+    }
+
+    /// Finds a user by their ID.
+    #[must_use]
+    pub fn user(&self, id: user::Id) -> Option<User> {
+        self.lock().users.get(&id).cloned()
+    }
+
+    /// Finds a chat by its ID.
+    #[must_use]
+    pub fn chat(&self, id: chat::Id) -> Option<Chat> {
+        self.lock().chats.get(&id).cloned()
+    }
+
+    /// Finds a user or a chat by the username, with or without the leading
+    /// `@`. Usernames are case-insensitive.
+    #[must_use]
+    pub fn by_username(&self, username: &str) -> Option<Found> {
+        let inner = self.lock();
+        let username =
+            normalize(username.strip_prefix('@').unwrap_or(username));
+
+        match inner.usernames.get(&username)? {
+            Owner::User(id) => inner.users.get(id).cloned().map(Found::User),
+            Owner::Chat(id) => inner.chats.get(id).cloned().map(Found::Chat),
+        }
+    }
+
+    /// Finds a user or a chat by an argument such as `@username`,
+    /// `username` or a numeric ID. Leading and trailing whitespace
+    /// is ignored.
+    #[must_use]
+    pub fn resolve(&self, argument: &str) -> Option<Found> {
+        let argument = argument.trim();
+
+        if let Ok(id) = argument.parse::<i64>() {
+            return self
+                .user(user::Id(id))
+                .map(Found::User)
+                .or_else(|| self.chat(chat::Id(id)).map(Found::Chat));
+        }
+
+        self.by_username(argument)
+    }
+
+    /// Returns the number of known users.
+    #[must_use]
+    pub fn users_count(&self) -> usize {
+        self.lock().users.len()
+    }
+
+    /// Returns the number of known chats.
+    #[must_use]
+    pub fn chats_count(&self) -> usize {
+        self.lock().chats.len()
+    }
+
+    /// Forgets a user.
+    pub fn remove_user(&self, id: user::Id) {
+        let mut inner = self.lock();
+        if let Some(user) = inner.users.remove(&id) {
+            inner.update_username(
+                user.username.as_deref(),
+                None,
+                Owner::User(id),
+            );
+        }
+    }
+
+    /// Forgets a chat.
+    pub fn remove_chat(&self, id: chat::Id) {
+        let mut inner = self.lock();
+        if let Some(chat) = inner.chats.remove(&id) {
+            inner.update_username(
+                chat.username.as_deref(),
+                None,
+                Owner::Chat(id),
+            );
+        }
+    }
+}
+
+impl Debug for Directory {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let inner = self.lock();
+        formatter
+            .debug_struct("Directory")
+            .field("users", &inner.users.len())
+            .field("chats", &inner.chats.len())
+            .finish()
+    }
+}
+
+impl Persistent for Directory {
+    fn dump(&self) -> Result<Vec<Entry>, errors::Storage> {
+        let inner = self.lock();
+        let users = inner.users.values().map(|user| {
+            Ok((format!("{}{}", USERS, user.id.0), serde_json::to_vec(user)?))
+        });
+        let chats = inner.chats.values().map(|chat| {
+            Ok((format!("{}{}", CHATS, chat.id.0), serde_json::to_vec(chat)?))
+        });
+        let entries = users.chain(chats).collect();
+        drop(inner);
+
+        entries
+    }
+
+    fn restore(&self, entries: Vec<Entry>) -> Result<(), errors::Storage> {
+        let mut restored = Inner::default();
+
+        for (key, value) in entries {
+            if key.starts_with(USERS) {
+                restored.insert_user(serde_json::from_slice(&value)?);
+            } else if key.starts_with(CHATS) {
+                restored.insert_chat(serde_json::from_slice(&value)?);
+            } else {
+                return Err(errors::Storage::InvalidKey(key));
+            }
+        }
+
+        *self.lock() = restored;
+        Ok(())
+    }
+}
+
+fn normalize(username: &str) -> String {
+    username.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(json: &str) -> Message {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn indexes_observed_users_and_chats() {
+        let directory = Directory::new();
+        directory.observe_message(&message(
+            r#"{
+                "message_id": 1,
+                "date": 0,
+                "chat": {"id": -1, "type": "supergroup", "title": "Rust",
+                         "username": "rustlang"},
+                "from": {"id": 1, "is_bot": false, "first_name": "Ferris",
+                         "username": "Ferris"},
+                "new_chat_members": [
+                    {"id": 2, "is_bot": false, "first_name": "Corro"}
+                ]
+            }"#,
+        ));
+
+        assert!(directory.user(user::Id(2)).is_some());
+        assert_eq!(directory.chats_count(), 1);
+        assert!(directory
+            .resolve("@ferris")
+            .map_or(false, |found| found.is_user()));
+        assert!(directory
+            .resolve(" rustlang ")
+            .map_or(false, |found| found.is_chat()));
+        assert!(directory
+            .resolve("-1")
+            .map_or(false, |found| found.is_chat()));
+
+        directory.observe_message(&message(
+            r#"{
+                "message_id": 2,
+                "date": 0,
+                "chat": {"id": 1, "type": "private", "first_name": "Ferris"},
+                "from": {"id": 1, "is_bot": false, "first_name": "Ferris",
+                         "username": "crab"}
+            }"#,
+        ));
+
+        assert!(directory.by_username("ferris").is_none());
+        assert!(directory.by_username("CRAB").is_some());
+        assert_eq!(directory.chats_count(), 1);
+
+        let entries = directory.dump().unwrap();
+        let restored = Directory::new();
+        restored.restore(entries).unwrap();
+        assert_eq!(restored.user(user::Id(1)), directory.user(user::Id(1)));
+        assert!(restored.by_username("rustlang").is_some());
+    }
+}
@@ -119,3 +119,51 @@ fn entities_parsing() {
         })]
     );
 }
+
+#[test]
+fn rendering() {
+    use crate::types::parameters;
+
+    // "🦀" takes two UTF-16 code units, so the offsets must count them.
+    let text = Text {
+        value: "🦀 <bold> and [link] `code`".to_string(),
+        entities: vec![
+            RawEntity {
+                kind: EntityKind::Bold,
+                offset: 3,
+                length: 6,
+            },
+            RawEntity {
+                kind: EntityKind::Italic,
+                offset: 4,
+                length: 9,
+            },
+            RawEntity {
+                kind: EntityKind::TextLink("https://example.com/(x)".into()),
+                offset: 14,
+                length: 6,
+            },
+            RawEntity {
+                kind: EntityKind::Code,
+                offset: 21,
+                length: 6,
+            },
+        ],
+    };
+
+    assert_eq!(
+        text.to_html(),
+        parameters::Text::with_html(
+            "🦀 <b>&lt;</b><i><b>bold&gt;</b></i><i> and</i> \
+             <a href=\"https://example.com/(x)\">[link]</a> \
+             <code>`code`</code>"
+        ),
+    );
+    assert_eq!(
+        text.to_markdown_v2(),
+        parameters::Text::with_markdown_v2(
+            "🦀 *<*\r_*bold\\>*\r_\r_ and\r_ \
+             [\\[link\\]](https://example.com/(x\\)) `\\`code\\``"
+        ),
+    );
+}
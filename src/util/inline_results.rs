@@ -0,0 +1,406 @@
+//! Paginated and cached answers to inline queries.
+//!
+//! Telegram shows at most 50 results at once and asks for more with
+//! `InlineQuery::offset` when the user scrolls. [`InlineResults`] fetches
+//! all results for a query from your source, caches them, and answers with
+//! one page at a time, encoding the offset of the next page for you:
+//!
+//! ```no_run
+//! use tbot::{
+//!     contexts,
+//!     types::{
+//!         inline_query::{self, result::Article},
+//!         input_message_content::Text,
+//!     },
+//!     util::inline_results::InlineResults,
+//! };
+//! use std::{sync::Arc, time::Duration};
+//!
+//! let results = InlineResults::new(|context: Arc<contexts::Inline>| async move {
+//!     (1..=200)
+//!         .map(|n| n.to_string())
+//!         .filter(|n| n.starts_with(&context.query))
+//!         .map(|n| {
+//!             let content = Text::new(n.clone());
+//!             inline_query::Result::new(n.clone(), Article::new(n, content))
+//!         })
+//!         .collect()
+//! })
+//! .ttl(Duration::from_secs(300))
+//! .debounce(Duration::from_millis(300));
+//!
+//! let mut bot = tbot::from_env!("BOT_TOKEN").event_loop();
+//! results.register(&mut bot);
+//! ```
+//!
+//! Results are cached by the query's text, and also by the user if
+//! the answers are [personal]. When the user types quickly, Telegram sends
+//! a query for every keystroke; with [`debounce`] configured, only the last
+//! query from a user is answered.
+//!
+//! [`InlineResults`]: ./struct.InlineResults.html
+//! [personal]: ./struct.InlineResults.html#method.is_personal
+//! [`debounce`]: ./struct.InlineResults.html#method.debounce
+
+use crate::{
+    contexts, errors,
+    event_loop::EventLoop,
+    state::StatefulEventLoop,
+    types::{inline_query, user},
+};
+use futures::future::BoxFuture;
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    num::NonZeroUsize,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+use tracing::error;
+
+/// Telegram doesn't accept more than 50 results per answer.
+const MAX_PAGE_SIZE: usize = 50;
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+const DEFAULT_CAPACITY: usize = 1000;
+
+type Results = Arc<[inline_query::Result<'static>]>;
+type Fetch = dyn Fn(
+        Arc<contexts::Inline>,
+    ) -> BoxFuture<'static, Vec<inline_query::Result<'static>>>
+    + Send
+    + Sync;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+struct Key {
+    user: Option<user::Id>,
+    query: String,
+}
+
+#[derive(Debug)]
+struct Entry {
+    results: Results,
+    fetched_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug)]
+struct Cache {
+    entries: HashMap<Key, Entry>,
+    tick: u64,
+}
+
+impl Cache {
+    fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+            tick: 0,
+        }
+    }
+
+    fn get(&mut self, key: &Key, ttl: Duration) -> Option<Results> {
+        self.tick += 1;
+        let tick = self.tick;
+
+        let entry = self.entries.get_mut(key)?;
+        if entry.fetched_at.elapsed() >= ttl {
+            self.entries.remove(key);
+            return None;
+        }
+
+        entry.last_used = tick;
+        Some(Arc::clone(&entry.results))
+    }
+
+    fn insert(&mut self, key: Key, results: Results, capacity: usize) {
+        self.tick += 1;
+
+        while self.entries.len() >= capacity && !self.entries.contains_key(&key)
+        {
+            let least_recent = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+
+            match least_recent {
+                Some(least_recent) => self.entries.remove(&least_recent),
+                None => break,
+            };
+        }
+
+        let entry = Entry {
+            results,
+            fetched_at: Instant::now(),
+            last_used: self.tick,
+        };
+        self.entries.insert(key, entry);
+    }
+}
+
+#[derive(Debug, Default)]
+struct Latest {
+    next_ticket: u64,
+    tickets: HashMap<user::Id, u64>,
+}
+
+/// Answers inline queries with paginated and cached results. See
+/// [the module's docs] to learn how to use it.
+///
+/// [the module's docs]: ./index.html
+#[must_use]
+pub struct InlineResults {
+    fetch: Arc<Fetch>,
+    page_size: usize,
+    ttl: Duration,
+    capacity: usize,
+    debounce: Option<Duration>,
+    is_personal: bool,
+    cache_time: Option<u64>,
+    cache: Arc<Mutex<Cache>>,
+    latest: Arc<Mutex<Latest>>,
+}
+
+impl InlineResults {
+    /// Constructs new `InlineResults` which fetch results from `source`.
+    /// The source returns all results for the query, and they're split
+    /// into pages afterwards.
+    pub fn new<F, Fut>(source: F) -> Self
+    where
+        F: Fn(Arc<contexts::Inline>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<inline_query::Result<'static>>>
+            + Send
+            + 'static,
+    {
+        Self {
+            fetch: Arc::new(move |context| Box::pin(source(context))),
+            page_size: MAX_PAGE_SIZE,
+            ttl: DEFAULT_TTL,
+            capacity: DEFAULT_CAPACITY,
+            debounce: None,
+            is_personal: false,
+            cache_time: None,
+            cache: Arc::new(Mutex::new(Cache::new())),
+            latest: Arc::new(Mutex::new(Latest::default())),
+        }
+    }
+
+    /// Configures how many results are sent at once. Telegram accepts
+    /// at most 50 results, so larger values are clamped. By default,
+    /// 50 results are sent.
+    pub fn page_size(mut self, page_size: NonZeroUsize) -> Self {
+        self.page_size = page_size.get().min(MAX_PAGE_SIZE);
+        self
+    }
+
+    /// Configures for how long fetched results are cached. By default,
+    /// results are cached for a minute.
+    pub const fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Configures how many queries' results are cached at most. When
+    /// the cache is full, the least recently used results are evicted.
+    /// By default, 1000 queries are cached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "[tbot] The cache capacity must not be zero");
+        self.capacity = capacity;
+        self
+    }
+
+    /// Configures for how long to wait for a newer query from the same user
+    /// before answering. If one arrives, the older query is left unanswered.
+    /// Requests for further pages are never delayed. By default, queries
+    /// are answered immediately.
+    pub const fn debounce(mut self, delay: Duration) -> Self {
+        self.debounce = Some(delay);
+        self
+    }
+
+    /// Configures whether results are personal. Personal results are cached
+    /// separately for each user, both by `tbot` and by Telegram.
+    /// Reflects the `is_personal` parameter.
+    pub const fn is_personal(mut self, is_personal: bool) -> Self {
+        self.is_personal = is_personal;
+        self
+    }
+
+    /// Configures for how long Telegram may cache the answer, in seconds.
+    /// Reflects the `cache_time` parameter.
+    pub const fn cache_time(mut self, time: u64) -> Self {
+        self.cache_time = Some(time);
+        self
+    }
+
+    /// Forgets all cached results.
+    pub fn clear(&self) {
+        self.lock_cache().entries.clear();
+    }
+
+    /// Answers the query with the page requested by its offset.
+    pub async fn answer(
+        &self,
+        context: Arc<contexts::Inline>,
+    ) -> Result<(), errors::MethodCall> {
+        let offset = context.offset.parse().unwrap_or(0);
+
+        if let Some(delay) = self.debounce {
+            if offset == 0 && self.is_superseded(context.from.id, delay).await {
+                return Ok(());
+            }
+        }
+
+        let key = Key {
+            user: Some(context.from.id).filter(|_| self.is_personal),
+            query: context.query.clone(),
+        };
+
+        let cached = self.lock_cache().get(&key, self.ttl);
+        let results = if let Some(results) = cached {
+            results
+        } else {
+            let results: Results =
+                (self.fetch)(Arc::clone(&context)).await.into();
+            self.lock_cache()
+                .insert(key, Arc::clone(&results), self.capacity);
+            results
+        };
+
+        let start = offset.min(results.len());
+        let end = (start + self.page_size).min(results.len());
+
+        let mut answer = context
+            .answer(results[start..end].to_vec())
+            .is_personal(self.is_personal);
+        if let Some(cache_time) = self.cache_time {
+            answer = answer.cache_time(cache_time);
+        }
+        if end < results.len() {
+            answer = answer.next_offset(end.to_string());
+        }
+
+        answer.call().await
+    }
+
+    /// Adds a handler which answers inline queries to the event loop.
+    pub fn register(&self, event_loop: &mut EventLoop) {
+        let results = self.clone();
+        event_loop.inline(move |context| {
+            let results = results.clone();
+            async move { results.handle(context).await }
+        });
+    }
+
+    /// Adds a handler which answers inline queries to the stateful
+    /// event loop.
+    pub fn register_stateful<S>(&self, event_loop: &mut StatefulEventLoop<S>)
+    where
+        S: Send + Sync + 'static,
+    {
+        let results = self.clone();
+        event_loop.inline(move |context, _| {
+            let results = results.clone();
+            async move { results.handle(context).await }
+        });
+    }
+
+    async fn handle(&self, context: Arc<contexts::Inline>) {
+        if let Err(error) = self.answer(context).await {
+            error!(?error, "failed to answer an inline query");
+        }
+    }
+
+    async fn is_superseded(&self, user: user::Id, delay: Duration) -> bool {
+        let ticket = {
+            let mut latest = self.lock_latest();
+            latest.next_ticket += 1;
+            let ticket = latest.next_ticket;
+            latest.tickets.insert(user, ticket);
+            ticket
+        };
+
+        tokio::time::sleep(delay).await;
+
+        let mut latest = self.lock_latest();
+        if latest.tickets.get(&user) == Some(&ticket) {
+            latest.tickets.remove(&user);
+            false
+        } else {
+            true
+        }
+    }
+
+    fn lock_cache(&self) -> MutexGuard<'_, Cache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_latest(&self) -> MutexGuard<'_, Latest> {
+        self.latest.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for InlineResults {
+    fn clone(&self) -> Self {
+        Self {
+            fetch: Arc::clone(&self.fetch),
+            page_size: self.page_size,
+            ttl: self.ttl,
+            capacity: self.capacity,
+            debounce: self.debounce,
+            is_personal: self.is_personal,
+            cache_time: self.cache_time,
+            cache: Arc::clone(&self.cache),
+            latest: Arc::clone(&self.latest),
+        }
+    }
+}
+
+impl Debug for InlineResults {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("InlineResults")
+            .field("page_size", &self.page_size)
+            .field("ttl", &self.ttl)
+            .field("capacity", &self.capacity)
+            .field("debounce", &self.debounce)
+            .field("is_personal", &self.is_personal)
+            .field("cache_time", &self.cache_time)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(query: &str) -> Key {
+        Key {
+            user: None,
+            query: query.to_owned(),
+        }
+    }
+
+    #[test]
+    fn evicts_least_recently_used_and_expired() {
+        let ttl = Duration::from_secs(60);
+        let mut cache = Cache::new();
+        let results: Results = Vec::new().into();
+
+        cache.insert(key("a"), Arc::clone(&results), 2);
+        cache.insert(key("b"), Arc::clone(&results), 2);
+        assert!(cache.get(&key("a"), ttl).is_some());
+
+        cache.insert(key("c"), Arc::clone(&results), 2);
+        assert!(cache.get(&key("a"), ttl).is_some());
+        assert!(cache.get(&key("b"), ttl).is_none());
+        assert!(cache.get(&key("c"), ttl).is_some());
+
+        assert!(cache.get(&key("a"), Duration::from_secs(0)).is_none());
+        assert!(!cache.entries.contains_key(&key("a")));
+    }
+}
@@ -0,0 +1,544 @@
+//! Sessions for sharing live locations.
+//!
+//! [`LiveLocation`] remembers where a live location was sent, throttles
+//! edits and stops the live location for you. Feed it a stream of positions
+//! with [`follow`], and it keeps the location up to date until the stream
+//! closes or the live period ends:
+//!
+//! ```no_run
+//! # async fn foo() -> Result<(), tbot::errors::MethodCall> {
+//! use std::time::Duration;
+//! use tbot::{
+//!     types::chat,
+//!     util::live_location::{LiveLocation, Position},
+//! };
+//!
+//! let bot = tbot::from_env!("BOT_TOKEN");
+//! let start = Position::new((59.95, 30.31)).horizontal_accuracy(10.0);
+//! let session = LiveLocation::send(
+//!     &bot,
+//!     chat::Id(0),
+//!     start,
+//!     Duration::from_secs(15 * 60),
+//! )
+//! .await?;
+//!
+//! let positions = futures::stream::iter(vec![
+//!     Position::new((59.951, 30.312)).heading(90),
+//!     Position::new((59.952, 30.314)).heading(90),
+//! ]);
+//! session.follow(positions).await?;
+//! # Ok(()) }
+//! ```
+//!
+//! For inline messages, construct a session with [`LiveLocation::inline`]
+//! from the inline message ID you get in a `chosen_inline` handler.
+//!
+//! [`LiveLocation`]: ./struct.LiveLocation.html
+//! [`follow`]: ./struct.LiveLocation.html#method.follow
+//! [`LiveLocation::inline`]: ./struct.LiveLocation.html#method.inline
+
+use crate::{
+    errors,
+    types::{chat, message, InlineMessageId, Message},
+    Bot,
+};
+use futures::{
+    future::{select, Either},
+    pin_mut, Stream, StreamExt,
+};
+use std::{convert::TryFrom, future::Future, time::Duration};
+use tokio::time::{sleep_until, Instant};
+
+/// Telegram accepts live periods from a minute to a day.
+const MIN_LIVE_PERIOD: Duration = Duration::from_secs(60);
+const MAX_LIVE_PERIOD: Duration = Duration::from_secs(24 * 60 * 60);
+const DEFAULT_MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Represents a position of a live location.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[must_use]
+pub struct Position {
+    latitude: f64,
+    longitude: f64,
+    horizontal_accuracy: Option<f64>,
+    heading: Option<u16>,
+    proximity_alert_radius: Option<u32>,
+}
+
+impl Position {
+    /// Constructs a new `Position` from latitude and longitude.
+    pub const fn new((latitude, longitude): (f64, f64)) -> Self {
+        Self {
+            latitude,
+            longitude,
+            horizontal_accuracy: None,
+            heading: None,
+            proximity_alert_radius: None,
+        }
+    }
+
+    /// Configures the radius of uncertainty for the location, in meters.
+    pub const fn horizontal_accuracy(mut self, accuracy: f64) -> Self {
+        self.horizontal_accuracy = Some(accuracy);
+        self
+    }
+
+    /// Configures the direction in which the user is moving, in degrees
+    /// and in range `1..=360`.
+    pub const fn heading(mut self, heading: u16) -> Self {
+        self.heading = Some(heading);
+        self
+    }
+
+    /// Configures the maximum distance for proximity alerts about
+    /// approaching another chat member, in meters.
+    pub const fn proximity_alert_radius(mut self, radius: u32) -> Self {
+        self.proximity_alert_radius = Some(radius);
+        self
+    }
+
+    const fn coordinates(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+}
+
+impl From<(f64, f64)> for Position {
+    fn from(coordinates: (f64, f64)) -> Self {
+        Self::new(coordinates)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Target {
+    Message {
+        chat_id: chat::Id,
+        message_id: message::Id,
+    },
+    Inline(InlineMessageId<'static>),
+}
+
+/// What `Throttle::follow` should do next.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Step {
+    /// The position should be sent.
+    Edit(Position),
+    /// The stream has closed; the latest position is yet to be sent.
+    Close(Option<Position>),
+    /// The live period has ended.
+    Expire,
+}
+
+/// Decides when the location may be edited, apart from the bot so that it
+/// can be tested without Telegram.
+#[derive(Debug, Clone, Copy)]
+struct Throttle {
+    expires_at: Instant,
+    min_interval: Duration,
+    last_edit: Option<Instant>,
+    last_position: Option<Position>,
+}
+
+impl Throttle {
+    fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    fn next_edit_at(&self) -> Instant {
+        self.last_edit.map_or_else(Instant::now, |last_edit| {
+            last_edit + self.min_interval
+        })
+    }
+
+    /// Waits for the minimum interval since the previous edit to pass,
+    /// then checks if the position should be sent.
+    async fn wait(&self, position: Position) -> bool {
+        sleep_until(self.next_edit_at().min(self.expires_at)).await;
+        !self.is_expired() && self.last_position != Some(position)
+    }
+
+    fn edited(&mut self, position: Position) {
+        self.last_edit = Some(Instant::now());
+        self.last_position = Some(position);
+    }
+
+    /// Takes positions from the stream until one should be sent, keeping
+    /// only the latest of those which arrive faster than the minimum
+    /// interval.
+    async fn next<S>(
+        &self,
+        positions: &mut S,
+        pending: &mut Option<Position>,
+    ) -> Step
+    where
+        S: Stream<Item = Position> + Unpin,
+    {
+        loop {
+            let deadline = if pending.is_some() {
+                self.next_edit_at().min(self.expires_at)
+            } else {
+                self.expires_at
+            };
+            let timer = sleep_until(deadline);
+            pin_mut!(timer);
+
+            match select(positions.next(), timer).await {
+                Either::Left((Some(position), _)) => *pending = Some(position),
+                Either::Left((None, _)) => return Step::Close(pending.take()),
+                Either::Right(((), _)) if self.is_expired() => {
+                    return Step::Expire
+                }
+                Either::Right(((), _)) => {
+                    if let Some(position) = pending.take() {
+                        return Step::Edit(position);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Edits the location with positions from the stream until the stream
+    /// closes or the live period ends. Returns `true` if the stream closed,
+    /// so the live location should be stopped.
+    async fn follow<S, E, F, T, Error>(
+        &mut self,
+        positions: S,
+        mut edit: E,
+    ) -> Result<bool, Error>
+    where
+        S: Stream<Item = Position>,
+        E: FnMut(Position) -> F,
+        F: Future<Output = Result<T, Error>>,
+    {
+        pin_mut!(positions);
+        let mut pending = None;
+
+        loop {
+            let step = self.next(&mut positions, &mut pending).await;
+            let position = match step {
+                Step::Edit(position) | Step::Close(Some(position)) => position,
+                Step::Close(None) => return Ok(true),
+                Step::Expire => return Ok(false),
+            };
+
+            if self.wait(position).await {
+                edit(position).await?;
+                self.edited(position);
+            }
+            if let Step::Close(_) = step {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+/// A live location which can be updated until it expires. See
+/// [the module's docs] to learn how to use it.
+///
+/// [the module's docs]: ./index.html
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct LiveLocation {
+    bot: Bot,
+    target: Target,
+    message: Option<Message>,
+    throttle: Throttle,
+}
+
+impl LiveLocation {
+    fn new(
+        bot: &Bot,
+        target: Target,
+        message: Option<Message>,
+        live_period: Duration,
+    ) -> Self {
+        Self {
+            bot: bot.clone(),
+            target,
+            message,
+            throttle: Throttle {
+                expires_at: Instant::now() + live_period,
+                min_interval: DEFAULT_MIN_INTERVAL,
+                last_edit: None,
+                last_position: None,
+            },
+        }
+    }
+
+    /// Sends a live location to the chat and starts a session for it.
+    /// The live period is clamped to Telegram's limits, from a minute
+    /// to a day.
+    pub async fn send(
+        bot: &Bot,
+        chat_id: impl Into<chat::Id>,
+        position: impl Into<Position>,
+        live_period: Duration,
+    ) -> Result<Self, errors::MethodCall> {
+        let position = position.into();
+        let live_period = live_period.max(MIN_LIVE_PERIOD).min(MAX_LIVE_PERIOD);
+        let seconds = u32::try_from(live_period.as_secs()).unwrap_or(u32::MAX);
+
+        let mut method = bot
+            .send_location(chat_id.into(), position.coordinates())
+            .live_period(seconds);
+        if let Some(accuracy) = position.horizontal_accuracy {
+            method = method.horizontal_accuracy(accuracy);
+        }
+        if let Some(heading) = position.heading {
+            method = method.heading(heading);
+        }
+        if let Some(radius) = position.proximity_alert_radius {
+            method = method.proximity_alert_radius(radius);
+        }
+        let message = method.call().await?;
+
+        let target = Target::Message {
+            chat_id: message.chat.id,
+            message_id: message.id,
+        };
+        let mut session = Self::new(bot, target, Some(message), live_period);
+        session.throttle.edited(position);
+
+        Ok(session)
+    }
+
+    /// Starts a session for a live location sent via the inline mode.
+    /// `live_period` is the time left until the location expires.
+    pub fn inline(
+        bot: &Bot,
+        inline_message_id: InlineMessageId<'static>,
+        live_period: Duration,
+    ) -> Self {
+        Self::new(bot, Target::Inline(inline_message_id), None, live_period)
+    }
+
+    /// Configures the minimum interval between edits. Positions which
+    /// arrive more often are skipped, except for the latest one.
+    /// By default, the location is edited at most once in three seconds.
+    pub const fn min_interval(mut self, interval: Duration) -> Self {
+        self.throttle.min_interval = interval;
+        self
+    }
+
+    /// Returns the message with the live location, if it was sent
+    /// with [`send`].
+    ///
+    /// [`send`]: #method.send
+    #[must_use]
+    pub const fn message(&self) -> Option<&Message> {
+        self.message.as_ref()
+    }
+
+    /// Checks if the live period has ended.
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.throttle.is_expired()
+    }
+
+    /// Updates the location, waiting for the minimum interval since
+    /// the previous edit to pass first. Does nothing if the position
+    /// hasn't changed or the live period has ended.
+    pub async fn update(
+        &mut self,
+        position: impl Into<Position>,
+    ) -> Result<(), errors::MethodCall> {
+        let position = position.into();
+        if !self.throttle.wait(position).await {
+            return Ok(());
+        }
+
+        if let Some(message) = edit(&self.bot, &self.target, position).await? {
+            self.message = Some(message);
+        }
+
+        self.throttle.edited(position);
+        Ok(())
+    }
+
+    /// Updates the location with positions from the stream, skipping
+    /// positions which arrive faster than the minimum interval. When
+    /// the stream closes, the latest position is sent and the live location
+    /// is stopped. When the live period ends, Telegram stops the live
+    /// location itself, and the rest of the stream is ignored.
+    pub async fn follow<S>(
+        mut self,
+        positions: S,
+    ) -> Result<(), errors::MethodCall>
+    where
+        S: Stream<Item = Position>,
+    {
+        let bot = &self.bot;
+        let target = &self.target;
+        let is_closed = self
+            .throttle
+            .follow(positions, |position| edit(bot, target, position))
+            .await?;
+
+        if is_closed {
+            self.stop().await
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Stops the live location. Does nothing if the live period has ended.
+    pub async fn stop(self) -> Result<(), errors::MethodCall> {
+        if self.is_expired() {
+            return Ok(());
+        }
+
+        match self.target {
+            Target::Message {
+                chat_id,
+                message_id,
+            } => {
+                self.bot
+                    .stop_message_location(chat_id, message_id)
+                    .call()
+                    .await?;
+            }
+            Target::Inline(id) => {
+                self.bot.stop_inline_location(id).call().await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sends a position, returning the edited message unless it's inline.
+async fn edit(
+    bot: &Bot,
+    target: &Target,
+    position: Position,
+) -> Result<Option<Message>, errors::MethodCall> {
+    match target {
+        Target::Message {
+            chat_id,
+            message_id,
+        } => {
+            let mut method = bot.edit_message_location(
+                *chat_id,
+                *message_id,
+                position.coordinates(),
+            );
+            if let Some(accuracy) = position.horizontal_accuracy {
+                method = method.horizontal_accuracy(accuracy);
+            }
+            if let Some(heading) = position.heading {
+                method = method.heading(heading);
+            }
+            if let Some(radius) = position.proximity_alert_radius {
+                method = method.proximity_alert_radius(radius);
+            }
+            method.call().await.map(Some)
+        }
+        Target::Inline(id) => {
+            let mut method =
+                bot.edit_inline_location(id.clone(), position.coordinates());
+            if let Some(accuracy) = position.horizontal_accuracy {
+                method = method.horizontal_accuracy(accuracy);
+            }
+            if let Some(heading) = position.heading {
+                method = method.heading(heading);
+            }
+            if let Some(radius) = position.proximity_alert_radius {
+                method = method.proximity_alert_radius(radius);
+            }
+            method.call().await.map(|()| None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use tokio::time::pause;
+
+    /// Emits positions with the given latitudes at the given seconds since
+    /// `start`, closing the stream when the latitude is `None`.
+    fn positions(
+        start: Instant,
+        schedule: Vec<(u64, Option<f64>)>,
+    ) -> impl Stream<Item = Position> + Unpin {
+        Box::pin(stream::unfold(
+            schedule.into_iter(),
+            move |mut schedule| async move {
+                let (at, latitude) = schedule.next()?;
+                sleep_until(start + Duration::from_secs(at)).await;
+                Some((Position::new((latitude?, 0.0)), schedule))
+            },
+        ))
+    }
+
+    /// Follows the positions, returning the edits as seconds since `start`
+    /// and latitudes, and whether the live location should be stopped.
+    async fn follow(
+        start: Instant,
+        mut throttle: Throttle,
+        schedule: Vec<(u64, Option<f64>)>,
+    ) -> (Vec<(u64, f64)>, bool) {
+        let mut edits = Vec::new();
+        let result = throttle
+            .follow(positions(start, schedule), |position: Position| {
+                let seconds = (Instant::now() - start).as_secs();
+                edits.push((seconds, position.latitude));
+                async { Ok::<_, ()>(()) }
+            })
+            .await;
+
+        (edits, result.unwrap())
+    }
+
+    fn throttle(start: Instant, live_period: u64) -> Throttle {
+        Throttle {
+            expires_at: start + Duration::from_secs(live_period),
+            min_interval: Duration::from_secs(3),
+            last_edit: Some(start),
+            last_position: Some(Position::new((0.0, 0.0))),
+        }
+    }
+
+    #[tokio::test]
+    async fn skips_positions_between_edits() {
+        pause();
+        let start = Instant::now();
+        let schedule = vec![
+            (1, Some(1.0)),
+            (2, Some(2.0)),
+            (10, Some(2.0)),
+            (20, Some(3.0)),
+            (21, None),
+        ];
+
+        let (edits, is_closed) =
+            follow(start, throttle(start, 60), schedule).await;
+        assert_eq!(edits, [(3, 2.0), (20, 3.0)]);
+        assert!(is_closed);
+    }
+
+    #[tokio::test]
+    async fn sends_the_latest_position_on_close() {
+        pause();
+        let start = Instant::now();
+        let schedule = vec![(1, Some(1.0)), (2, None)];
+
+        let (edits, is_closed) =
+            follow(start, throttle(start, 60), schedule).await;
+        assert_eq!(edits, [(3, 1.0)]);
+        assert!(is_closed);
+    }
+
+    #[tokio::test]
+    async fn stops_following_when_expired() {
+        pause();
+        let start = Instant::now();
+        let schedule = vec![(1, Some(1.0)), (4, Some(2.0)), (10, Some(3.0))];
+
+        let (edits, is_closed) =
+            follow(start, throttle(start, 5), schedule).await;
+        assert_eq!(edits, [(3, 1.0)]);
+        assert!(!is_closed);
+    }
+}
@@ -0,0 +1,356 @@
+//! Splitting and sending texts which exceed Telegram's length limits.
+//!
+//! Telegram rejects messages longer than 4096 UTF-16 code units.
+//! [`split`] parses the markup like Telegram would, then splits the text at
+//! paragraph, line or word boundaries, avoiding cuts inside entities. If an
+//! entity is longer than the limit itself, its formatting is re-opened in
+//! the next part.
+//!
+//! [`Bot::send_long_message`] and the contexts' `send_long_message` methods
+//! send all parts in order, each in reply to the previous one:
+//!
+//! ```no_run
+//! # async fn foo() -> Result<(), tbot::errors::SendLongMessage> {
+//! use tbot::{markup::{bold, html}, types::chat};
+//!
+//! let bot = tbot::from_env!("BOT_TOKEN");
+//! let report = "A very long line. ".repeat(1000);
+//! let text = html(("Report: ", bold(report.as_str())));
+//!
+//! let messages = bot.send_long_message(chat::Id(0), text).call().await?;
+//! assert!(messages.len() > 1);
+//! # Ok(()) }
+//! ```
+//!
+//! [`split`]: ./fn.split.html
+//! [`Bot::send_long_message`]: ../../struct.Bot.html#method.send_long_message
+
+use crate::{
+    bot::InnerBot,
+    errors,
+    methods::SendMessage,
+    types::{
+        keyboard,
+        message::{self, text::Entity, Message},
+        parameters::{ChatId, ImplicitChatId, Text},
+    },
+};
+use std::cmp::Ordering;
+
+/// The maximum length of a text message, in UTF-16 code units.
+pub const MESSAGE_LIMIT: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+struct Character {
+    /// The offset in UTF-16 code units.
+    offset: usize,
+    /// The offset in bytes.
+    index: usize,
+    value: char,
+}
+
+/// Splits the text into parts which are at most `limit` UTF-16 code units
+/// long. Parts with formatting are rendered as HTML.
+///
+/// # Panics
+///
+/// Panics if `limit` is zero.
+pub fn split(
+    text: &Text<'_>,
+    limit: usize,
+) -> Result<Vec<Text<'static>>, errors::ParseEntities> {
+    assert!(limit > 0, "[tbot] The length limit must not be zero");
+
+    let parts = split_parsed(&text.parse()?, limit)
+        .into_iter()
+        .map(|part| {
+            if part.entities.is_empty() {
+                Text::with_plain(part.value)
+            } else {
+                part.to_html()
+            }
+        })
+        .collect();
+
+    Ok(parts)
+}
+
+fn split_parsed(text: &message::Text, limit: usize) -> Vec<message::Text> {
+    let mut length = 0;
+    let characters: Vec<_> = text
+        .value
+        .char_indices()
+        .map(|(index, value)| {
+            let character = Character {
+                offset: length,
+                index,
+                value,
+            };
+            length += value.len_utf16();
+            character
+        })
+        .collect();
+
+    let offset = |index: usize| {
+        characters
+            .get(index)
+            .map_or(length, |character| character.offset)
+    };
+    let byte = |index: usize| {
+        characters
+            .get(index)
+            .map_or(text.value.len(), |character| character.index)
+    };
+
+    let mut parts = Vec::new();
+    let mut start = 0;
+
+    loop {
+        while characters
+            .get(start)
+            .map_or(false, |character| character.value.is_whitespace())
+        {
+            start += 1;
+        }
+        if start == characters.len() {
+            break parts;
+        }
+
+        let start_offset = offset(start);
+        let end = if length - start_offset <= limit {
+            characters.len()
+        } else {
+            let max_end = characters
+                .binary_search_by(|character| {
+                    if character.offset <= start_offset + limit {
+                        Ordering::Less
+                    } else {
+                        Ordering::Greater
+                    }
+                })
+                .unwrap_or_else(|index| index)
+                .saturating_sub(1)
+                .max(start + 1);
+
+            (start + 1..=max_end)
+                .max_by_key(|&end| {
+                    let is_outside_entities =
+                        !text.entities.iter().any(|entity| {
+                            entity.offset < offset(end)
+                                && offset(end) < entity.offset + entity.length
+                        });
+                    let boundary = boundary(&characters[start..end]);
+                    (is_outside_entities, boundary)
+                })
+                .unwrap_or(max_end)
+        };
+
+        let value = text.value[byte(start)..byte(end)].trim_end();
+        let end_offset =
+            start_offset + value.chars().map(char::len_utf16).sum::<usize>();
+        let entities = text
+            .entities
+            .iter()
+            .filter_map(|entity| {
+                let entity_start = entity.offset.max(start_offset);
+                let entity_end =
+                    (entity.offset + entity.length).min(end_offset);
+
+                if entity_start < entity_end {
+                    Some(Entity {
+                        kind: entity.kind.clone(),
+                        offset: entity_start - start_offset,
+                        length: entity_end - entity_start,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        parts.push(message::Text {
+            value: value.to_owned(),
+            entities,
+        });
+        start = end;
+    }
+}
+
+/// Rates how natural it is to end a part with these characters: paragraph
+/// boundaries are preferred over line boundaries, which are preferred over
+/// word boundaries.
+fn boundary(part: &[Character]) -> u8 {
+    match part {
+        [.., a, b] if a.value == '\n' && b.value == '\n' => 3,
+        [.., last] if last.value == '\n' => 2,
+        [.., last] if last.value.is_whitespace() => 1,
+        _ => 0,
+    }
+}
+
+/// Sends a text of any length as several messages.
+///
+/// The text is split with [`split`], and the parts are sent in order,
+/// each in reply to the previous one. The keyboard is attached to the last
+/// part.
+///
+/// [`split`]: ./fn.split.html
+#[derive(Debug, Clone)]
+#[must_use = "methods do nothing unless turned into a future"]
+pub struct SendLongMessage<'a> {
+    bot: &'a InnerBot,
+    chat_id: ChatId<'a>,
+    text: Text<'a>,
+    is_web_page_preview_disabled: Option<bool>,
+    is_notification_disabled: Option<bool>,
+    reply_to_message_id: Option<message::Id>,
+    reply_markup: Option<keyboard::Any<'a>>,
+}
+
+impl<'a> SendLongMessage<'a> {
+    pub(crate) fn new(
+        bot: &'a InnerBot,
+        chat_id: impl ImplicitChatId<'a>,
+        text: impl Into<Text<'a>>,
+    ) -> Self {
+        Self {
+            bot,
+            chat_id: chat_id.into(),
+            text: text.into(),
+            is_web_page_preview_disabled: None,
+            is_notification_disabled: None,
+            reply_to_message_id: None,
+            reply_markup: None,
+        }
+    }
+
+    /// Configures if previews for the first links in the messages should be
+    /// shown. Reflects the `disable_web_page_preview` parameter.
+    pub const fn is_web_page_preview_disabled(
+        mut self,
+        is_disabled: bool,
+    ) -> Self {
+        self.is_web_page_preview_disabled = Some(is_disabled);
+        self
+    }
+
+    /// Configures if the messages will be sent silently.
+    /// Reflects the `disable_notification` parameter.
+    pub const fn is_notification_disabled(mut self, is_disabled: bool) -> Self {
+        self.is_notification_disabled = Some(is_disabled);
+        self
+    }
+
+    /// Configures which message the first part is sent in reply to.
+    /// Reflects the `reply_to_message_id` parameter.
+    pub const fn in_reply_to(mut self, id: message::Id) -> Self {
+        self.reply_to_message_id = Some(id);
+        self
+    }
+
+    /// Configures a keyboard for the last part.
+    /// Reflects the `reply_markup` parameter.
+    pub fn reply_markup(
+        mut self,
+        markup: impl Into<keyboard::Any<'a>>,
+    ) -> Self {
+        self.reply_markup = Some(markup.into());
+        self
+    }
+}
+
+impl SendLongMessage<'_> {
+    /// Sends the parts, returning all sent messages.
+    pub async fn call(self) -> Result<Vec<Message>, errors::SendLongMessage> {
+        let mut parts = split(&self.text, MESSAGE_LIMIT)?;
+        if parts.is_empty() {
+            // Let Telegram report that the text is empty.
+            parts.push(Text::with_plain(""));
+        }
+
+        let last = parts.len() - 1;
+        let mut messages: Vec<Message> = Vec::with_capacity(parts.len());
+
+        for (index, part) in parts.into_iter().enumerate() {
+            let mut method = SendMessage::new(self.bot, &self.chat_id, part);
+
+            let in_reply_to = messages
+                .last()
+                .map(|message| message.id)
+                .or(self.reply_to_message_id);
+            if let Some(id) = in_reply_to {
+                method = method.in_reply_to(id);
+            }
+            if let Some(is_disabled) = self.is_web_page_preview_disabled {
+                method = method.is_web_page_preview_disabled(is_disabled);
+            }
+            if let Some(is_disabled) = self.is_notification_disabled {
+                method = method.is_notification_disabled(is_disabled);
+            }
+            if index == last {
+                if let Some(markup) = &self.reply_markup {
+                    method = method.reply_markup(markup.clone());
+                }
+            }
+
+            match method.call().await {
+                Ok(message) => messages.push(message),
+                Err(error) => {
+                    return Err(errors::SendLongMessage::MethodCall {
+                        error,
+                        sent: messages,
+                    })
+                }
+            }
+        }
+
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use message::text::EntityKind;
+
+    #[test]
+    fn splits_at_boundaries_and_reopens_entities() {
+        let text = message::Text {
+            value: "aaaa bbbb\n\ncccc dddd eeee ffff".to_owned(),
+            entities: vec![
+                Entity {
+                    kind: EntityKind::Bold,
+                    offset: 0,
+                    length: 9,
+                },
+                Entity {
+                    kind: EntityKind::Italic,
+                    offset: 11,
+                    length: 19,
+                },
+            ],
+        };
+
+        let parts = split_parsed(&text, 12);
+        let values: Vec<_> =
+            parts.iter().map(|part| part.value.as_str()).collect();
+        assert_eq!(values, ["aaaa bbbb", "cccc dddd", "eeee ffff"]);
+
+        for part in &parts {
+            assert_eq!(part.entities.len(), 1);
+            assert_eq!(part.entities[0].offset, 0);
+            assert_eq!(part.entities[0].length, 9);
+        }
+        assert_eq!(parts[2].entities[0].kind, EntityKind::Italic);
+
+        let emoji = message::Text {
+            value: "🦀🦀🦀".to_owned(),
+            entities: Vec::new(),
+        };
+        let values: Vec<_> = split_parsed(&emoji, 5)
+            .into_iter()
+            .map(|part| part.value)
+            .collect();
+        assert_eq!(values, ["🦀🦀", "🦀"]);
+    }
+}
@@ -0,0 +1,247 @@
+//! Building blocks for group management bots.
+//!
+//! This module implements features most group management bots share
+//! on top of `restrictChatMember` and `kickChatMember`:
+//!
+//! - [`mute`], [`ban`] and [`kick`] members, optionally for a duration
+//!   parsed with [`parse_duration`] from strings like `10m` or `2d`;
+//! - count warnings with [`Warns`], escalating to an [`Action`] when
+//!   a member reaches a threshold;
+//! - punish members who send too many messages with [`AntiFlood`];
+//! - ask new members to prove they're not bots with [`Captcha`];
+//! - delete join and leave service messages with
+//!   [`ServiceMessageCleaner`].
+//!
+//! ```no_run
+//! # async fn foo() {
+//! use std::{sync::Arc, time::Duration};
+//! use tbot::{
+//!     contexts::{fields::Message, Command},
+//!     prelude::*,
+//!     types::{message::From, user},
+//!     util::moderation::{
+//!         self, Action, AntiFlood, Captcha, ServiceMessageCleaner, Warns,
+//!     },
+//! };
+//!
+//! fn replied_user(context: &Command) -> Option<user::Id> {
+//!     match context.reply_to.as_ref()?.from.as_ref()? {
+//!         From::User(user) => Some(user.id),
+//!         From::Chat(..) => None,
+//!     }
+//! }
+//!
+//! let mut bot = tbot::from_env!("BOT_TOKEN").event_loop();
+//!
+//! AntiFlood::new(5, Duration::from_secs(10)).register(&mut bot);
+//! Captcha::new().register(&mut bot);
+//! ServiceMessageCleaner::new().register(&mut bot);
+//!
+//! let warns = Arc::new(
+//!     Warns::new()
+//!         .escalate(3, Action::Mute(Some(Duration::from_secs(60 * 60))))
+//!         .escalate(5, Action::Ban(None)),
+//! );
+//!
+//! bot.command("warn", move |context| {
+//!     let warns = Arc::clone(&warns);
+//!     async move {
+//!         let user = match replied_user(&context) {
+//!             Some(user) => user,
+//!             None => return,
+//!         };
+//!         let chat = context.chat().id;
+//!         let warned = warns.warn(&context.bot, chat, user).await.unwrap();
+//!         let reply = format!("Warnings: {}", warned.count);
+//!         context.send_message_in_reply(&reply).call().await.unwrap();
+//!     }
+//! });
+//!
+//! bot.command("mute", |context| async move {
+//!     let user = match replied_user(&context) {
+//!         Some(user) => user,
+//!         None => return,
+//!     };
+//!     // `/mute 10m` mutes for 10 minutes, `/mute` mutes forever
+//!     let duration = moderation::parse_duration(&context.text.value).ok();
+//!     moderation::mute(&context.bot, context.chat().id, user, duration)
+//!         .await
+//!         .unwrap();
+//! });
+//! # }
+//! ```
+//!
+//! Telegram treats restrictions shorter than 30 seconds or longer than
+//! 366 days as permanent. Durations are clamped to the range from a minute
+//! to 366 days so that a timed restriction never becomes a permanent one,
+//! even if the bot's clock is a bit off.
+//!
+//! [`mute`]: ./fn.mute.html
+//! [`ban`]: ./fn.ban.html
+//! [`kick`]: ./fn.kick.html
+//! [`parse_duration`]: ./fn.parse_duration.html
+//! [`Warns`]: ./struct.Warns.html
+//! [`Action`]: ./enum.Action.html
+//! [`AntiFlood`]: ./struct.AntiFlood.html
+//! [`Captcha`]: ./struct.Captcha.html
+//! [`ServiceMessageCleaner`]: ./struct.ServiceMessageCleaner.html
+
+use crate::{
+    errors,
+    types::{chat, user},
+    Bot,
+};
+use std::{
+    convert::TryFrom,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+mod captcha;
+mod duration;
+mod flood;
+mod service_messages;
+mod warns;
+
+pub use {
+    captcha::Captcha,
+    duration::{format_duration, parse_duration},
+    flood::AntiFlood,
+    service_messages::ServiceMessageCleaner,
+    warns::{Warned, Warns},
+};
+
+const MIN_RESTRICTION: Duration = Duration::from_secs(60);
+const MAX_RESTRICTION: Duration = Duration::from_secs(366 * 24 * 60 * 60);
+
+/// Represents a punishment for a member.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Action {
+    /// Mutes the member for the duration, or forever if it's `None`.
+    Mute(Option<Duration>),
+    /// Bans the member for the duration, or forever if it's `None`.
+    Ban(Option<Duration>),
+    /// Removes the member from the chat, letting them join again.
+    Kick,
+}
+
+impl Action {
+    /// Applies the action to a member of the chat.
+    pub async fn apply(
+        self,
+        bot: &Bot,
+        chat_id: chat::Id,
+        user_id: user::Id,
+    ) -> Result<(), errors::MethodCall> {
+        match self {
+            Self::Mute(duration) => mute(bot, chat_id, user_id, duration).await,
+            Self::Ban(duration) => ban(bot, chat_id, user_id, duration).await,
+            Self::Kick => kick(bot, chat_id, user_id).await,
+        }
+    }
+}
+
+/// Forbids a member to send any messages for the duration, or forever
+/// if it's `None`.
+pub async fn mute(
+    bot: &Bot,
+    chat_id: chat::Id,
+    user_id: user::Id,
+    duration: Option<Duration>,
+) -> Result<(), errors::MethodCall> {
+    let permissions = chat::Permissions::new()
+        .can_send_messages(false)
+        .can_send_media_messages(false)
+        .can_send_polls(false)
+        .can_send_other_messages(false)
+        .can_add_web_page_previews(false);
+
+    let mut method = bot.restrict_chat_member(chat_id, user_id, permissions);
+    if let Some(duration) = duration {
+        method = method.until_date(until_date(duration));
+    }
+
+    method.call().await
+}
+
+/// Lifts all restrictions from a member, letting a muted member send
+/// messages again.
+pub async fn unmute(
+    bot: &Bot,
+    chat_id: chat::Id,
+    user_id: user::Id,
+) -> Result<(), errors::MethodCall> {
+    bot.restrict_chat_member(chat_id, user_id, unrestricted())
+        .call()
+        .await
+}
+
+/// Telegram lifts restrictions only if all permissions are granted, and
+/// treats omitted ones as revoked.
+fn unrestricted() -> chat::Permissions {
+    chat::Permissions::new()
+        .can_send_messages(true)
+        .can_send_media_messages(true)
+        .can_send_polls(true)
+        .can_send_other_messages(true)
+        .can_add_web_page_previews(true)
+        .can_change_info(true)
+        .can_invite_users(true)
+        .can_pin_messages(true)
+}
+
+/// Bans a member for the duration, or forever if it's `None`.
+pub async fn ban(
+    bot: &Bot,
+    chat_id: chat::Id,
+    user_id: user::Id,
+    duration: Option<Duration>,
+) -> Result<(), errors::MethodCall> {
+    let mut method = bot.kick_chat_member(chat_id, user_id);
+    if let Some(duration) = duration {
+        method = method.until_date(until_date(duration));
+    }
+
+    method.call().await
+}
+
+/// Removes a member from the chat without banning them.
+pub async fn kick(
+    bot: &Bot,
+    chat_id: chat::Id,
+    user_id: user::Id,
+) -> Result<(), errors::MethodCall> {
+    bot.kick_chat_member(chat_id, user_id).call().await?;
+    bot.unban_chat_member(chat_id, user_id).call().await
+}
+
+/// Converts a duration into a timestamp suitable for `until_date`.
+fn until_date(duration: Duration) -> i64 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    now.checked_add(duration.max(MIN_RESTRICTION).min(MAX_RESTRICTION))
+        .and_then(|until| i64::try_from(until.as_secs()).ok())
+        .unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unmutes_with_all_permissions() {
+        let permissions = serde_json::to_value(unrestricted()).unwrap();
+        let expected = serde_json::json!({
+            "can_send_messages": true,
+            "can_send_media_messages": true,
+            "can_send_polls": true,
+            "can_send_other_messages": true,
+            "can_add_web_page_previews": true,
+            "can_change_info": true,
+            "can_invite_users": true,
+            "can_pin_messages": true,
+        });
+        assert_eq!(permissions, expected);
+    }
+}
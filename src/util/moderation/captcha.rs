@@ -0,0 +1,359 @@
+use super::Action;
+use crate::{
+    contexts::{
+        self,
+        fields::Message,
+        methods::{Callback, Message as _},
+    },
+    event_loop::EventLoop,
+    state::StatefulEventLoop,
+    types::{
+        chat,
+        keyboard::inline::{self, Button, ButtonKind},
+        message,
+        parameters::Text,
+        user, User,
+    },
+    util::callback_data::{CallbackData, Codec},
+    Bot,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+use tokio::time::sleep;
+use tracing::error;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+/// New members stay muted for this long after the timeout, so that they
+/// can't speak before the action is applied.
+const MUTE_MARGIN: Duration = Duration::from_secs(60);
+
+/// The callback data of the captcha's button.
+#[derive(Serialize, Deserialize)]
+struct Challenge(i64);
+
+impl CallbackData for Challenge {
+    const TAG: &'static str = "tbot-captcha";
+}
+
+type RenderText = dyn Fn(&User) -> Text<'static> + Send + Sync;
+type Pending = HashMap<(chat::Id, user::Id), message::Id>;
+
+/// Asks new members of groups to prove they're not bots.
+///
+/// When someone joins a group, they're muted for a minute longer than
+/// the timeout, and the bot sends a message with a button only they may
+/// press. Pressing the button in time lifts the restriction, otherwise
+/// the configured [`Action`] is applied to them. Either way, the message
+/// with the button is deleted afterwards. Bots added to the group aren't
+/// challenged.
+///
+/// [`Action`]: ./enum.Action.html
+#[derive(Clone)]
+#[must_use]
+pub struct Captcha {
+    timeout: Duration,
+    text: Arc<RenderText>,
+    button: Cow<'static, str>,
+    action: Action,
+    codec: Codec,
+    pending: Arc<Mutex<Pending>>,
+}
+
+impl Captcha {
+    /// Constructs a captcha which gives new members two minutes to press
+    /// the button and kicks them if they don't.
+    pub fn new() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            text: Arc::new(|user: &User| {
+                format!(
+                    "{}, please press the button below to show you're \
+                     not a bot.",
+                    user.first_name,
+                )
+                .into()
+            }),
+            button: Cow::Borrowed("I'm not a bot"),
+            action: Action::Kick,
+            codec: Codec::new(),
+            pending: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Configures how much time new members have to press the button.
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Configures a function which renders the challenge's text for
+    /// a new member.
+    pub fn text<F, R>(mut self, text: F) -> Self
+    where
+        F: Fn(&User) -> R + Send + Sync + 'static,
+        R: Into<Text<'static>>,
+    {
+        self.text = Arc::new(move |user| text(user).into());
+        self
+    }
+
+    /// Configures the text of the button.
+    pub fn button(mut self, text: impl Into<Cow<'static, str>>) -> Self {
+        self.button = text.into();
+        self
+    }
+
+    /// Configures the action applied to new members who don't press
+    /// the button in time.
+    pub const fn action(mut self, action: Action) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Configures the codec for the button's callback data.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Pending> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn challenge(&self, context: Arc<contexts::NewMembers>) {
+        let chat = &context.chat;
+        if !(chat.kind.is_group() || chat.kind.is_supergroup()) {
+            return;
+        }
+
+        for member in context.members.iter().filter(|member| !member.is_bot) {
+            self.challenge_member(&context.bot, chat.id, member).await;
+        }
+    }
+
+    async fn challenge_member(
+        &self,
+        bot: &Bot,
+        chat_id: chat::Id,
+        user: &User,
+    ) {
+        let duration = self.timeout + MUTE_MARGIN;
+        if let Err(error) =
+            super::mute(bot, chat_id, user.id, Some(duration)).await
+        {
+            error!(?error, "failed to mute a new member");
+            return;
+        }
+
+        let data = match self.codec.encode(&Challenge(user.id.0)) {
+            Ok(data) => data,
+            Err(error) => {
+                error!(?error, "failed to encode a captcha button");
+                return;
+            }
+        };
+        let button = Button::new(
+            self.button.as_ref(),
+            ButtonKind::with_callback_data(data),
+        );
+        let keyboard = inline::Builder::new().button(button).build();
+
+        let message = bot
+            .send_message(chat_id, (self.text)(user))
+            .reply_markup(&keyboard)
+            .call()
+            .await;
+        let message = match message {
+            Ok(message) => message,
+            Err(error) => {
+                error!(?error, "failed to send a captcha");
+                return;
+            }
+        };
+
+        self.lock().insert((chat_id, user.id), message.id);
+
+        let captcha = self.clone();
+        let bot = bot.clone();
+        let user_id = user.id;
+        tokio::spawn(async move {
+            sleep(captcha.timeout).await;
+            captcha.expire(&bot, chat_id, user_id, message.id).await;
+        });
+    }
+
+    /// Removes a pending challenge unless the member has already left and
+    /// rejoined, in which case the challenge belongs to a newer timer.
+    fn remove_challenge(
+        &self,
+        key: (chat::Id, user::Id),
+        message_id: message::Id,
+    ) -> bool {
+        let mut pending = self.lock();
+        if pending.get(&key) == Some(&message_id) {
+            pending.remove(&key);
+            true
+        } else {
+            false
+        }
+    }
+
+    async fn expire(
+        &self,
+        bot: &Bot,
+        chat_id: chat::Id,
+        user_id: user::Id,
+        message_id: message::Id,
+    ) {
+        if !self.remove_challenge((chat_id, user_id), message_id) {
+            return;
+        }
+
+        if let Err(error) = self.action.apply(bot, chat_id, user_id).await {
+            error!(?error, "failed to punish a member who failed a captcha");
+        }
+
+        if let Err(error) = bot.delete_message(chat_id, message_id).call().await
+        {
+            error!(?error, "failed to delete a captcha");
+        }
+    }
+
+    async fn forget(&self, context: Arc<contexts::LeftMember>) {
+        let key = (context.chat.id, context.member.id);
+        let message_id = self.lock().remove(&key);
+        let message_id = match message_id {
+            Some(message_id) => message_id,
+            None => return,
+        };
+
+        if let Err(error) = context.delete_message(message_id).call().await {
+            error!(?error, "failed to delete a captcha");
+        }
+    }
+
+    async fn solve(&self, context: Arc<contexts::MessageDataCallback>) {
+        let challenge = match self.codec.decode::<Challenge>(&context.data) {
+            Ok(challenge) => challenge,
+            Err(_) => return,
+        };
+
+        if context.from.id.0 != challenge.0 {
+            let result = context.notify("This button isn't for you.").call();
+            if let Err(error) = result.await {
+                error!(?error, "failed to answer a captcha callback");
+            }
+            return;
+        }
+
+        let chat_id = context.chat().id;
+        let user_id = context.from.id;
+        if self.lock().remove(&(chat_id, user_id)).is_some() {
+            let bot = &context.bot;
+            if let Err(error) = super::unmute(bot, chat_id, user_id).await {
+                error!(
+                    ?error,
+                    "failed to unmute a member who passed a captcha"
+                );
+            }
+
+            if let Err(error) = context.delete_this_message().call().await {
+                error!(?error, "failed to delete a captcha");
+            }
+        }
+
+        if let Err(error) = context.ignore().call().await {
+            error!(?error, "failed to answer a captcha callback");
+        }
+    }
+
+    /// Adds handlers for new members, left members and the captcha's
+    /// button to the event loop.
+    pub fn register(&self, event_loop: &mut EventLoop) {
+        let captcha = self.clone();
+        event_loop.new_members(move |context| {
+            let captcha = captcha.clone();
+            async move { captcha.challenge(context).await }
+        });
+
+        let captcha = self.clone();
+        event_loop.left_member(move |context| {
+            let captcha = captcha.clone();
+            async move { captcha.forget(context).await }
+        });
+
+        let captcha = self.clone();
+        event_loop.message_data_callback(move |context| {
+            let captcha = captcha.clone();
+            async move { captcha.solve(context).await }
+        });
+    }
+
+    /// Adds handlers for new members, left members and the captcha's
+    /// button to the stateful event loop.
+    pub fn register_stateful<S>(&self, event_loop: &mut StatefulEventLoop<S>)
+    where
+        S: Send + Sync + 'static,
+    {
+        let captcha = self.clone();
+        event_loop.new_members(move |context, _| {
+            let captcha = captcha.clone();
+            async move { captcha.challenge(context).await }
+        });
+
+        let captcha = self.clone();
+        event_loop.left_member(move |context, _| {
+            let captcha = captcha.clone();
+            async move { captcha.forget(context).await }
+        });
+
+        let captcha = self.clone();
+        event_loop.message_data_callback(move |context, _| {
+            let captcha = captcha.clone();
+            async move { captcha.solve(context).await }
+        });
+    }
+}
+
+impl Default for Captcha {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Captcha {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Captcha")
+            .field("timeout", &self.timeout)
+            .field("button", &self.button)
+            .field("action", &self.action)
+            .field("codec", &self.codec)
+            .field("pending", &self.lock().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_challenges_of_rejoined_members() {
+        let captcha = Captcha::new();
+        let key = (chat::Id(-1), user::Id(1));
+        captcha.lock().insert(key, message::Id(2));
+
+        assert!(!captcha.remove_challenge(key, message::Id(1)));
+        assert_eq!(captcha.lock().get(&key), Some(&message::Id(2)));
+
+        assert!(captcha.remove_challenge(key, message::Id(2)));
+        assert!(captcha.lock().is_empty());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::errors;
+use std::time::Duration;
+
+const UNITS: [(&str, u64); 5] = [
+    ("w", 7 * 24 * 60 * 60),
+    ("d", 24 * 60 * 60),
+    ("h", 60 * 60),
+    ("m", 60),
+    ("s", 1),
+];
+
+/// Parses a human-readable duration such as `30s`, `10m`, `2d` or `1h30m`.
+///
+/// A duration consists of numbers followed by units: `s` for seconds, `m`
+/// for minutes, `h` for hours, `d` for days and `w` for weeks. Units are
+/// case-insensitive, and parts may be separated with whitespace.
+pub fn parse_duration(input: &str) -> Result<Duration, errors::ParseDuration> {
+    let mut rest = input.trim();
+    if rest.is_empty() {
+        return Err(errors::ParseDuration::Empty);
+    }
+
+    let mut seconds: u64 = 0;
+    while !rest.is_empty() {
+        let number_length = rest
+            .find(|character: char| !character.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if number_length == 0 {
+            return Err(errors::ParseDuration::MissingNumber);
+        }
+
+        let (number, unit) = rest.split_at(number_length);
+        let number: u64 = number
+            .parse()
+            .map_err(|_| errors::ParseDuration::Overflow)?;
+
+        let unit_length = unit
+            .find(|character: char| !character.is_alphabetic())
+            .unwrap_or(unit.len());
+        if unit_length == 0 {
+            return Err(errors::ParseDuration::MissingUnit);
+        }
+
+        let (unit, tail) = unit.split_at(unit_length);
+        let &(_, multiplier) = UNITS
+            .iter()
+            .find(|(name, _)| unit.eq_ignore_ascii_case(name))
+            .ok_or_else(|| {
+                errors::ParseDuration::UnknownUnit(unit.to_owned())
+            })?;
+
+        seconds = number
+            .checked_mul(multiplier)
+            .and_then(|part| seconds.checked_add(part))
+            .ok_or(errors::ParseDuration::Overflow)?;
+        rest = tail.trim_start();
+    }
+
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Formats a duration in the format [`parse_duration`] accepts, e.g. `1h30m`.
+/// Fractions of a second are dropped.
+///
+/// [`parse_duration`]: ./fn.parse_duration.html
+#[must_use]
+pub fn format_duration(duration: Duration) -> String {
+    let mut seconds = duration.as_secs();
+    if seconds == 0 {
+        return "0s".to_owned();
+    }
+
+    let mut formatted = String::new();
+    for &(unit, length) in &UNITS {
+        if seconds >= length {
+            formatted.push_str(&(seconds / length).to_string());
+            formatted.push_str(unit);
+            seconds %= length;
+        }
+    }
+
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_durations() {
+        assert_eq!(parse_duration("10m"), Ok(Duration::from_secs(600)));
+        assert_eq!(
+            parse_duration(" 1h 30M "),
+            Ok(Duration::from_secs(90 * 60)),
+        );
+        assert_eq!(parse_duration("2d"), Ok(Duration::from_secs(172_800)));
+
+        assert_eq!(parse_duration(""), Err(errors::ParseDuration::Empty));
+        assert_eq!(
+            parse_duration("m"),
+            Err(errors::ParseDuration::MissingNumber)
+        );
+        assert_eq!(
+            parse_duration("10"),
+            Err(errors::ParseDuration::MissingUnit)
+        );
+        assert_eq!(
+            parse_duration("5y"),
+            Err(errors::ParseDuration::UnknownUnit("y".to_owned())),
+        );
+        assert_eq!(
+            parse_duration("99999999999999999999w"),
+            Err(errors::ParseDuration::Overflow),
+        );
+
+        assert_eq!(format_duration(Duration::from_secs(90 * 60)), "1h30m");
+        assert_eq!(
+            format_duration(Duration::from_secs(8 * 86_400 + 1)),
+            "1w1d1s"
+        );
+        assert_eq!(format_duration(Duration::from_millis(500)), "0s");
+    }
+}
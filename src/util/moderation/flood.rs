@@ -0,0 +1,182 @@
+use super::Action;
+use crate::{
+    contexts::{fields::Message, methods::Message as _},
+    event_loop::EventLoop,
+    predicates::message::RateLimiter,
+    state::StatefulEventLoop,
+    types::{chat, message::From, user},
+    util::admins,
+};
+use futures::future::BoxFuture;
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tracing::error;
+
+const DEFAULT_ACTION: Action = Action::Mute(Some(Duration::from_secs(10 * 60)));
+
+/// Punishes members who send too many messages in a short time.
+///
+/// A member floods if they send more than `messages` messages in `per`.
+/// The message which exceeds the limit is deleted, and the configured
+/// [`Action`] is applied to its sender. Admins and messages sent on behalf
+/// of chats are never punished.
+///
+/// [`Action`]: ./enum.Action.html
+#[derive(Clone)]
+#[must_use]
+pub struct AntiFlood {
+    limiter: Arc<RateLimiter<(chat::Id, user::Id)>>,
+    action: Action,
+    delete_messages: bool,
+}
+
+impl AntiFlood {
+    /// Constructs an `AntiFlood` which allows at most `messages` messages
+    /// from a member in `per`. By default, flooders are muted
+    /// for 10 minutes.
+    pub fn new(messages: usize, per: Duration) -> Self {
+        Self {
+            limiter: Arc::new(RateLimiter::new(messages, per)),
+            action: DEFAULT_ACTION,
+            delete_messages: true,
+        }
+    }
+
+    /// Configures the action applied to flooders.
+    pub const fn action(mut self, action: Action) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Configures if the message exceeding the limit is deleted.
+    /// Enabled by default.
+    pub const fn delete_messages(mut self, is_enabled: bool) -> Self {
+        self.delete_messages = is_enabled;
+        self
+    }
+
+    /// Counts the message and punishes its sender if they flood. Returns
+    /// `true` if the message exceeded the limit.
+    ///
+    /// Call this method yourself for messages which don't reach
+    /// the handlers added by [`register`], e.g. commands.
+    ///
+    /// [`register`]: #method.register
+    pub async fn check<C>(&self, context: &C) -> bool
+    where
+        C: Message + Sync,
+    {
+        let chat = context.chat();
+        let user = match context.from() {
+            Some(From::User(user)) => user,
+            _ => return false,
+        };
+
+        if !(chat.kind.is_group() || chat.kind.is_supergroup())
+            || self.limiter.try_acquire((chat.id, user.id), Instant::now())
+        {
+            return false;
+        }
+
+        let bot = context.bot();
+        match admins::is_sender_admin(bot, chat, context.from()).await {
+            Ok(false) => (),
+            Ok(true) => return true,
+            Err(error) => {
+                error!(?error, "failed to check if a flooder is an admin");
+                return true;
+            }
+        }
+
+        if self.delete_messages {
+            if let Err(error) = context.delete_this_message().call().await {
+                error!(?error, "failed to delete a flood message");
+            }
+        }
+
+        if let Err(error) = self.action.apply(bot, chat.id, user.id).await {
+            error!(?error, "failed to punish a flooder");
+        }
+
+        true
+    }
+
+    fn handler<C>(&self) -> impl Fn(Arc<C>) -> BoxFuture<'static, ()>
+    where
+        C: Message + Send + Sync + 'static,
+    {
+        let flood = self.clone();
+        move |context| {
+            let flood = flood.clone();
+            Box::pin(async move {
+                flood.check(&*context).await;
+            })
+        }
+    }
+
+    fn stateful_handler<C, S>(
+        &self,
+    ) -> impl Fn(Arc<C>, Arc<S>) -> BoxFuture<'static, ()>
+    where
+        C: Message + Send + Sync + 'static,
+    {
+        let handler = self.handler();
+        move |context, _| handler(context)
+    }
+
+    /// Adds handlers to the event loop which check all non-service
+    /// messages except for commands.
+    pub fn register(&self, event_loop: &mut EventLoop) {
+        event_loop.animation(self.handler());
+        event_loop.audio(self.handler());
+        event_loop.contact(self.handler());
+        event_loop.dice(self.handler());
+        event_loop.document(self.handler());
+        event_loop.game(self.handler());
+        event_loop.location(self.handler());
+        event_loop.photo(self.handler());
+        event_loop.poll(self.handler());
+        event_loop.sticker(self.handler());
+        event_loop.text(self.handler());
+        event_loop.venue(self.handler());
+        event_loop.video(self.handler());
+        event_loop.video_note(self.handler());
+        event_loop.voice(self.handler());
+    }
+
+    /// Adds handlers to the stateful event loop which check all non-service
+    /// messages except for commands.
+    pub fn register_stateful<S>(&self, event_loop: &mut StatefulEventLoop<S>)
+    where
+        S: Send + Sync + 'static,
+    {
+        event_loop.animation(self.stateful_handler());
+        event_loop.audio(self.stateful_handler());
+        event_loop.contact(self.stateful_handler());
+        event_loop.dice(self.stateful_handler());
+        event_loop.document(self.stateful_handler());
+        event_loop.game(self.stateful_handler());
+        event_loop.location(self.stateful_handler());
+        event_loop.photo(self.stateful_handler());
+        event_loop.poll(self.stateful_handler());
+        event_loop.sticker(self.stateful_handler());
+        event_loop.text(self.stateful_handler());
+        event_loop.venue(self.stateful_handler());
+        event_loop.video(self.stateful_handler());
+        event_loop.video_note(self.stateful_handler());
+        event_loop.voice(self.stateful_handler());
+    }
+}
+
+impl Debug for AntiFlood {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("AntiFlood")
+            .field("action", &self.action)
+            .field("delete_messages", &self.delete_messages)
+            .finish()
+    }
+}
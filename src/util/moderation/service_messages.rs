@@ -0,0 +1,75 @@
+use crate::{
+    contexts::{fields::Message, methods::Message as _},
+    event_loop::EventLoop,
+    state::StatefulEventLoop,
+};
+use std::sync::Arc;
+use tracing::error;
+
+/// Deletes service messages about members joining and leaving groups.
+///
+/// Both kinds of messages are deleted by default.
+#[derive(Debug, Clone, Copy)]
+#[must_use]
+pub struct ServiceMessageCleaner {
+    joins: bool,
+    leaves: bool,
+}
+
+async fn delete<C: Message + Sync>(context: Arc<C>) {
+    if let Err(error) = context.delete_this_message().call().await {
+        error!(?error, "failed to delete a service message");
+    }
+}
+
+impl ServiceMessageCleaner {
+    /// Constructs a cleaner which deletes both join and leave messages.
+    pub const fn new() -> Self {
+        Self {
+            joins: true,
+            leaves: true,
+        }
+    }
+
+    /// Configures if messages about new members are deleted.
+    pub const fn joins(mut self, is_enabled: bool) -> Self {
+        self.joins = is_enabled;
+        self
+    }
+
+    /// Configures if messages about left members are deleted.
+    pub const fn leaves(mut self, is_enabled: bool) -> Self {
+        self.leaves = is_enabled;
+        self
+    }
+
+    /// Adds handlers which delete the service messages to the event loop.
+    pub fn register(self, event_loop: &mut EventLoop) {
+        if self.joins {
+            event_loop.new_members(delete);
+        }
+        if self.leaves {
+            event_loop.left_member(delete);
+        }
+    }
+
+    /// Adds handlers which delete the service messages to the stateful
+    /// event loop.
+    pub fn register_stateful<S>(self, event_loop: &mut StatefulEventLoop<S>)
+    where
+        S: Send + Sync + 'static,
+    {
+        if self.joins {
+            event_loop.new_members(|context, _| delete(context));
+        }
+        if self.leaves {
+            event_loop.left_member(|context, _| delete(context));
+        }
+    }
+}
+
+impl Default for ServiceMessageCleaner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,218 @@
+use super::Action;
+use crate::{
+    errors,
+    state::storage::{Entry, Persistent},
+    types::{chat, user},
+    Bot,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+type Counts = HashMap<(chat::Id, user::Id), u32>;
+
+/// The outcome of warning a member.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub struct Warned {
+    /// How many warnings the member has received, including this one.
+    pub count: u32,
+    /// The action applied to the member if they reached a threshold.
+    pub action: Option<Action>,
+}
+
+/// Counts warnings of chat members and punishes them when they reach
+/// configured thresholds.
+///
+/// Once a member reaches the highest threshold, their counter starts over.
+/// Counters live in memory, but `Warns` implements [`Persistent`], so they
+/// can be saved to a [`Storage`].
+///
+/// [`Persistent`]: ../../state/storage/trait.Persistent.html
+/// [`Storage`]: ../../state/storage/trait.Storage.html
+#[must_use]
+pub struct Warns {
+    escalations: Vec<(u32, Action)>,
+    counts: Mutex<Counts>,
+}
+
+impl Warns {
+    /// Constructs `Warns` without any escalations.
+    pub fn new() -> Self {
+        Self {
+            escalations: Vec::new(),
+            counts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configures the action applied to a member when they receive `count`
+    /// warnings. Configuring the same `count` again replaces the action.
+    pub fn escalate(mut self, count: u32, action: Action) -> Self {
+        self.escalations
+            .retain(|&(threshold, _)| threshold != count);
+        self.escalations.push((count, action));
+        self.escalations.sort_by_key(|&(threshold, _)| threshold);
+        self
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Counts> {
+        self.counts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn record(&self, chat_id: chat::Id, user_id: user::Id) -> Warned {
+        let mut counts = self.lock();
+        let count = counts.entry((chat_id, user_id)).or_insert(0);
+        *count = count.saturating_add(1);
+
+        let warned = Warned {
+            count: *count,
+            action: self
+                .escalations
+                .iter()
+                .find(|&&(threshold, _)| threshold == *count)
+                .map(|&(_, action)| action),
+        };
+
+        let highest = self.escalations.last().map(|&(threshold, _)| threshold);
+        if highest.map_or(false, |highest| *count >= highest) {
+            counts.remove(&(chat_id, user_id));
+        }
+        drop(counts);
+
+        warned
+    }
+
+    /// Warns a member, applying the action of the threshold they reach.
+    /// The warning is counted even if applying the action fails.
+    pub async fn warn(
+        &self,
+        bot: &Bot,
+        chat_id: chat::Id,
+        user_id: user::Id,
+    ) -> Result<Warned, errors::MethodCall> {
+        let warned = self.record(chat_id, user_id);
+        if let Some(action) = warned.action {
+            action.apply(bot, chat_id, user_id).await?;
+        }
+
+        Ok(warned)
+    }
+
+    /// Returns how many warnings the member has.
+    #[must_use]
+    pub fn count(&self, chat_id: chat::Id, user_id: user::Id) -> u32 {
+        self.lock().get(&(chat_id, user_id)).copied().unwrap_or(0)
+    }
+
+    /// Takes back one warning from the member, returning how many
+    /// warnings they have left.
+    pub fn unwarn(&self, chat_id: chat::Id, user_id: user::Id) -> u32 {
+        let mut counts = self.lock();
+        let count = match counts.get_mut(&(chat_id, user_id)) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                *count
+            }
+            _ => {
+                counts.remove(&(chat_id, user_id));
+                0
+            }
+        };
+        drop(counts);
+
+        count
+    }
+
+    /// Takes back all warnings from the member.
+    pub fn reset(&self, chat_id: chat::Id, user_id: user::Id) {
+        self.lock().remove(&(chat_id, user_id));
+    }
+}
+
+impl Default for Warns {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for Warns {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Warns")
+            .field("escalations", &self.escalations)
+            .field("counts", &self.lock().len())
+            .finish()
+    }
+}
+
+impl Persistent for Warns {
+    fn dump(&self) -> Result<Vec<Entry>, errors::Storage> {
+        let counts = self.lock();
+        let entries = counts
+            .iter()
+            .map(|(&(chat_id, user_id), count)| {
+                let key = format!("{}/{}", chat_id.0, user_id.0);
+                Ok((key, serde_json::to_vec(count)?))
+            })
+            .collect();
+        drop(counts);
+
+        entries
+    }
+
+    fn restore(&self, entries: Vec<Entry>) -> Result<(), errors::Storage> {
+        let mut counts = Counts::new();
+
+        for (key, value) in entries {
+            let mut ids = key.splitn(2, '/');
+            let ids =
+                ids.next().zip(ids.next()).and_then(|(chat_id, user_id)| {
+                    Some((chat_id.parse().ok()?, user_id.parse().ok()?))
+                });
+            let (chat_id, user_id) = match ids {
+                Some(ids) => ids,
+                None => return Err(errors::Storage::InvalidKey(key)),
+            };
+
+            counts.insert(
+                (chat::Id(chat_id), user::Id(user_id)),
+                serde_json::from_slice(&value)?,
+            );
+        }
+
+        *self.lock() = counts;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn escalates_and_starts_over() {
+        let mute = Action::Mute(Some(Duration::from_secs(60)));
+        let warns = Warns::new()
+            .escalate(3, Action::Ban(None))
+            .escalate(2, mute);
+        let (chat_id, user_id) = (chat::Id(-1), user::Id(1));
+
+        assert_eq!(warns.record(chat_id, user_id).action, None);
+        assert_eq!(warns.record(chat_id, user_id).action, Some(mute));
+        assert_eq!(warns.unwarn(chat_id, user_id), 1);
+        assert_eq!(warns.record(chat_id, user_id).count, 2);
+
+        let warned = warns.record(chat_id, user_id);
+        assert_eq!(warned.count, 3);
+        assert_eq!(warned.action, Some(Action::Ban(None)));
+        assert_eq!(warns.count(chat_id, user_id), 0);
+
+        warns.record(chat_id, user_id);
+        let restored = Warns::new();
+        restored.restore(warns.dump().unwrap()).unwrap();
+        assert_eq!(restored.count(chat_id, user_id), 1);
+    }
+}
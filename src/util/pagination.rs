@@ -0,0 +1,513 @@
+//! A paginated inline keyboard.
+//!
+//! Bots that list items usually show a few items per page and
+//! a "‹ 3/10 ›" row to switch pages. [`Pagination`] implements this:
+//! it renders a page of items into an inline keyboard, handles presses
+//! on the navigation buttons and edits the message in place.
+//!
+//! ```no_run
+//! use tbot::{
+//!     prelude::*,
+//!     types::keyboard::inline::{Button, ButtonKind},
+//!     util::pagination::Pagination,
+//! };
+//!
+//! let fruits: Vec<String> = ["apple", "banana", "cherry", "durian"]
+//!     .iter()
+//!     .map(|fruit| fruit.to_string())
+//!     .collect();
+//!
+//! let pagination = Pagination::new("fruits", fruits, |fruit: &String| {
+//!     let data = ButtonKind::with_callback_data(format!("fruit {}", fruit));
+//!     Button::new(fruit.clone(), data)
+//! })
+//! .page_size(std::num::NonZeroUsize::new(2).unwrap());
+//!
+//! let mut bot = tbot::from_env!("BOT_TOKEN").event_loop();
+//! pagination.register(&mut bot);
+//!
+//! bot.command("fruits", move |context| {
+//!     let pagination = pagination.clone();
+//!     async move {
+//!         let page = pagination.render(0).await;
+//!         let call_result = context
+//!             .send_message("Pick a fruit:")
+//!             .reply_markup(&page.keyboard)
+//!             .call()
+//!             .await;
+//!
+//!         if let Err(err) = call_result {
+//!             dbg!(err);
+//!         }
+//!     }
+//! });
+//! ```
+//!
+//! Navigation buttons carry callback data encoded with a [`Codec`]. If you
+//! configure a signed codec for the pagination, users can't jump to pages
+//! by forging callback data.
+//!
+//! [`Pagination`]: ./struct.Pagination.html
+//! [`Codec`]: ../callback_data/struct.Codec.html
+
+use super::callback_data::{CallbackData, Codec};
+use crate::{
+    contexts::{
+        self,
+        fields::{Context, Message},
+        methods::Callback,
+    },
+    event_loop::EventLoop,
+    state::StatefulEventLoop,
+    types::{
+        keyboard::inline::{self, Button, ButtonKind},
+        parameters::Text,
+    },
+};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    num::NonZeroUsize,
+    sync::Arc,
+};
+use tracing::error;
+
+const DEFAULT_PAGE_SIZE: usize = 10;
+
+/// A page of items returned by a [`Source`].
+///
+/// [`Source`]: ./trait.Source.html
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Page<T> {
+    /// Items on the page.
+    pub items: Vec<T>,
+    /// How many items there are in total.
+    pub total: usize,
+}
+
+/// A source of items for a [`Pagination`].
+///
+/// `tbot` implements it for `Vec`s, and you can construct a source from
+/// a function with [`from_fn`] or [`from_async_fn`].
+///
+/// [`Pagination`]: ./struct.Pagination.html
+/// [`from_fn`]: ./fn.from_fn.html
+/// [`from_async_fn`]: ./fn.from_async_fn.html
+pub trait Source: Send + Sync + 'static {
+    /// The type of items.
+    type Item;
+
+    /// Fetches at most `limit` items starting from `offset`.
+    fn fetch(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> BoxFuture<'_, Page<Self::Item>>;
+}
+
+impl<T> Source for Vec<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type Item = T;
+
+    fn fetch(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> BoxFuture<'_, Page<Self::Item>> {
+        let items = self.iter().skip(offset).take(limit).cloned().collect();
+        let total = self.len();
+
+        Box::pin(async move { Page { items, total } })
+    }
+}
+
+/// A source constructed from a synchronous function.
+pub struct FromFn<F>(F);
+
+/// Constructs a source from a function which receives an offset and a limit.
+pub fn from_fn<F, T>(fetch: F) -> FromFn<F>
+where
+    F: Fn(usize, usize) -> Page<T> + Send + Sync + 'static,
+{
+    FromFn(fetch)
+}
+
+impl<F, T> Source for FromFn<F>
+where
+    F: Fn(usize, usize) -> Page<T> + Send + Sync + 'static,
+    T: Send + 'static,
+{
+    type Item = T;
+
+    fn fetch(&self, offset: usize, limit: usize) -> BoxFuture<'_, Page<T>> {
+        let page = (self.0)(offset, limit);
+        Box::pin(async move { page })
+    }
+}
+
+/// A source constructed from an asynchronous function.
+pub struct FromAsyncFn<F>(F);
+
+/// Constructs a source from an asynchronous function which receives
+/// an offset and a limit, e.g. one querying a database.
+pub fn from_async_fn<F, Fut, T>(fetch: F) -> FromAsyncFn<F>
+where
+    F: Fn(usize, usize) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Page<T>> + Send + 'static,
+{
+    FromAsyncFn(fetch)
+}
+
+impl<F, Fut, T> Source for FromAsyncFn<F>
+where
+    F: Fn(usize, usize) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Page<T>> + Send + 'static,
+{
+    type Item = T;
+
+    fn fetch(&self, offset: usize, limit: usize) -> BoxFuture<'_, Page<T>> {
+        Box::pin((self.0)(offset, limit))
+    }
+}
+
+/// The callback data of navigation buttons.
+#[derive(Serialize, Deserialize)]
+struct Navigation(String, Option<usize>);
+
+impl CallbackData for Navigation {
+    const TAG: &'static str = "tbot-page";
+}
+
+type RenderButton<T> = dyn Fn(&T) -> Button<'static> + Send + Sync;
+type RenderText<T> = dyn Fn(&[T], usize, usize) -> Text<'static> + Send + Sync;
+
+/// A rendered page.
+#[derive(Debug, Clone)]
+pub struct Rendered {
+    /// The index of the page, starting from zero. It may differ from
+    /// the requested one if there are fewer pages.
+    pub page: usize,
+    /// How many pages there are.
+    pub pages: usize,
+    /// The message's text, if a [text renderer] is configured.
+    ///
+    /// [text renderer]: ./struct.Pagination.html#method.text
+    pub text: Option<Text<'static>>,
+    /// The keyboard with the page's items and navigation buttons.
+    pub keyboard: inline::OwnedKeyboard<'static>,
+}
+
+/// A paginated inline keyboard. See [the module's docs] to learn how
+/// to use it.
+///
+/// [the module's docs]: ./index.html
+#[must_use]
+pub struct Pagination<T> {
+    id: Cow<'static, str>,
+    source: Arc<dyn Source<Item = T>>,
+    button: Arc<RenderButton<T>>,
+    text: Option<Arc<RenderText<T>>>,
+    page_size: usize,
+    columns: Option<NonZeroUsize>,
+    codec: Codec,
+}
+
+impl<T: 'static> Pagination<T> {
+    /// Constructs a new pagination.
+    ///
+    /// `id` distinguishes this pagination's buttons from other paginations'
+    /// buttons, so it must be unique within the bot. `button` renders
+    /// an item into a button.
+    pub fn new<S, B>(
+        id: impl Into<Cow<'static, str>>,
+        source: S,
+        button: B,
+    ) -> Self
+    where
+        S: Source<Item = T>,
+        B: Fn(&T) -> Button<'static> + Send + Sync + 'static,
+    {
+        Self {
+            id: id.into(),
+            source: Arc::new(source),
+            button: Arc::new(button),
+            text: None,
+            page_size: DEFAULT_PAGE_SIZE,
+            columns: None,
+            codec: Codec::new(),
+        }
+    }
+
+    /// Configures how many items are shown on a page. By default, 10 items
+    /// are shown.
+    pub const fn page_size(mut self, page_size: NonZeroUsize) -> Self {
+        self.page_size = page_size.get();
+        self
+    }
+
+    /// Configures how many item buttons fit in a row. By default, each item
+    /// is on its own row.
+    pub const fn columns(mut self, columns: NonZeroUsize) -> Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    /// Configures a function which renders the message's text for a page.
+    /// It receives the page's items, the page's index and the number
+    /// of pages. If configured, the message's text is edited along with
+    /// the keyboard when the user switches pages.
+    pub fn text<F, R>(mut self, text: F) -> Self
+    where
+        F: Fn(&[T], usize, usize) -> R + Send + Sync + 'static,
+        R: Into<Text<'static>>,
+    {
+        self.text = Some(Arc::new(move |items, page, pages| {
+            text(items, page, pages).into()
+        }));
+        self
+    }
+
+    /// Configures the codec for navigation buttons' callback data.
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Renders a page.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the navigation buttons' callback data can't be encoded,
+    /// which may only happen if `id` is too long.
+    pub async fn render(&self, page: usize) -> Rendered {
+        let page_size = self.page_size;
+        // The page comes from callback data, so it may be forged to overflow
+        // the offset. Such pages are past the end and clamped below anyway.
+        let offset = page.checked_mul(page_size).unwrap_or(0);
+        let mut fetched = self.source.fetch(offset, page_size).await;
+        let pages = ((fetched.total + page_size - 1) / page_size).max(1);
+
+        let page = if page >= pages {
+            let last_page = pages - 1;
+            fetched = self.source.fetch(last_page * page_size, page_size).await;
+            last_page
+        } else {
+            page
+        };
+
+        let mut keyboard = inline::Builder::new();
+        if let Some(columns) = self.columns {
+            keyboard = keyboard.columns(columns);
+        }
+        keyboard = keyboard.buttons(fetched.items.iter().map(&*self.button));
+
+        if pages > 1 {
+            // On the first and last pages, the outer buttons do nothing
+            // instead of re-rendering the current page.
+            let previous = page.checked_sub(1);
+            let next = Some(page + 1).filter(|&next| next < pages);
+
+            keyboard =
+                keyboard
+                    .row()
+                    .button(self.navigation_button("‹", previous))
+                    .button(self.navigation_button(
+                        format!("{}/{}", page + 1, pages),
+                        None,
+                    ))
+                    .button(self.navigation_button("›", next));
+        }
+
+        let text = self
+            .text
+            .as_ref()
+            .map(|text| text(&fetched.items, page, pages));
+
+        Rendered {
+            page,
+            pages,
+            text,
+            keyboard: keyboard.build(),
+        }
+    }
+
+    fn navigation_button(
+        &self,
+        text: impl Into<Cow<'static, str>>,
+        page: Option<usize>,
+    ) -> Button<'static> {
+        let navigation = Navigation(self.id.to_string(), page);
+        let data = self
+            .codec
+            .encode(&navigation)
+            .expect("[tbot] Failed to encode a pagination button");
+
+        Button::new(text, ButtonKind::with_callback_data(data))
+    }
+
+    async fn handle(&self, context: Arc<contexts::MessageDataCallback>) {
+        let navigation = match self.codec.decode::<Navigation>(&context.data) {
+            Ok(navigation) if navigation.0 == self.id => navigation,
+            _ => return,
+        };
+
+        if let Some(page) = navigation.1 {
+            let rendered = self.render(page).await;
+            let bot = context.bot();
+            let chat_id = context.chat().id;
+            let message_id = context.message_id();
+
+            let result = match rendered.text {
+                Some(text) => bot
+                    .edit_message_text(chat_id, message_id, text)
+                    .reply_markup(rendered.keyboard.as_keyboard())
+                    .call()
+                    .await
+                    .map(drop),
+                None => bot
+                    .edit_message_reply_markup(
+                        chat_id,
+                        message_id,
+                        rendered.keyboard.as_keyboard(),
+                    )
+                    .call()
+                    .await
+                    .map(drop),
+            };
+
+            // If the items changed so that the requested page is clamped
+            // to the shown one, the message stays intact, which Telegram
+            // reports as an error, so it is not worth logging.
+            if let Err(error) = result {
+                if !is_not_modified(&error) {
+                    error!(?error, "failed to switch a page");
+                }
+            }
+        }
+
+        if let Err(error) = context.ignore().call().await {
+            error!(?error, "failed to answer a pagination callback");
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Pagination<T> {
+    /// Adds a handler for the navigation buttons to the event loop.
+    pub fn register(&self, event_loop: &mut EventLoop) {
+        let pagination = self.clone();
+        event_loop.message_data_callback(move |context| {
+            let pagination = pagination.clone();
+            async move { pagination.handle(context).await }
+        });
+    }
+
+    /// Adds a handler for the navigation buttons to the stateful event loop.
+    pub fn register_stateful<S>(&self, event_loop: &mut StatefulEventLoop<S>)
+    where
+        S: Send + Sync + 'static,
+    {
+        let pagination = self.clone();
+        event_loop.message_data_callback(move |context, _| {
+            let pagination = pagination.clone();
+            async move { pagination.handle(context).await }
+        });
+    }
+}
+
+fn is_not_modified(error: &crate::errors::MethodCall) -> bool {
+    matches!(
+        error,
+        crate::errors::MethodCall::RequestError { description, .. }
+            if description.contains("message is not modified")
+    )
+}
+
+impl<T> Clone for Pagination<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            source: Arc::clone(&self.source),
+            button: Arc::clone(&self.button),
+            text: self.text.clone(),
+            page_size: self.page_size,
+            columns: self.columns,
+            codec: self.codec.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Pagination<T> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Pagination")
+            .field("id", &self.id)
+            .field("page_size", &self.page_size)
+            .field("columns", &self.columns)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn clamps_pages_and_adds_navigation() {
+        let pagination = Pagination::new(
+            "numbers",
+            (1..=5).collect::<Vec<u32>>(),
+            |n: &u32| {
+                Button::new(n.to_string(), ButtonKind::with_callback_data("n"))
+            },
+        )
+        .page_size(NonZeroUsize::new(2).unwrap())
+        .text(|items: &[u32], page, pages| {
+            format!("{:?} {}/{}", items, page + 1, pages)
+        });
+
+        let rendered = pagination.render(10).await;
+        assert_eq!(rendered.page, 2);
+        assert_eq!(rendered.pages, 3);
+
+        let keyboard =
+            serde_json::to_value(rendered.keyboard.as_keyboard()).unwrap();
+        let rows = keyboard["inline_keyboard"].as_array().unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][0]["text"], "5");
+        assert_eq!(rows[1][1]["text"], "3/3");
+
+        let next = rows[1][2]["callback_data"].as_str().unwrap();
+        let navigation: Navigation = pagination.codec.decode(next).unwrap();
+        assert_eq!(navigation.1, None);
+
+        let previous = rows[1][0]["callback_data"].as_str().unwrap();
+        let navigation: Navigation = pagination.codec.decode(previous).unwrap();
+        assert_eq!(navigation.1, Some(1));
+    }
+
+    #[tokio::test]
+    async fn clamps_forged_pages() {
+        let pagination = Pagination::new(
+            "numbers",
+            (1..=5).collect::<Vec<u32>>(),
+            |n: &u32| {
+                Button::new(n.to_string(), ButtonKind::with_callback_data("n"))
+            },
+        )
+        .page_size(NonZeroUsize::new(2).unwrap());
+
+        let rendered = pagination.render(usize::MAX).await;
+        assert_eq!(rendered.page, 2);
+        assert_eq!(rendered.pages, 3);
+
+        let keyboard =
+            serde_json::to_value(rendered.keyboard.as_keyboard()).unwrap();
+        assert_eq!(keyboard["inline_keyboard"][0][0]["text"], "5");
+    }
+}
@@ -0,0 +1,34 @@
+//! Utilities for Telegram Passport.
+//!
+//! Passport data arrives encrypted. With the `passport` feature enabled,
+//! [`Decryptor`] decrypts it with the bot's private key and returns typed
+//! elements, such as [`PersonalDetails`] or [`ResidentialAddress`].
+//!
+//! To ask the user for their data, build a link to the authorization form
+//! with [`authorization::Request`].
+//!
+//! [`Decryptor`]: ./struct.Decryptor.html
+//! [`PersonalDetails`]: ./struct.PersonalDetails.html
+//! [`ResidentialAddress`]: ./struct.ResidentialAddress.html
+//! [`authorization::Request`]: ./authorization/struct.Request.html
+
+pub mod authorization;
+
+#[cfg(feature = "passport")]
+mod credentials;
+#[cfg(feature = "passport")]
+mod data;
+#[cfg(feature = "passport")]
+mod decryption;
+#[cfg(feature = "passport")]
+mod element;
+
+#[cfg(feature = "passport")]
+pub use {
+    credentials::{
+        Credentials, DataCredentials, FileCredentials, SecureData, SecureValue,
+    },
+    data::{IdDocumentData, PersonalDetails, ResidentialAddress},
+    decryption::{Decrypted, Decryptor},
+    element::{Documents, Element, ElementError, File, IdDocument, Value},
+};
@@ -0,0 +1,365 @@
+//! Requesting data with Telegram Passport.
+//!
+//! A Passport flow starts when the user opens a link to the Passport
+//! authorization form. [`Request`] builds such a link, which you can put
+//! into an inline URL button:
+//!
+//! ```
+//! use tbot::{
+//!     types::{
+//!         keyboard::inline::{Button, ButtonKind, OwnedKeyboard},
+//!         user,
+//!     },
+//!     util::passport::authorization::{Element, Kind, OneOf, Request, Scope},
+//! };
+//!
+//! const PUBLIC_KEY: &str = "-----BEGIN PUBLIC KEY-----\n...";
+//!
+//! let scope = Scope::new()
+//!     .element(Kind::PersonalDetails)
+//!     .element(
+//!         OneOf::new(vec![Kind::Passport, Kind::IdentityCard]).selfie(true),
+//!     )
+//!     .element(Element::new(Kind::Address).translation(true));
+//!
+//! let request =
+//!     Request::new(user::Id(123_456), scope, PUBLIC_KEY, "unique nonce");
+//! let url = request.url();
+//! let keyboard = OwnedKeyboard::new(vec![vec![Button::new(
+//!     "Share documents",
+//!     ButtonKind::with_url(url),
+//! )]]);
+//! ```
+//!
+//! Once the user shares their data, the bot receives a `passport` update.
+//! The nonce comes back in the decrypted credentials, and the bot must check
+//! that it is the one the bot generated for this user, e.g. with
+//! [`Credentials::verify_nonce`] if the `passport` feature is enabled.
+//!
+//! [`Request`]: ./struct.Request.html
+//! [`Credentials::verify_nonce`]: ../struct.Credentials.html#method.verify_nonce
+
+use crate::types::user;
+use serde::Serialize;
+use std::{borrow::Cow, fmt::Write};
+
+const SCOPE_VERSION: u8 = 1;
+
+/// Represents kinds of elements that a bot can request.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+#[must_use]
+pub enum Kind {
+    /// The user's personal details.
+    PersonalDetails,
+    /// The user's passport.
+    Passport,
+    /// The user's driver license.
+    DriverLicense,
+    /// The user's identity card.
+    IdentityCard,
+    /// The user's internal passport.
+    InternalPassport,
+    /// The user's address.
+    Address,
+    /// The user's utility bill.
+    UtilityBill,
+    /// The user's bank statement.
+    BankStatement,
+    /// The user's rental agreement.
+    RentalAgreement,
+    /// The user's passport registration.
+    PassportRegistration,
+    /// The user's temporary registration.
+    TemporaryRegistration,
+    /// The user's phone number.
+    PhoneNumber,
+    /// The user's email.
+    Email,
+}
+
+/// Represents a [`PassportScopeElementOne`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#passportscopeelementone
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize)]
+#[must_use]
+pub struct Element {
+    #[serde(rename = "type")]
+    kind: Kind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selfie: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    native_names: Option<bool>,
+}
+
+/// Represents a [`PassportScopeElementOneOfSeveral`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#passportscopeelementoneofseveral
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
+#[must_use]
+pub struct OneOf {
+    #[serde(rename = "one_of")]
+    elements: Vec<Element>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selfie: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translation: Option<bool>,
+}
+
+/// Represents a [`PassportScopeElement`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#passportscopeelement
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
+#[serde(untagged)]
+#[must_use]
+pub enum ScopeElement {
+    /// A single required element.
+    One(Element),
+    /// A choice of elements.
+    OneOf(OneOf),
+}
+
+/// Represents a [`PassportScope`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#passportscope
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize)]
+#[must_use]
+pub struct Scope {
+    data: Vec<ScopeElement>,
+    v: u8,
+}
+
+/// Builds a link to the Passport authorization form.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[must_use]
+pub struct Request<'a> {
+    bot_id: user::Id,
+    scope: Scope,
+    public_key: Cow<'a, str>,
+    nonce: Cow<'a, str>,
+    callback_url: Option<Cow<'a, str>>,
+}
+
+impl Element {
+    /// Constructs a new scope `Element`.
+    pub const fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            selfie: None,
+            translation: None,
+            native_names: None,
+        }
+    }
+
+    /// Configures whether a selfie with the document is required.
+    pub const fn selfie(mut self, is_required: bool) -> Self {
+        self.selfie = Some(is_required);
+        self
+    }
+
+    /// Configures whether a translation of the document is required.
+    pub const fn translation(mut self, is_required: bool) -> Self {
+        self.translation = Some(is_required);
+        self
+    }
+
+    /// Configures whether the user's name in the language of their country
+    /// of residence is required. Only applies to personal details.
+    pub const fn native_names(mut self, is_required: bool) -> Self {
+        self.native_names = Some(is_required);
+        self
+    }
+}
+
+impl From<Kind> for Element {
+    fn from(kind: Kind) -> Self {
+        Self::new(kind)
+    }
+}
+
+impl OneOf {
+    /// Constructs a new `OneOf` which requires any one of `elements`.
+    pub fn new<I>(elements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<Element>,
+    {
+        Self {
+            elements: elements.into_iter().map(Into::into).collect(),
+            selfie: None,
+            translation: None,
+        }
+    }
+
+    /// Configures whether a selfie with the chosen document is required.
+    pub const fn selfie(mut self, is_required: bool) -> Self {
+        self.selfie = Some(is_required);
+        self
+    }
+
+    /// Configures whether a translation of the chosen document is required.
+    pub const fn translation(mut self, is_required: bool) -> Self {
+        self.translation = Some(is_required);
+        self
+    }
+}
+
+impl Scope {
+    /// Constructs an empty `Scope`.
+    pub const fn new() -> Self {
+        Self {
+            data: Vec::new(),
+            v: SCOPE_VERSION,
+        }
+    }
+
+    /// Adds an element to the scope. Pass a [`Kind`] or an [`Element`]
+    /// to request one element, or a [`OneOf`] to let the user choose.
+    ///
+    /// [`Kind`]: ./enum.Kind.html
+    /// [`Element`]: ./struct.Element.html
+    /// [`OneOf`]: ./struct.OneOf.html
+    pub fn element(mut self, element: impl Into<ScopeElement>) -> Self {
+        self.data.push(element.into());
+        self
+    }
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Kind> for ScopeElement {
+    fn from(kind: Kind) -> Self {
+        Self::One(kind.into())
+    }
+}
+
+impl From<Element> for ScopeElement {
+    fn from(element: Element) -> Self {
+        Self::One(element)
+    }
+}
+
+impl From<OneOf> for ScopeElement {
+    fn from(one_of: OneOf) -> Self {
+        Self::OneOf(one_of)
+    }
+}
+
+impl<'a> Request<'a> {
+    /// Constructs a new `Request`.
+    ///
+    /// `public_key` is the bot's public key in the PEM format, and `nonce`
+    /// is a unique string which the bot checks when it receives the data.
+    pub fn new(
+        bot_id: impl Into<user::Id>,
+        scope: Scope,
+        public_key: impl Into<Cow<'a, str>>,
+        nonce: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            bot_id: bot_id.into(),
+            scope,
+            public_key: public_key.into(),
+            nonce: nonce.into(),
+            callback_url: None,
+        }
+    }
+
+    /// Configures the URL the user is redirected to after sharing their data
+    /// or cancelling the request.
+    pub fn callback_url(mut self, url: impl Into<Cow<'a, str>>) -> Self {
+        self.callback_url = Some(url.into());
+        self
+    }
+
+    /// Returns the nonce of the request.
+    #[must_use]
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    /// Builds the `tg://resolve` link which opens the authorization form.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the scope could not be serialized, which should not happen.
+    #[must_use]
+    pub fn url(&self) -> String {
+        let scope = serde_json::to_string(&self.scope)
+            .expect("[tbot] Failed to serialize a passport scope");
+
+        let mut url = format!(
+            "tg://resolve?domain=telegrampassport&bot_id={}&scope={}\
+             &public_key={}&nonce={}",
+            self.bot_id,
+            encode(&scope),
+            encode(&self.public_key),
+            encode(&self.nonce),
+        );
+
+        if let Some(callback_url) = &self.callback_url {
+            url.push_str("&callback_url=");
+            url.push_str(&encode(callback_url));
+        }
+
+        // Old clients expect the nonce in `payload`.
+        url.push_str("&payload=");
+        url.push_str(&encode(&self.nonce));
+
+        url
+    }
+}
+
+/// Percent-encodes a query parameter.
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'.'
+            | b'_'
+            | b'~' => encoded.push(char::from(byte)),
+            byte => {
+                let _ = write!(encoded, "%{:02X}", byte);
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_url() {
+        let scope = Scope::new()
+            .element(Element::new(Kind::PersonalDetails).native_names(true))
+            .element(OneOf::new(vec![Kind::Passport]).selfie(true));
+
+        assert_eq!(
+            serde_json::to_string(&scope).unwrap(),
+            r#"{"data":[{"type":"personal_details","native_names":true},{"one_of":[{"type":"passport"}],"selfie":true}],"v":1}"#,
+        );
+
+        let url = Request::new(42, Scope::new(), "key\n", "a b").url();
+        assert_eq!(
+            url,
+            "tg://resolve?domain=telegrampassport&bot_id=42\
+             &scope=%7B%22data%22%3A%5B%5D%2C%22v%22%3A1%7D\
+             &public_key=key%0A&nonce=a%20b&payload=a%20b",
+        );
+    }
+}
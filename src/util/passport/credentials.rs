@@ -0,0 +1,121 @@
+use crate::{errors, types::passport::element::Kind};
+use serde::Deserialize;
+
+/// Represents decrypted [`Credentials`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#credentials
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct Credentials {
+    /// Credentials for the shared elements.
+    pub secure_data: SecureData,
+    /// The nonce the bot passed when requesting the data.
+    pub nonce: String,
+}
+
+/// Represents [`SecureData`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#securedata
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct SecureData {
+    /// Credentials for the user's personal details.
+    pub personal_details: Option<SecureValue>,
+    /// Credentials for the user's passport.
+    pub passport: Option<SecureValue>,
+    /// Credentials for the user's internal passport.
+    pub internal_passport: Option<SecureValue>,
+    /// Credentials for the user's driver license.
+    pub driver_license: Option<SecureValue>,
+    /// Credentials for the user's identity card.
+    pub identity_card: Option<SecureValue>,
+    /// Credentials for the user's address.
+    pub address: Option<SecureValue>,
+    /// Credentials for the user's utility bill.
+    pub utility_bill: Option<SecureValue>,
+    /// Credentials for the user's bank statement.
+    pub bank_statement: Option<SecureValue>,
+    /// Credentials for the user's rental agreement.
+    pub rental_agreement: Option<SecureValue>,
+    /// Credentials for the user's passport registration.
+    pub passport_registration: Option<SecureValue>,
+    /// Credentials for the user's temporary registration.
+    pub temporary_registration: Option<SecureValue>,
+}
+
+/// Represents [`SecureValue`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#securevalue
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct SecureValue {
+    /// Credentials for the element's data.
+    pub data: Option<DataCredentials>,
+    /// Credentials for the document's front side.
+    pub front_side: Option<FileCredentials>,
+    /// Credentials for the document's reverse side.
+    pub reverse_side: Option<FileCredentials>,
+    /// Credentials for the user's selfie with the document.
+    pub selfie: Option<FileCredentials>,
+    /// Credentials for the document's translation.
+    pub translation: Option<Vec<FileCredentials>>,
+    /// Credentials for the document's files.
+    pub files: Option<Vec<FileCredentials>>,
+}
+
+/// Represents [`DataCredentials`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#datacredentials
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct DataCredentials {
+    /// Base64-encoded hash of the data.
+    pub data_hash: String,
+    /// Base64-encoded secret required for decryption.
+    pub secret: String,
+}
+
+/// Represents [`FileCredentials`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#filecredentials
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct FileCredentials {
+    /// Base64-encoded hash of the file.
+    pub file_hash: String,
+    /// Base64-encoded secret required for decryption.
+    pub secret: String,
+}
+
+impl Credentials {
+    /// Checks that the credentials contain the nonce the bot generated
+    /// when requesting the data.
+    pub fn verify_nonce(&self, nonce: &str) -> Result<(), errors::Passport> {
+        if self.nonce == nonce {
+            Ok(())
+        } else {
+            Err(errors::Passport::NonceMismatch)
+        }
+    }
+}
+
+impl SecureData {
+    pub(super) const fn get(&self, kind: &Kind) -> Option<&SecureValue> {
+        let value = match kind {
+            Kind::PersonalDetails(..) => &self.personal_details,
+            Kind::Passport { .. } => &self.passport,
+            Kind::DriverLicense { .. } => &self.driver_license,
+            Kind::IdentityCard { .. } => &self.identity_card,
+            Kind::InternalPassport { .. } => &self.internal_passport,
+            Kind::Address(..) => &self.address,
+            Kind::UtilityBill { .. } => &self.utility_bill,
+            Kind::BankStatement { .. } => &self.bank_statement,
+            Kind::RentalAgreement { .. } => &self.rental_agreement,
+            Kind::PassportRegistration { .. } => &self.passport_registration,
+            Kind::TemporaryRegistration { .. } => &self.temporary_registration,
+            Kind::PhoneNumber(..) | Kind::Email(..) => &None,
+        };
+
+        value.as_ref()
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Deserialize;
+
+/// Represents [`PersonalDetails`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#personaldetails
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct PersonalDetails {
+    /// The user's first name.
+    pub first_name: String,
+    /// The user's last name.
+    pub last_name: String,
+    /// The user's middle name.
+    pub middle_name: Option<String>,
+    /// The user's date of birth in the `DD.MM.YYYY` format.
+    pub birth_date: String,
+    /// The user's gender, either `male` or `female`.
+    pub gender: String,
+    /// The ISO 3166-1 alpha-2 code of the user's citizenship.
+    pub country_code: String,
+    /// The ISO 3166-1 alpha-2 code of the user's country of residence.
+    pub residence_country_code: String,
+    /// The user's first name in the language of their country of residence.
+    pub first_name_native: Option<String>,
+    /// The user's last name in the language of their country of residence.
+    pub last_name_native: Option<String>,
+    /// The user's middle name in the language of their country of residence.
+    pub middle_name_native: Option<String>,
+}
+
+/// Represents [`IdDocumentData`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#iddocumentdata
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct IdDocumentData {
+    /// The document's number.
+    pub document_no: String,
+    /// The document's expiry date in the `DD.MM.YYYY` format.
+    pub expiry_date: Option<String>,
+}
+
+/// Represents [`ResidentialAddress`][docs].
+///
+/// [docs]: https://core.telegram.org/passport#residentialaddress
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Deserialize)]
+#[non_exhaustive]
+pub struct ResidentialAddress {
+    /// The first line of the address.
+    pub street_line1: String,
+    /// The second line of the address.
+    pub street_line2: Option<String>,
+    /// The city.
+    pub city: String,
+    /// The state.
+    pub state: Option<String>,
+    /// The ISO 3166-1 alpha-2 country code.
+    pub country_code: String,
+    /// The post code.
+    pub post_code: String,
+}
@@ -0,0 +1,601 @@
+use super::{
+    credentials::{Credentials, FileCredentials, SecureData, SecureValue},
+    element::{Documents, Element, ElementError, File, IdDocument, Value},
+};
+use crate::{
+    errors,
+    types::passport::{
+        self,
+        element::{
+            error::{
+                file, front_side, reverse_side, selfie, translation_file,
+                unspecified, FrontSide, ReverseSide, Selfie, Source,
+                TranslationFile,
+            },
+            Kind,
+        },
+    },
+};
+use aes::Aes256;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use block_modes::{block_padding::NoPadding, BlockMode, Cbc};
+use rsa::{PaddingScheme, RSAPrivateKey};
+use serde::de::DeserializeOwned;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::{
+    convert::TryFrom,
+    fmt::{self, Debug, Formatter},
+};
+
+const BLOCK_SIZE: usize = 16;
+const MIN_PADDING: usize = 32;
+
+/// Decrypts the value as described in the [Passport docs].
+///
+/// [Passport docs]: https://core.telegram.org/passport#decrypting-data
+pub(super) fn decrypt(
+    encrypted: &[u8],
+    secret: &str,
+    hash: &str,
+) -> Result<Vec<u8>, errors::Passport> {
+    decrypt_with(encrypted, &decode(secret)?, &decode(hash)?)
+}
+
+fn decode(data: &str) -> Result<Vec<u8>, errors::Passport> {
+    STANDARD
+        .decode(data)
+        .map_err(|_| errors::Passport::Malformed)
+}
+
+fn decrypt_with(
+    encrypted: &[u8],
+    secret: &[u8],
+    hash: &[u8],
+) -> Result<Vec<u8>, errors::Passport> {
+    if encrypted.is_empty() || encrypted.len() % BLOCK_SIZE != 0 {
+        return Err(errors::Passport::Malformed);
+    }
+
+    let secret_hash = Sha512::new().chain_update(secret).chain_update(hash);
+    let secret_hash = secret_hash.finalize();
+    let (key, iv) = (&secret_hash[..32], &secret_hash[32..48]);
+
+    let mut data = encrypted.to_vec();
+    Cbc::<Aes256, NoPadding>::new_var(key, iv)
+        .map_err(|_| errors::Passport::Malformed)?
+        .decrypt(&mut data)
+        .map_err(|_| errors::Passport::Malformed)?;
+
+    if Sha256::digest(&data).as_slice() != hash {
+        return Err(errors::Passport::HashMismatch);
+    }
+
+    let padding = usize::from(data[0]);
+    if padding < MIN_PADDING || padding > data.len() {
+        return Err(errors::Passport::Malformed);
+    }
+
+    data.drain(..padding);
+    Ok(data)
+}
+
+/// Decrypts Telegram Passport data with the bot's private key.
+///
+/// ```no_run
+/// use tbot::util::passport::Decryptor;
+///
+/// let key = std::fs::read_to_string("private.key").unwrap();
+/// let decryptor = Decryptor::from_pem(&key).unwrap();
+///
+/// let mut bot = tbot::from_env!("BOT_TOKEN").event_loop();
+///
+/// bot.passport(move |context| {
+///     let decrypted = decryptor.decrypt(&context.passport_data);
+///     async move {
+///         match decrypted {
+///             Ok(decrypted) => {
+///                 dbg!(decrypted.elements);
+///             }
+///             Err(err) => {
+///                 dbg!(err);
+///             }
+///         }
+///     }
+/// });
+/// ```
+#[derive(Clone)]
+#[must_use]
+pub struct Decryptor {
+    key: RSAPrivateKey,
+}
+
+/// Decrypted passport data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Decrypted {
+    /// The decrypted credentials.
+    pub credentials: Credentials,
+    /// The shared elements. Elements which could not be decrypted are
+    /// returned as errors, so that the bot can ask the user to re-upload
+    /// them with [`Bot::set_passport_data_errors`].
+    ///
+    /// [`Bot::set_passport_data_errors`]: ../../bot/struct.Bot.html#method.set_passport_data_errors
+    pub elements: Vec<Result<Element, ElementError>>,
+}
+
+impl Decryptor {
+    /// Parses the bot's private key in the PEM format, either PKCS#1
+    /// (`BEGIN RSA PRIVATE KEY`) or PKCS#8 (`BEGIN PRIVATE KEY`).
+    pub fn from_pem(pem: &str) -> Result<Self, errors::Passport> {
+        let key = rsa::pem::parse(pem)
+            .map_err(|error| error.to_string())
+            .and_then(|pem| {
+                RSAPrivateKey::try_from(pem).map_err(|error| error.to_string())
+            })
+            .map_err(errors::Passport::InvalidKey)?;
+
+        Ok(Self { key })
+    }
+
+    /// Decrypts the credentials.
+    pub fn decrypt_credentials(
+        &self,
+        credentials: &passport::Credentials,
+    ) -> Result<Credentials, errors::Passport> {
+        let secret = decode(&credentials.secret)?;
+        let secret = self
+            .key
+            .decrypt(PaddingScheme::new_oaep::<Sha1>(), &secret)
+            .map_err(|_| errors::Passport::Rsa)?;
+        let data = decrypt_with(
+            &decode(&credentials.data)?,
+            &secret,
+            &decode(&credentials.hash)?,
+        )?;
+
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Decrypts the credentials and all shared elements. Files are not
+    /// downloaded; decrypt their content with [`File::decrypt`].
+    ///
+    /// Don't forget to check that the credentials contain the nonce the bot
+    /// requested the data with, using [`Credentials::verify_nonce`].
+    ///
+    /// [`File::decrypt`]: ./struct.File.html#method.decrypt
+    /// [`Credentials::verify_nonce`]: ./struct.Credentials.html#method.verify_nonce
+    pub fn decrypt(
+        &self,
+        data: &passport::Data,
+    ) -> Result<Decrypted, errors::Passport> {
+        let credentials = self.decrypt_credentials(&data.credentials)?;
+        let elements = data
+            .data
+            .iter()
+            .map(|element| decrypt_element(element, &credentials.secure_data))
+            .collect();
+
+        Ok(Decrypted {
+            credentials,
+            elements,
+        })
+    }
+}
+
+impl Debug for Decryptor {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.debug_struct("Decryptor").finish()
+    }
+}
+
+const fn element_kind(kind: &Kind) -> unspecified::Kind {
+    match kind {
+        Kind::PersonalDetails(..) => unspecified::Kind::PersonalDetails,
+        Kind::Passport { .. } => unspecified::Kind::Passport,
+        Kind::DriverLicense { .. } => unspecified::Kind::DriverLicense,
+        Kind::IdentityCard { .. } => unspecified::Kind::IdentityCard,
+        Kind::InternalPassport { .. } => unspecified::Kind::InternalPassport,
+        Kind::Address(..) => unspecified::Kind::Address,
+        Kind::UtilityBill { .. } => unspecified::Kind::UtilityBill,
+        Kind::BankStatement { .. } => unspecified::Kind::BankStatement,
+        Kind::RentalAgreement { .. } => unspecified::Kind::RentalAgreement,
+        Kind::PassportRegistration { .. } => {
+            unspecified::Kind::PassportRegistration
+        }
+        Kind::TemporaryRegistration { .. } => {
+            unspecified::Kind::TemporaryRegistration
+        }
+        Kind::PhoneNumber(..) => unspecified::Kind::PhoneNumber,
+        Kind::Email(..) => unspecified::Kind::Email,
+    }
+}
+
+fn decrypt_element(
+    element: &passport::Element,
+    secure_data: &SecureData,
+) -> Result<Element, ElementError> {
+    let kind = element_kind(&element.kind);
+    let credentials = secure_data.get(&element.kind);
+    let data_hash = credentials
+        .and_then(|credentials| credentials.data.as_ref())
+        .map(|data| data.data_hash.clone());
+
+    match decrypt_value(&element.kind, credentials) {
+        Ok(value) => Ok(Element {
+            value,
+            hash: element.hash.clone(),
+            kind,
+            data_hash,
+        }),
+        Err(error) => Err(ElementError {
+            hash: element.hash.clone(),
+            kind,
+            error,
+        }),
+    }
+}
+
+fn decrypt_data<T: DeserializeOwned>(
+    data: &str,
+    credentials: &SecureValue,
+) -> Result<T, errors::Passport> {
+    let credentials = credentials
+        .data
+        .as_ref()
+        .ok_or(errors::Passport::MissingCredentials)?;
+    let data =
+        decrypt(&decode(data)?, &credentials.secret, &credentials.data_hash)?;
+
+    Ok(serde_json::from_slice(&data)?)
+}
+
+fn file(
+    file: &passport::File,
+    credentials: Option<&FileCredentials>,
+    source: impl FnOnce(&str) -> Source<'static>,
+) -> Result<File, errors::Passport> {
+    let credentials = credentials
+        .ok_or(errors::Passport::MissingCredentials)?
+        .clone();
+
+    Ok(File {
+        file: file.clone(),
+        source: source(&credentials.file_hash),
+        credentials,
+    })
+}
+
+fn files(
+    files: &[passport::File],
+    credentials: Option<&Vec<FileCredentials>>,
+    source: impl Fn(&str) -> Source<'static>,
+) -> Result<Vec<File>, errors::Passport> {
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let credentials = credentials
+        .filter(|credentials| credentials.len() == files.len())
+        .ok_or(errors::Passport::MissingCredentials)?;
+
+    files
+        .iter()
+        .zip(credentials)
+        .map(|(file, credentials)| self::file(file, Some(credentials), &source))
+        .collect()
+}
+
+fn translation(
+    kind: translation_file::Kind,
+    translation: &[passport::File],
+    credentials: &SecureValue,
+) -> Result<Vec<File>, errors::Passport> {
+    files(translation, credentials.translation.as_ref(), |hash| {
+        TranslationFile::new(kind, hash.to_owned()).into()
+    })
+}
+
+fn id_document(
+    kind: (front_side::Kind, selfie::Kind, translation_file::Kind),
+    data: &str,
+    front_side: &passport::File,
+    reverse_side: Option<(reverse_side::Kind, &passport::File)>,
+    selfie: &passport::File,
+    translation: &[passport::File],
+    credentials: &SecureValue,
+) -> Result<IdDocument, errors::Passport> {
+    let (front_side_kind, selfie_kind, translation_kind) = kind;
+    let reverse_side = reverse_side
+        .map(|(kind, reverse_side)| {
+            file(reverse_side, credentials.reverse_side.as_ref(), |hash| {
+                ReverseSide::new(kind, hash.to_owned()).into()
+            })
+        })
+        .transpose()?;
+
+    Ok(IdDocument {
+        data: decrypt_data(data, credentials)?,
+        front_side: file(
+            front_side,
+            credentials.front_side.as_ref(),
+            |hash| FrontSide::new(front_side_kind, hash.to_owned()).into(),
+        )?,
+        reverse_side,
+        selfie: file(selfie, credentials.selfie.as_ref(), |hash| {
+            Selfie::new(selfie_kind, hash.to_owned()).into()
+        })?,
+        translation: self::translation(
+            translation_kind,
+            translation,
+            credentials,
+        )?,
+    })
+}
+
+fn documents(
+    kind: (file::Kind, translation_file::Kind),
+    files: &[passport::File],
+    translation: &[passport::File],
+    credentials: &SecureValue,
+) -> Result<Documents, errors::Passport> {
+    let (file_kind, translation_kind) = kind;
+
+    Ok(Documents {
+        files: self::files(files, credentials.files.as_ref(), |hash| {
+            file::File::new(file_kind, hash.to_owned()).into()
+        })?,
+        translation: self::translation(
+            translation_kind,
+            translation,
+            credentials,
+        )?,
+    })
+}
+
+#[allow(clippy::too_many_lines)]
+fn decrypt_value(
+    kind: &Kind,
+    credentials: Option<&SecureValue>,
+) -> Result<Value, errors::Passport> {
+    match kind {
+        Kind::PhoneNumber(phone_number) => {
+            return Ok(Value::PhoneNumber(phone_number.clone()))
+        }
+        Kind::Email(email) => return Ok(Value::Email(email.clone())),
+        _ => (),
+    }
+
+    let credentials =
+        credentials.ok_or(errors::Passport::MissingCredentials)?;
+
+    let value = match kind {
+        Kind::PersonalDetails(data) => {
+            Value::PersonalDetails(decrypt_data(data, credentials)?)
+        }
+        Kind::Passport {
+            data,
+            front_side,
+            selfie,
+            translation,
+        } => Value::Passport(id_document(
+            (
+                front_side::Kind::Passport,
+                selfie::Kind::Passport,
+                translation_file::Kind::Passport,
+            ),
+            data,
+            front_side,
+            None,
+            selfie,
+            translation,
+            credentials,
+        )?),
+        Kind::DriverLicense {
+            data,
+            front_side,
+            reverse_side,
+            selfie,
+            translation,
+        } => Value::DriverLicense(id_document(
+            (
+                front_side::Kind::DriverLicense,
+                selfie::Kind::DriverLicense,
+                translation_file::Kind::DriverLicense,
+            ),
+            data,
+            front_side,
+            Some((reverse_side::Kind::DriverLicense, reverse_side)),
+            selfie,
+            translation,
+            credentials,
+        )?),
+        Kind::IdentityCard {
+            data,
+            front_side,
+            reverse_side,
+            selfie,
+            translation,
+        } => Value::IdentityCard(id_document(
+            (
+                front_side::Kind::IdentityCard,
+                selfie::Kind::IdentityCard,
+                translation_file::Kind::IdentityCard,
+            ),
+            data,
+            front_side,
+            Some((reverse_side::Kind::IdentityCard, reverse_side)),
+            selfie,
+            translation,
+            credentials,
+        )?),
+        Kind::InternalPassport {
+            data,
+            front_side,
+            selfie,
+            translation,
+        } => Value::InternalPassport(id_document(
+            (
+                front_side::Kind::InternalPassport,
+                selfie::Kind::InternalPassport,
+                translation_file::Kind::InternalPassport,
+            ),
+            data,
+            front_side,
+            None,
+            selfie,
+            translation,
+            credentials,
+        )?),
+        Kind::Address(data) => Value::Address(decrypt_data(data, credentials)?),
+        Kind::UtilityBill { files, translation } => {
+            Value::UtilityBill(documents(
+                (file::Kind::UtilityBill, translation_file::Kind::UtilityBill),
+                files,
+                translation,
+                credentials,
+            )?)
+        }
+        Kind::BankStatement { files, translation } => {
+            Value::BankStatement(documents(
+                (
+                    file::Kind::BankStatement,
+                    translation_file::Kind::BankStatement,
+                ),
+                files,
+                translation,
+                credentials,
+            )?)
+        }
+        Kind::RentalAgreement { files, translation } => {
+            Value::RentalAgreement(documents(
+                (
+                    file::Kind::RentalAgreement,
+                    translation_file::Kind::RentalAgreement,
+                ),
+                files,
+                translation,
+                credentials,
+            )?)
+        }
+        Kind::PassportRegistration { files, translation } => {
+            Value::PassportRegistration(documents(
+                (
+                    file::Kind::PassportRegistration,
+                    translation_file::Kind::PassportRegistration,
+                ),
+                files,
+                translation,
+                credentials,
+            )?)
+        }
+        Kind::TemporaryRegistration { files, translation } => {
+            Value::TemporaryRegistration(documents(
+                (
+                    file::Kind::TemporaryRegistration,
+                    translation_file::Kind::TemporaryRegistration,
+                ),
+                files,
+                translation,
+                credentials,
+            )?)
+        }
+        Kind::PhoneNumber(..) | Kind::Email(..) => unreachable!(),
+    };
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encrypt(data: &[u8], secret: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let padding = MIN_PADDING + (BLOCK_SIZE - data.len() % BLOCK_SIZE);
+        #[allow(clippy::cast_possible_truncation)]
+        let mut padded = vec![padding as u8; padding];
+        padded.extend_from_slice(data);
+
+        let hash = Sha256::digest(&padded).to_vec();
+        let secret_hash =
+            Sha512::new().chain_update(secret).chain_update(&hash);
+        let secret_hash = secret_hash.finalize();
+
+        let length = padded.len();
+        Cbc::<Aes256, NoPadding>::new_var(
+            &secret_hash[..32],
+            &secret_hash[32..48],
+        )
+        .unwrap()
+        .encrypt(&mut padded, length)
+        .unwrap();
+
+        (padded, hash)
+    }
+
+    #[test]
+    fn decrypts_and_verifies_data() {
+        let (encrypted, hash) = encrypt(br#"{"document_no":"42"}"#, b"secret");
+
+        let data = decrypt_with(&encrypted, b"secret", &hash).unwrap();
+        assert_eq!(data, br#"{"document_no":"42"}"#);
+
+        let wrong_secret = decrypt_with(&encrypted, b"guess", &hash);
+        assert!(wrong_secret.unwrap_err().is_hash_mismatch());
+
+        let truncated = decrypt_with(&encrypted[1..], b"secret", &hash);
+        assert!(truncated.unwrap_err().is_malformed());
+    }
+
+    #[test]
+    fn pairs_files_with_credentials() {
+        let secret = STANDARD.encode(b"secret");
+        let (data, data_hash) = encrypt(br#"{"document_no":"42"}"#, b"secret");
+        let file_credentials = |hash: &str| serde_json::json!({ "file_hash": hash, "secret": secret });
+        let passport_file = |id: &str| {
+            serde_json::from_value::<passport::File>(serde_json::json!({
+                "file_id": id,
+                "file_unique_id": id,
+                "file_size": 0,
+                "file_date": 0,
+            }))
+            .unwrap()
+        };
+
+        let credentials: SecureValue =
+            serde_json::from_value(serde_json::json!({
+                "data": {
+                    "data_hash": STANDARD.encode(&data_hash),
+                    "secret": secret,
+                },
+                "front_side": file_credentials("front"),
+                "selfie": file_credentials("selfie"),
+            }))
+            .unwrap();
+
+        let kind = Kind::Passport {
+            data: STANDARD.encode(&data),
+            front_side: passport_file("front"),
+            selfie: passport_file("selfie"),
+            translation: Vec::new(),
+        };
+
+        let document = match decrypt_value(&kind, Some(&credentials)) {
+            Ok(Value::Passport(document)) => document,
+            value => panic!("Unexpected value: {:?}", value),
+        };
+        assert_eq!(document.data.document_no, "42");
+        assert_eq!(
+            document.front_side.source,
+            FrontSide::new(front_side::Kind::Passport, "front").into(),
+        );
+
+        let kind = Kind::UtilityBill {
+            files: vec![passport_file("bill")],
+            translation: Vec::new(),
+        };
+        assert!(decrypt_value(&kind, Some(&credentials))
+            .unwrap_err()
+            .is_missing_credentials());
+    }
+}
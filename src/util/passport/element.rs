@@ -0,0 +1,190 @@
+use super::{
+    credentials::FileCredentials,
+    data::{IdDocumentData, PersonalDetails, ResidentialAddress},
+    decryption::decrypt,
+};
+use crate::{
+    errors,
+    types::passport::{
+        self,
+        element::error::{data, unspecified, Data, Error, Source, Unspecified},
+    },
+};
+use is_macro::Is;
+use std::borrow::Cow;
+
+/// A decrypted passport file.
+///
+/// Telegram stores the file encrypted, so download it as usual and decrypt
+/// its content with [`decrypt`].
+///
+/// [`decrypt`]: #method.decrypt
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct File {
+    /// The encrypted file.
+    pub file: passport::File,
+    /// The credentials required to decrypt the file.
+    pub credentials: FileCredentials,
+    pub(super) source: Source<'static>,
+}
+
+impl File {
+    /// Decrypts the downloaded content of the file.
+    pub fn decrypt(
+        &self,
+        encrypted: &[u8],
+    ) -> Result<Vec<u8>, errors::Passport> {
+        decrypt(
+            encrypted,
+            &self.credentials.secret,
+            &self.credentials.file_hash,
+        )
+    }
+
+    /// Constructs an error pointing at this file to be passed to
+    /// [`Bot::set_passport_data_errors`].
+    ///
+    /// [`Bot::set_passport_data_errors`]: ../../bot/struct.Bot.html#method.set_passport_data_errors
+    pub fn error(
+        &self,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Error<'static> {
+        Error::new(self.source.clone(), message)
+    }
+}
+
+/// A decrypted identity document.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct IdDocument {
+    /// The document's data.
+    pub data: IdDocumentData,
+    /// The document's front side.
+    pub front_side: File,
+    /// The document's reverse side, provided for driver licenses and identity
+    /// cards.
+    pub reverse_side: Option<File>,
+    /// The user's selfie with the document.
+    pub selfie: File,
+    /// Translated versions of the document.
+    pub translation: Vec<File>,
+}
+
+/// A decrypted document proving the user's address.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct Documents {
+    /// Photos of the document.
+    pub files: Vec<File>,
+    /// Translated versions of the document.
+    pub translation: Vec<File>,
+}
+
+/// Represents different kinds of decrypted [`Element`]s.
+///
+/// [`Element`]: ./struct.Element.html
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Is)]
+#[non_exhaustive]
+#[must_use]
+pub enum Value {
+    /// The user's personal details.
+    PersonalDetails(PersonalDetails),
+    /// The user's passport.
+    Passport(IdDocument),
+    /// The user's driver license.
+    DriverLicense(IdDocument),
+    /// The user's identity card.
+    IdentityCard(IdDocument),
+    /// The user's internal passport.
+    InternalPassport(IdDocument),
+    /// The user's address.
+    Address(ResidentialAddress),
+    /// The user's utility bill.
+    UtilityBill(Documents),
+    /// The user's bank statement.
+    BankStatement(Documents),
+    /// The user's rental agreement.
+    RentalAgreement(Documents),
+    /// The user's passport registration.
+    PassportRegistration(Documents),
+    /// The user's temporary registration.
+    TemporaryRegistration(Documents),
+    /// The user's phone number.
+    PhoneNumber(String),
+    /// The user's email.
+    Email(String),
+}
+
+/// A decrypted passport element.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct Element {
+    /// The decrypted value.
+    pub value: Value,
+    /// The hash of the element.
+    pub hash: String,
+    pub(super) kind: unspecified::Kind,
+    pub(super) data_hash: Option<String>,
+}
+
+impl Element {
+    /// Constructs an error concerning the whole element to be passed to
+    /// [`Bot::set_passport_data_errors`].
+    ///
+    /// [`Bot::set_passport_data_errors`]: ../../bot/struct.Bot.html#method.set_passport_data_errors
+    pub fn error(
+        &self,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Error<'static> {
+        Error::new(Unspecified::new(self.kind, self.hash.clone()), message)
+    }
+
+    /// Constructs an error concerning a field of the element's data, e.g.
+    /// `first_name`. Returns `None` if the element has no data, e.g. if it
+    /// is a utility bill.
+    #[must_use]
+    pub fn data_error(
+        &self,
+        field_name: impl Into<Cow<'static, str>>,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Option<Error<'static>> {
+        let kind = match self.kind {
+            unspecified::Kind::PersonalDetails => data::Kind::PersonalDetails,
+            unspecified::Kind::Passport => data::Kind::Passport,
+            unspecified::Kind::DriverLicense => data::Kind::DriverLicense,
+            unspecified::Kind::IdentityCard => data::Kind::IdentityCard,
+            unspecified::Kind::InternalPassport => data::Kind::InternalPassport,
+            unspecified::Kind::Address => data::Kind::Address,
+            _ => return None,
+        };
+        let data_hash = self.data_hash.clone()?;
+
+        Some(Error::new(Data::new(kind, field_name, data_hash), message))
+    }
+}
+
+/// An element which could not be decrypted.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct ElementError {
+    /// The hash of the element.
+    pub hash: String,
+    /// The kind of the element.
+    pub kind: unspecified::Kind,
+    /// The reason why decryption failed.
+    pub error: errors::Passport,
+}
+
+impl ElementError {
+    /// Constructs an error to be passed to [`Bot::set_passport_data_errors`]
+    /// so that the user re-uploads the element.
+    ///
+    /// [`Bot::set_passport_data_errors`]: ../../bot/struct.Bot.html#method.set_passport_data_errors
+    pub fn to_error(
+        &self,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Error<'static> {
+        Error::new(Unspecified::new(self.kind, self.hash.clone()), message)
+    }
+}
@@ -0,0 +1,746 @@
+//! Selling products with Telegram Payments.
+//!
+//! A sale involves several updates: the bot sends an invoice, Telegram asks
+//! for shipping options if the product needs shipping, then asks the bot
+//! to confirm the order before charging the user, and finally tells the bot
+//! that the payment succeeded. [`Payments`] wires these steps together:
+//! you describe the products and the business logic, and it answers
+//! shipping and pre-checkout queries on its own.
+//!
+//! ```no_run
+//! use tbot::{
+//!     types::{shipping, LabeledPrice},
+//!     util::payments::{Payments, Product},
+//! };
+//!
+//! let bot = tbot::from_env!("BOT_TOKEN");
+//! let payments = Payments::new(std::env::var("PROVIDER_TOKEN").unwrap())
+//!     .product(
+//!         Product::new(
+//!             "mug",
+//!             "A mug",
+//!             "A mug with the bot's logo",
+//!             "USD",
+//!             vec![LabeledPrice::new("Mug", 9_99)],
+//!         )
+//!         .needs_shipping(true),
+//!     )
+//!     .shipping(|_context, _order| async {
+//!         Ok(vec![shipping::Option::new(
+//!             "post",
+//!             "Post",
+//!             vec![LabeledPrice::new("Post", 2_00)],
+//!         )])
+//!     })
+//!     .reserve(|_context, _order| async { Ok(()) })
+//!     .on_completed(|_context, completed| async move {
+//!         println!("Sold a {} ({})", completed.product.id(), completed.payload);
+//!     });
+//!
+//! let mut event_loop = bot.event_loop();
+//! payments.register(&mut event_loop);
+//!
+//! event_loop.command("buy", move |context| {
+//!     let payments = payments.clone();
+//!     async move {
+//!         let result = payments
+//!             .send_invoice(&context.bot, context.chat.id, "mug", "order-1")
+//!             .await;
+//!
+//!         if let Err(err) = result {
+//!             dbg!(err);
+//!         }
+//!     }
+//! });
+//! ```
+//!
+//! The invoice payload is used to find the product when queries arrive,
+//! and your own payload is passed to the callbacks along with the product.
+//! Before confirming a checkout, `Payments` checks that the currency and
+//! the total amount still match the product's current prices and the chosen
+//! shipping option, so that stale invoices are rejected.
+//! Updates for invoices that weren't sent by `Payments` are ignored, so
+//! you can still handle them with your own handlers.
+//!
+//! [`Payments`]: ./struct.Payments.html
+
+use crate::{
+    contexts, errors,
+    event_loop::EventLoop,
+    state::StatefulEventLoop,
+    types::{
+        chat,
+        parameters::{Invoice, Photo},
+        shipping, user, LabeledPrice, Message, SuccessfulPayment,
+    },
+    Bot,
+};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+use tokio::time::{timeout, Instant};
+use tracing::error;
+
+const MAX_PAYLOAD_LENGTH: usize = 128;
+// Telegram waits for an answer for 10 seconds; leave some time for
+// the request itself.
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(8);
+const DEFAULT_TIMEOUT_MESSAGE: &str =
+    "The shop is busy right now, please try again later.";
+const NO_SHIPPING_MESSAGE: &str = "Sorry, we can't ship to this address.";
+const PRICE_CHANGED_MESSAGE: &str =
+    "The price has changed, please request a new invoice.";
+// How long the user may take to check out after choosing a shipping option.
+const QUOTE_LIFETIME: Duration = Duration::from_secs(60 * 60);
+
+/// A product which can be sold with [`Payments`].
+///
+/// [`Payments`]: ./struct.Payments.html
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Product {
+    id: String,
+    title: String,
+    description: String,
+    currency: String,
+    prices: Vec<LabeledPrice<'static>>,
+    photo: Option<Photo<'static>>,
+    needs_shipping: bool,
+}
+
+impl Product {
+    /// Constructs a new `Product`. `id` must be unique within the catalogue,
+    /// and `prices` are the default prices which you can override with
+    /// [`Payments::price`].
+    ///
+    /// [`Payments::price`]: ./struct.Payments.html#method.price
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        description: impl Into<String>,
+        currency: impl Into<String>,
+        prices: Vec<LabeledPrice<'static>>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            description: description.into(),
+            currency: currency.into(),
+            prices,
+            photo: None,
+            needs_shipping: false,
+        }
+    }
+
+    /// Configures a photo for the product's invoices.
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn photo(mut self, photo: Photo<'static>) -> Self {
+        self.photo = Some(photo);
+        self
+    }
+
+    /// Configures whether the product needs to be shipped. If it does,
+    /// the user is asked for their address, and the price depends on
+    /// the shipping option they choose.
+    pub const fn needs_shipping(mut self, needs_shipping: bool) -> Self {
+        self.needs_shipping = needs_shipping;
+        self
+    }
+
+    /// Returns the ID of the product.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the title of the product.
+    #[must_use]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// Returns the currency of the product.
+    #[must_use]
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// Returns the default prices of the product.
+    pub fn prices(&self) -> &[LabeledPrice<'static>] {
+        &self.prices
+    }
+}
+
+/// An order passed to the callbacks of [`Payments`].
+///
+/// [`Payments`]: ./struct.Payments.html
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Order {
+    /// The ordered product.
+    pub product: Arc<Product>,
+    /// The payload passed to [`Payments::send_invoice`].
+    ///
+    /// [`Payments::send_invoice`]: ./struct.Payments.html#method.send_invoice
+    pub payload: String,
+}
+
+/// A completed order.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Completed {
+    /// The ordered product.
+    pub product: Arc<Product>,
+    /// The payload passed to [`Payments::send_invoice`].
+    ///
+    /// [`Payments::send_invoice`]: ./struct.Payments.html#method.send_invoice
+    pub payload: String,
+    /// Information about the payment.
+    pub payment: SuccessfulPayment,
+}
+
+/// The invoice payload, which identifies the product and the chat
+/// the invoice was sent to.
+#[derive(Serialize, Deserialize)]
+struct Payload<'a> {
+    #[serde(rename = "p")]
+    product: Cow<'a, str>,
+    #[serde(rename = "c")]
+    chat_id: chat::Id,
+    #[serde(rename = "d")]
+    payload: Cow<'a, str>,
+}
+
+/// The shipping options last offered to a user, used to check the total
+/// amount on checkout.
+struct Quote {
+    invoice_payload: String,
+    options: HashMap<String, u64>,
+    expires_at: Instant,
+}
+
+impl Quote {
+    /// Checks if the quote is still valid for a checkout.
+    fn is_for(&self, context: &contexts::PreCheckout) -> bool {
+        self.invoice_payload == context.invoice_payload
+            && self.expires_at > Instant::now()
+    }
+}
+
+type Price = dyn Fn(Order, chat::Id) -> BoxFuture<'static, Vec<LabeledPrice<'static>>>
+    + Send
+    + Sync;
+type Shipping = dyn Fn(
+        Arc<contexts::Shipping>,
+        Order,
+    )
+        -> BoxFuture<'static, Result<Vec<shipping::Option<'static>>, String>>
+    + Send
+    + Sync;
+type Reserve = dyn Fn(
+        Arc<contexts::PreCheckout>,
+        Order,
+    ) -> BoxFuture<'static, Result<(), String>>
+    + Send
+    + Sync;
+type OnCompleted = dyn Fn(Arc<contexts::Payment>, Completed) -> BoxFuture<'static, ()>
+    + Send
+    + Sync;
+
+/// Sells products from a catalogue. See [the module's docs] to learn how
+/// to use it.
+///
+/// [the module's docs]: ./index.html
+#[derive(Clone)]
+#[must_use]
+pub struct Payments {
+    provider_token: Arc<str>,
+    products: Arc<HashMap<String, Arc<Product>>>,
+    price: Option<Arc<Price>>,
+    shipping: Option<Arc<Shipping>>,
+    reserve: Option<Arc<Reserve>>,
+    on_completed: Option<Arc<OnCompleted>>,
+    deadline: Duration,
+    timeout_message: Arc<str>,
+    quotes: Arc<Mutex<HashMap<user::Id, Quote>>>,
+}
+
+impl Payments {
+    /// Constructs `Payments` with the payment provider's token.
+    pub fn new(provider_token: impl Into<String>) -> Self {
+        Self {
+            provider_token: provider_token.into().into(),
+            products: Arc::new(HashMap::new()),
+            price: None,
+            shipping: None,
+            reserve: None,
+            on_completed: None,
+            deadline: DEFAULT_DEADLINE,
+            timeout_message: DEFAULT_TIMEOUT_MESSAGE.into(),
+            quotes: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Adds a product to the catalogue.
+    pub fn product(mut self, product: Product) -> Self {
+        Arc::make_mut(&mut self.products)
+            .insert(product.id.clone(), Arc::new(product));
+        self
+    }
+
+    /// Configures a callback which calculates prices when an invoice is sent,
+    /// e.g. to apply a discount. By default, the product's prices are used.
+    ///
+    /// The callback is called again on checkout to check that the price
+    /// hasn't changed since the invoice was sent.
+    pub fn price<H, F>(mut self, price: H) -> Self
+    where
+        H: Fn(Order, chat::Id) -> F + Send + Sync + 'static,
+        F: Future<Output = Vec<LabeledPrice<'static>>> + Send + 'static,
+    {
+        self.price = Some(Arc::new(move |order, chat_id| {
+            Box::pin(price(order, chat_id))
+        }));
+        self
+    }
+
+    /// Configures a callback which returns shipping options for the user's
+    /// address, or an error message if the product can't be shipped there.
+    pub fn shipping<H, F>(mut self, shipping: H) -> Self
+    where
+        H: Fn(Arc<contexts::Shipping>, Order) -> F + Send + Sync + 'static,
+        F: Future<Output = Result<Vec<shipping::Option<'static>>, String>>
+            + Send
+            + 'static,
+    {
+        self.shipping = Some(Arc::new(move |context, order| {
+            Box::pin(shipping(context, order))
+        }));
+        self
+    }
+
+    /// Configures a callback which reserves stock before the user is charged.
+    /// If it fails, the error message is shown to the user and the payment
+    /// is cancelled. By default, all orders are confirmed.
+    pub fn reserve<H, F>(mut self, reserve: H) -> Self
+    where
+        H: Fn(Arc<contexts::PreCheckout>, Order) -> F + Send + Sync + 'static,
+        F: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.reserve = Some(Arc::new(move |context, order| {
+            Box::pin(reserve(context, order))
+        }));
+        self
+    }
+
+    /// Configures a callback which is called once the user has paid.
+    pub fn on_completed<H, F>(mut self, on_completed: H) -> Self
+    where
+        H: Fn(Arc<contexts::Payment>, Completed) -> F + Send + Sync + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.on_completed = Some(Arc::new(move |context, completed| {
+            Box::pin(on_completed(context, completed))
+        }));
+        self
+    }
+
+    /// Configures how long the shipping and reservation callbacks may run.
+    /// Telegram cancels the payment if the bot doesn't answer in 10 seconds,
+    /// so if a callback takes longer, the query is answered with an error.
+    /// By default, callbacks may run for 8 seconds.
+    pub const fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Configures the error message shown if a callback misses the deadline.
+    pub fn timeout_message(mut self, message: impl Into<String>) -> Self {
+        self.timeout_message = message.into().into();
+        self
+    }
+
+    /// Returns the product with the ID.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Arc<Product>> {
+        self.products.get(id)
+    }
+
+    /// Sends an invoice for the product to the chat. `payload` is passed
+    /// to the callbacks, e.g. to identify the order in your database.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the invoice payload could not be serialized, which should
+    /// not happen.
+    pub async fn send_invoice(
+        &self,
+        bot: &Bot,
+        chat_id: impl Into<chat::Id>,
+        product_id: &str,
+        payload: impl Into<String>,
+    ) -> Result<Message, errors::Payments> {
+        let chat_id = chat_id.into();
+        let product = self.get(product_id).ok_or_else(|| {
+            errors::Payments::UnknownProduct(product_id.to_owned())
+        })?;
+        let payload = payload.into();
+
+        let encoded = serde_json::to_string(&Payload {
+            product: product.id.as_str().into(),
+            chat_id,
+            payload: payload.as_str().into(),
+        })
+        .expect("[tbot] Failed to serialize an invoice payload");
+
+        if encoded.len() > MAX_PAYLOAD_LENGTH {
+            return Err(errors::Payments::PayloadTooLong {
+                length: encoded.len(),
+            });
+        }
+
+        let order = Order {
+            product: Arc::clone(product),
+            payload,
+        };
+        let prices = self.prices(order, chat_id).await;
+
+        let mut invoice = Invoice::new(
+            product.title.as_str(),
+            product.description.as_str(),
+            encoded,
+            product.currency.as_str(),
+            prices,
+        )
+        .provider_token(&*self.provider_token)
+        .is_shipping_address_needed(product.needs_shipping)
+        .is_flexible(product.needs_shipping);
+
+        if let Some(photo) = &product.photo {
+            invoice = invoice.photo(photo.clone());
+        }
+
+        let invoice = bot
+            .send_invoice_with(chat_id, invoice)
+            .start_parameter(product.id.as_str());
+
+        Ok(invoice.call().await?)
+    }
+
+    async fn prices(
+        &self,
+        order: Order,
+        chat_id: chat::Id,
+    ) -> Vec<LabeledPrice<'static>> {
+        match &self.price {
+            Some(price) => price(order, chat_id).await,
+            None => order.product.prices.clone(),
+        }
+    }
+
+    fn order(&self, payload: &str) -> Option<(Order, chat::Id)> {
+        let payload: Payload = serde_json::from_str(payload).ok()?;
+        let product = self.products.get(&*payload.product)?;
+        let order = Order {
+            product: Arc::clone(product),
+            payload: payload.payload.into_owned(),
+        };
+
+        Some((order, payload.chat_id))
+    }
+
+    fn quotes(&self) -> MutexGuard<'_, HashMap<user::Id, Quote>> {
+        self.quotes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Saves a quote, dropping expired ones so that users who never check
+    /// out don't pile up.
+    fn save_quote(&self, user_id: user::Id, quote: Quote) {
+        let mut quotes = self.quotes();
+        let now = Instant::now();
+        quotes.retain(|_, quote| quote.expires_at > now);
+        quotes.insert(user_id, quote);
+    }
+
+    /// Removes the quote once the user checked out with it.
+    fn forget_quote(&self, context: &contexts::PreCheckout) {
+        let mut quotes = self.quotes();
+        if quotes
+            .get(&context.from.id)
+            .map_or(false, |quote| quote.is_for(context))
+        {
+            quotes.remove(&context.from.id);
+        }
+    }
+
+    async fn handle_shipping(&self, context: Arc<contexts::Shipping>) {
+        let order = match self.order(&context.invoice_payload) {
+            Some((order, _)) => order,
+            None => return,
+        };
+
+        let result = match &self.shipping {
+            Some(shipping) => {
+                timeout(self.deadline, shipping(Arc::clone(&context), order))
+                    .await
+                    .unwrap_or_else(|_| Err(self.timeout_message.to_string()))
+            }
+            None => Err(NO_SHIPPING_MESSAGE.to_owned()),
+        };
+
+        let call_result = match result {
+            Ok(options) => {
+                let quote = Quote {
+                    invoice_payload: context.invoice_payload.clone(),
+                    options: options
+                        .iter()
+                        .map(|option| {
+                            (option.id().to_owned(), total(option.prices()))
+                        })
+                        .collect(),
+                    expires_at: Instant::now() + QUOTE_LIFETIME,
+                };
+                self.save_quote(context.from.id, quote);
+
+                let options: Vec<_> =
+                    options.iter().map(shipping::Option::reborrow).collect();
+                context.ok(options).call().await
+            }
+            Err(message) => context.err(message).call().await,
+        };
+
+        if let Err(error) = call_result {
+            error!(?error, "failed to answer a shipping query");
+        }
+    }
+
+    async fn handle_pre_checkout(&self, context: Arc<contexts::PreCheckout>) {
+        let (order, chat_id) = match self.order(&context.invoice_payload) {
+            Some(order) => order,
+            None => return,
+        };
+
+        let result =
+            timeout(self.deadline, self.confirm(&context, order, chat_id))
+                .await
+                .unwrap_or_else(|_| Err(self.timeout_message.to_string()));
+
+        let call_result = match result {
+            Ok(()) => context.ok().call().await,
+            Err(message) => context.err(message).call().await,
+        };
+
+        if let Err(error) = call_result {
+            error!(?error, "failed to answer a pre-checkout query");
+        }
+    }
+
+    async fn confirm(
+        &self,
+        context: &Arc<contexts::PreCheckout>,
+        order: Order,
+        chat_id: chat::Id,
+    ) -> Result<(), String> {
+        let shipping = match &context.shipping_option_id {
+            Some(id) => self
+                .quotes()
+                .get(&context.from.id)
+                .filter(|quote| quote.is_for(context))
+                .and_then(|quote| quote.options.get(id).copied())
+                .ok_or_else(|| PRICE_CHANGED_MESSAGE.to_owned())?,
+            None => 0,
+        };
+
+        let prices = self.prices(order.clone(), chat_id).await;
+        let expected = total(&prices) + shipping;
+        if context.currency != order.product.currency
+            || u64::from(context.total_amount) != expected
+        {
+            return Err(PRICE_CHANGED_MESSAGE.to_owned());
+        }
+
+        if let Some(reserve) = &self.reserve {
+            reserve(Arc::clone(context), order).await?;
+        }
+
+        self.forget_quote(context);
+        Ok(())
+    }
+
+    async fn handle_payment(&self, context: Arc<contexts::Payment>) {
+        let order = match self.order(&context.invoice.invoice_payload) {
+            Some((order, _)) => order,
+            None => return,
+        };
+
+        if let Some(on_completed) = &self.on_completed {
+            let completed = Completed {
+                product: order.product,
+                payload: order.payload,
+                payment: context.invoice.clone(),
+            };
+            on_completed(context, completed).await;
+        }
+    }
+
+    /// Adds handlers for shipping queries, pre-checkout queries and payments
+    /// to the event loop.
+    pub fn register(&self, event_loop: &mut EventLoop) {
+        let payments = self.clone();
+
+        let shipping = payments.clone();
+        event_loop.shipping(move |context| {
+            let payments = shipping.clone();
+            async move { payments.handle_shipping(context).await }
+        });
+
+        let pre_checkout = payments.clone();
+        event_loop.pre_checkout(move |context| {
+            let payments = pre_checkout.clone();
+            async move { payments.handle_pre_checkout(context).await }
+        });
+
+        event_loop.payment(move |context| {
+            let payments = payments.clone();
+            async move { payments.handle_payment(context).await }
+        });
+    }
+
+    /// Adds handlers for shipping queries, pre-checkout queries and payments
+    /// to the stateful event loop.
+    pub fn register_stateful<S>(&self, event_loop: &mut StatefulEventLoop<S>)
+    where
+        S: Send + Sync + 'static,
+    {
+        let payments = self.clone();
+
+        let shipping = payments.clone();
+        event_loop.shipping(move |context, _| {
+            let payments = shipping.clone();
+            async move { payments.handle_shipping(context).await }
+        });
+
+        let pre_checkout = payments.clone();
+        event_loop.pre_checkout(move |context, _| {
+            let payments = pre_checkout.clone();
+            async move { payments.handle_pre_checkout(context).await }
+        });
+
+        event_loop.payment(move |context, _| {
+            let payments = payments.clone();
+            async move { payments.handle_payment(context).await }
+        });
+    }
+}
+
+fn total(prices: &[LabeledPrice<'_>]) -> u64 {
+    prices.iter().map(|price| u64::from(price.amount())).sum()
+}
+
+impl Debug for Payments {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Payments")
+            .field("products", &self.products)
+            .field("deadline", &self.deadline)
+            .field("timeout_message", &self.timeout_message)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_orders_by_payload() {
+        let payments = Payments::new("token").product(Product::new(
+            "mug",
+            "Mug",
+            "A mug",
+            "USD",
+            vec![LabeledPrice::new("Mug", 999)],
+        ));
+
+        let payload = serde_json::to_string(&Payload {
+            product: "mug".into(),
+            chat_id: chat::Id(42),
+            payload: "order-1".into(),
+        })
+        .unwrap();
+        let (order, chat_id) = payments.order(&payload).unwrap();
+        assert_eq!(order.product.id(), "mug");
+        assert_eq!(order.payload, "order-1");
+        assert_eq!(chat_id, chat::Id(42));
+
+        assert!(payments.order(r#"{"p":"cup","c":1,"d":""}"#).is_none());
+        assert!(payments.order("someone else's payload").is_none());
+    }
+
+    #[tokio::test]
+    async fn checks_the_total_on_checkout() {
+        let payments = Payments::new("token").product(
+            Product::new(
+                "mug",
+                "Mug",
+                "A mug",
+                "USD",
+                vec![LabeledPrice::new("Mug", 999)],
+            )
+            .needs_shipping(true),
+        );
+        let payload = serde_json::to_string(&Payload {
+            product: "mug".into(),
+            chat_id: chat::Id(1),
+            payload: "order-1".into(),
+        })
+        .unwrap();
+
+        let confirm = |currency: &str, amount: u32, shipping: Option<&str>| {
+            let query = serde_json::from_value(serde_json::json!({
+                "id": "query",
+                "from": { "id": 1, "is_bot": false, "first_name": "Alice" },
+                "currency": currency,
+                "total_amount": amount,
+                "invoice_payload": payload,
+                "shipping_option_id": shipping,
+            }))
+            .unwrap();
+            let context = Arc::new(contexts::PreCheckout::new(
+                Bot::new(String::new()),
+                query,
+            ));
+            let (order, chat_id) = payments.order(&payload).unwrap();
+            let payments = payments.clone();
+
+            async move { payments.confirm(&context, order, chat_id).await }
+        };
+
+        assert!(confirm("USD", 999, None).await.is_ok());
+        assert!(confirm("EUR", 999, None).await.is_err());
+        assert!(confirm("USD", 1, None).await.is_err());
+        assert!(confirm("USD", 1199, Some("post")).await.is_err());
+
+        let quote = || Quote {
+            invoice_payload: payload.clone(),
+            options: vec![("post".to_owned(), 200)].into_iter().collect(),
+            expires_at: Instant::now() + QUOTE_LIFETIME,
+        };
+        payments.quotes().insert(user::Id(1), quote());
+        assert!(confirm("USD", 999, Some("post")).await.is_err());
+        assert!(confirm("USD", 1199, Some("post")).await.is_ok());
+        assert!(confirm("USD", 1199, Some("post")).await.is_err());
+
+        tokio::time::pause();
+        payments.quotes().insert(user::Id(1), quote());
+        tokio::time::advance(QUOTE_LIFETIME).await;
+        assert!(confirm("USD", 1199, Some("post")).await.is_err());
+    }
+}
@@ -0,0 +1,671 @@
+//! Declarative management of sticker sets.
+//!
+//! Instead of adding, moving and deleting stickers one by one, describe
+//! the set you want with a [`Manifest`] and let [`SyncStickerSet`] make
+//! the set match it:
+//!
+//! ```no_run
+//! # async fn foo() -> Result<(), tbot::errors::StickerSet> {
+//! use tbot::{
+//!     types::{input_file::PngSticker, user},
+//!     util::sticker_set::{Manifest, Sticker, SyncStickerSet},
+//! };
+//!
+//! let bot = tbot::from_env!("BOT_TOKEN");
+//! let owner = user::Id(0);
+//!
+//! let manifest = Manifest::new()
+//!     .sticker(Sticker::new(
+//!         "ferris",
+//!         PngSticker::with_bytes(std::fs::read("ferris.png").unwrap()),
+//!         "🦀",
+//!     ))
+//!     .sticker(Sticker::new(
+//!         "corro",
+//!         PngSticker::with_bytes(std::fs::read("corro.png").unwrap()),
+//!         "🦎",
+//!     ));
+//!
+//! let report = SyncStickerSet::new(&bot, owner, "rust", "Rust", manifest)
+//!     .on_progress(|progress| println!("{:?}", progress))
+//!     .call()
+//!     .await?;
+//!
+//! // Save these IDs and pass them to `Sticker::file_unique_id`
+//! // on the next sync.
+//! dbg!(report.file_unique_ids);
+//! # Ok(()) }
+//! ```
+//!
+//! Telegram doesn't let bots attach their own data to stickers, so stickers
+//! are matched by the unique file IDs from the previous sync's [`Report`].
+//! Stickers without a known ID are added, and stickers in the set which
+//! the manifest doesn't mention are deleted. Stickers are deleted only after
+//! the new ones are added and moved, so an interrupted sync never leaves
+//! the set with fewer stickers. To replace a sticker's image, don't set its
+//! unique file ID. Flood waits are awaited transparently.
+//!
+//! [`Manifest`]: ./struct.Manifest.html
+//! [`SyncStickerSet`]: ./struct.SyncStickerSet.html
+//! [`Report`]: ./struct.Report.html
+
+use crate::{
+    errors,
+    types::{
+        input_file::{InputFile, PngSticker, StickerForStickerSet},
+        sticker::{self, MaskPosition},
+        user,
+    },
+    Bot,
+};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    fmt::{self, Debug, Formatter},
+    future::Future,
+    time::Duration,
+};
+use tokio::time::sleep;
+
+/// Generates the name of a sticker set owned by the bot. Telegram requires
+/// such names to end with `_by_<bot username>`, which is appended unless
+/// the name already ends with it.
+#[must_use]
+pub fn set_name(name: &str, username: &str) -> String {
+    let suffix = format!("_by_{}", username);
+    if name.to_lowercase().ends_with(&suffix.to_lowercase()) {
+        name.to_owned()
+    } else {
+        format!("{}{}", name, suffix)
+    }
+}
+
+/// Represents a sticker in a [`Manifest`].
+///
+/// [`Manifest`]: ./struct.Manifest.html
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct Sticker<'a> {
+    key: String,
+    file: StickerForStickerSet<'a>,
+    emojis: Cow<'a, str>,
+    mask_position: Option<MaskPosition>,
+    file_unique_id: Option<String>,
+}
+
+impl<'a> Sticker<'a> {
+    /// Constructs a new `Sticker`. The key identifies the sticker in
+    /// the manifest and in the [`Report`].
+    ///
+    /// [`Report`]: ./struct.Report.html
+    pub fn new(
+        key: impl Into<String>,
+        file: impl Into<StickerForStickerSet<'a>>,
+        emojis: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        Self {
+            key: key.into(),
+            file: file.into(),
+            emojis: emojis.into(),
+            mask_position: None,
+            file_unique_id: None,
+        }
+    }
+
+    /// Configures the mask position of the sticker.
+    pub const fn mask_position(mut self, mask_position: MaskPosition) -> Self {
+        self.mask_position = Some(mask_position);
+        self
+    }
+
+    /// Configures the unique file ID the sticker got in a previous sync.
+    /// If the set contains a sticker with this ID, the sticker isn't
+    /// uploaded again.
+    pub fn file_unique_id(mut self, file_unique_id: impl Into<String>) -> Self {
+        self.file_unique_id = Some(file_unique_id.into());
+        self
+    }
+}
+
+/// Describes the desired state of a sticker set. Stickers are kept in
+/// the order they're added to the manifest.
+#[derive(Debug, Clone, Default)]
+#[must_use]
+pub struct Manifest<'a> {
+    stickers: Vec<Sticker<'a>>,
+}
+
+impl<'a> Manifest<'a> {
+    /// Constructs an empty manifest.
+    pub const fn new() -> Self {
+        Self {
+            stickers: Vec::new(),
+        }
+    }
+
+    /// Adds a sticker to the end of the manifest.
+    pub fn sticker(mut self, sticker: Sticker<'a>) -> Self {
+        self.stickers.push(sticker);
+        self
+    }
+}
+
+/// Represents a step of a sync, reported as soon as it's done.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum Progress {
+    /// The set didn't exist and was created with the sticker.
+    Created {
+        /// The key of the sticker.
+        key: String,
+    },
+    /// The sticker's file was uploaded.
+    Uploaded {
+        /// The key of the sticker.
+        key: String,
+    },
+    /// The sticker was added to the set.
+    Added {
+        /// The key of the sticker.
+        key: String,
+    },
+    /// The sticker was moved.
+    Moved {
+        /// The key of the sticker.
+        key: String,
+        /// The new position of the sticker.
+        position: u32,
+    },
+    /// A sticker which isn't in the manifest was deleted.
+    Removed {
+        /// The unique file ID of the sticker.
+        file_unique_id: String,
+    },
+    /// Telegram asked to wait before making more requests.
+    FloodWait(Duration),
+}
+
+/// Describes what a sync has done.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct Report {
+    /// The full name of the set, including the `_by_<bot username>` suffix.
+    pub name: String,
+    /// `true` if the set didn't exist and was created.
+    pub is_created: bool,
+    /// The unique file IDs of the stickers in the set by their keys.
+    pub file_unique_ids: HashMap<String, String>,
+    /// The keys of the added stickers.
+    pub added: Vec<String>,
+    /// The keys of the moved stickers.
+    pub moved: Vec<String>,
+    /// The unique file IDs of the deleted stickers.
+    pub removed: Vec<String>,
+}
+
+type OnProgress<'a> = dyn Fn(Progress) + Send + Sync + 'a;
+
+/// Makes a sticker set match a [`Manifest`]. See [the module's docs]
+/// to learn how to use it.
+///
+/// [`Manifest`]: ./struct.Manifest.html
+/// [the module's docs]: ./index.html
+#[must_use = "the sync does nothing unless turned into a future"]
+pub struct SyncStickerSet<'a> {
+    bot: &'a Bot,
+    owner: user::Id,
+    name: Cow<'a, str>,
+    title: Cow<'a, str>,
+    manifest: Manifest<'a>,
+    on_progress: Option<Box<OnProgress<'a>>>,
+}
+
+impl<'a> SyncStickerSet<'a> {
+    /// Constructs a new `SyncStickerSet`. The `_by_<bot username>` suffix
+    /// is appended to `name` if needed, and `title` is only used if the set
+    /// needs to be created.
+    pub fn new(
+        bot: &'a Bot,
+        owner: user::Id,
+        name: impl Into<Cow<'a, str>>,
+        title: impl Into<Cow<'a, str>>,
+        manifest: Manifest<'a>,
+    ) -> Self {
+        Self {
+            bot,
+            owner,
+            name: name.into(),
+            title: title.into(),
+            manifest,
+            on_progress: None,
+        }
+    }
+
+    /// Configures a callback which is called after each step of the sync.
+    pub fn on_progress(
+        mut self,
+        on_progress: impl Fn(Progress) + Send + Sync + 'a,
+    ) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    fn report(&self, progress: Progress) {
+        if let Some(on_progress) = &self.on_progress {
+            on_progress(progress);
+        }
+    }
+
+    /// Calls the method, waiting for flood waits, until it succeeds
+    /// or fails with another error.
+    async fn retry<T, F, Fut>(&self, call: F) -> Result<T, errors::MethodCall>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, errors::MethodCall>>,
+    {
+        loop {
+            match call().await {
+                Err(errors::MethodCall::RequestError {
+                    retry_after: Some(seconds),
+                    ..
+                }) => {
+                    let delay = Duration::from_secs(seconds);
+                    self.report(Progress::FloodWait(delay));
+                    sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn fetch(
+        &self,
+        name: &str,
+    ) -> Result<Option<sticker::Set>, errors::MethodCall> {
+        match self.retry(|| self.bot.get_sticker_set(name).call()).await {
+            Ok(set) => Ok(Some(set)),
+            Err(errors::MethodCall::RequestError { description, .. })
+                if description.contains("STICKERSET_INVALID") =>
+            {
+                Ok(None)
+            }
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Uploads PNG stickers with `uploadStickerFile` first, so that
+    /// retrying after a flood wait doesn't upload them again.
+    async fn upload(
+        &self,
+        sticker: &Sticker<'a>,
+    ) -> Result<StickerForStickerSet<'a>, errors::MethodCall> {
+        let bytes = match &sticker.file {
+            StickerForStickerSet::Png(PngSticker {
+                media: InputFile::File { bytes, .. },
+            }) => bytes,
+            file => return Ok(file.clone()),
+        };
+
+        let file = self
+            .retry(|| {
+                self.bot
+                    .upload_sticker_file(self.owner, bytes.as_ref())
+                    .call()
+            })
+            .await?;
+        self.report(Progress::Uploaded {
+            key: sticker.key.clone(),
+        });
+
+        Ok(PngSticker::with_id(file.id).into())
+    }
+
+    async fn create(
+        &self,
+        name: &str,
+        sticker: &Sticker<'a>,
+    ) -> Result<(), errors::MethodCall> {
+        let file = self.upload(sticker).await?;
+        let contains_masks = self
+            .manifest
+            .stickers
+            .iter()
+            .any(|sticker| sticker.mask_position.is_some());
+
+        self.retry(|| {
+            let mut method = self
+                .bot
+                .create_new_sticker_set(
+                    self.owner,
+                    name,
+                    self.title.as_ref(),
+                    file.clone(),
+                    sticker.emojis.as_ref(),
+                )
+                .contains_masks(contains_masks);
+            if let Some(mask_position) = sticker.mask_position {
+                method = method.mask_position(mask_position);
+            }
+            method.call()
+        })
+        .await?;
+
+        self.report(Progress::Created {
+            key: sticker.key.clone(),
+        });
+        Ok(())
+    }
+
+    async fn add(
+        &self,
+        name: &str,
+        sticker: &Sticker<'a>,
+    ) -> Result<(), errors::MethodCall> {
+        let file = self.upload(sticker).await?;
+
+        self.retry(|| {
+            let mut method = self.bot.add_sticker_to_set(
+                self.owner,
+                name,
+                file.clone(),
+                sticker.emojis.as_ref(),
+            );
+            if let Some(mask_position) = sticker.mask_position {
+                method = method.mask_position(mask_position);
+            }
+            method.call()
+        })
+        .await?;
+
+        self.report(Progress::Added {
+            key: sticker.key.clone(),
+        });
+        Ok(())
+    }
+
+    async fn remove(
+        &self,
+        file_unique_id: &str,
+        file_id: &str,
+    ) -> Result<(), errors::MethodCall> {
+        self.retry(|| self.bot.delete_sticker_from_set(file_id).call())
+            .await?;
+
+        self.report(Progress::Removed {
+            file_unique_id: file_unique_id.to_owned(),
+        });
+        Ok(())
+    }
+
+    /// Records the unique file IDs of the stickers and moves them to
+    /// the positions from the manifest.
+    async fn reorder(
+        &self,
+        report: &mut Report,
+        stickers: &[sticker::Sticker],
+    ) -> Result<(), errors::MethodCall> {
+        let current: Vec<&str> = stickers
+            .iter()
+            .map(|sticker| sticker.file_unique_id.as_str())
+            .collect();
+        report.file_unique_ids =
+            file_unique_ids(&self.manifest.stickers, &report.added, &current);
+
+        let wanted: Vec<_> = self
+            .manifest
+            .stickers
+            .iter()
+            .map(|sticker| {
+                report.file_unique_ids.get(&sticker.key).map(String::as_str)
+            })
+            .collect();
+
+        for (index, sticker, position) in moves(&wanted, &current) {
+            let file_id = stickers[sticker].file_id.0.as_ref();
+            let position = u32::try_from(position).unwrap_or(u32::MAX);
+            self.retry(|| {
+                self.bot
+                    .set_sticker_position_in_set(file_id, position)
+                    .call()
+            })
+            .await?;
+
+            let key = &self.manifest.stickers[index].key;
+            self.report(Progress::Moved {
+                key: key.clone(),
+                position,
+            });
+            report.moved.push(key.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Synchronizes the set, returning what has been done.
+    pub async fn call(self) -> Result<Report, errors::StickerSet> {
+        let mut keys = HashSet::new();
+        if let Some(sticker) = self
+            .manifest
+            .stickers
+            .iter()
+            .find(|sticker| !keys.insert(sticker.key.as_str()))
+        {
+            return Err(errors::StickerSet::DuplicateKey(sticker.key.clone()));
+        }
+
+        let me = self.retry(|| self.bot.get_me().call()).await?;
+        let username =
+            me.user.username.ok_or(errors::StickerSet::NoUsername)?;
+        let name = set_name(&self.name, &username);
+
+        let mut report = Report {
+            name: name.clone(),
+            ..Report::default()
+        };
+
+        let set = self.fetch(&name).await?;
+        let remote: HashSet<&str> = set
+            .iter()
+            .flat_map(|set| {
+                set.stickers
+                    .iter()
+                    .map(|sticker| sticker.file_unique_id.as_str())
+            })
+            .collect();
+
+        let mut new =
+            new_stickers(&self.manifest.stickers, &remote).into_iter();
+        if set.is_none() {
+            let first = new.next().ok_or(errors::StickerSet::EmptyManifest)?;
+            self.create(&name, first).await?;
+            report.is_created = true;
+            report.added.push(first.key.clone());
+        }
+
+        for sticker in new {
+            self.add(&name, sticker).await?;
+            report.added.push(sticker.key.clone());
+        }
+
+        let stickers = self
+            .fetch(&name)
+            .await?
+            .map(|set| set.stickers)
+            .unwrap_or_default();
+        self.reorder(&mut report, &stickers).await?;
+
+        for sticker in &stickers {
+            let id = sticker.file_unique_id.as_str();
+            if !report.file_unique_ids.values().any(|wanted| wanted == id) {
+                self.remove(id, &sticker.file_id.0).await?;
+                report.removed.push(id.to_owned());
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Returns the stickers from the manifest which aren't in the set yet.
+fn new_stickers<'m, 'a>(
+    manifest: &'m [Sticker<'a>],
+    remote: &HashSet<&str>,
+) -> Vec<&'m Sticker<'a>> {
+    manifest
+        .iter()
+        .filter(|sticker| {
+            sticker
+                .file_unique_id
+                .as_deref()
+                .map_or(true, |id| !remote.contains(id))
+        })
+        .collect()
+}
+
+/// Maps the keys of the stickers in the set to their unique file IDs.
+/// Added stickers are appended to the set in the order they were added.
+fn file_unique_ids(
+    manifest: &[Sticker<'_>],
+    added: &[String],
+    current: &[&str],
+) -> HashMap<String, String> {
+    let appended = &current[current.len().saturating_sub(added.len())..];
+    let mut ids: HashMap<_, _> = added
+        .iter()
+        .cloned()
+        .zip(appended.iter().map(|&id| id.to_owned()))
+        .collect();
+
+    for sticker in manifest {
+        if let Some(id) = sticker
+            .file_unique_id
+            .as_ref()
+            .filter(|id| current.contains(&id.as_str()))
+        {
+            ids.insert(sticker.key.clone(), id.clone());
+        }
+    }
+
+    ids
+}
+
+/// Computes the moves which put the stickers in the order of `wanted`,
+/// as the indices in `wanted`, the indices in `current` and the new
+/// positions. Stickers which aren't wanted end up after the wanted ones.
+fn moves(
+    wanted: &[Option<&str>],
+    current: &[&str],
+) -> Vec<(usize, usize, usize)> {
+    let mut order: Vec<usize> = (0..current.len()).collect();
+    let mut moves = Vec::new();
+    let mut position = 0;
+
+    for (index, wanted) in wanted.iter().enumerate() {
+        let wanted = match wanted {
+            Some(wanted) => wanted,
+            None => continue,
+        };
+        let from = match order.iter().position(|&id| current[id] == *wanted) {
+            Some(from) => from,
+            None => continue,
+        };
+
+        if from != position {
+            let sticker = order.remove(from);
+            order.insert(position, sticker);
+            moves.push((index, sticker, position));
+        }
+        position += 1;
+    }
+
+    moves
+}
+
+impl Debug for SyncStickerSet<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("SyncStickerSet")
+            .field("owner", &self.owner)
+            .field("name", &self.name)
+            .field("title", &self.title)
+            .field("manifest", &self.manifest)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::file;
+
+    fn sticker(key: &str, file_unique_id: Option<&str>) -> Sticker<'static> {
+        let sticker = Sticker::new(
+            key,
+            PngSticker::with_id(file::Id(key.to_owned().into())),
+            "🦀",
+        );
+        match file_unique_id {
+            Some(id) => sticker.file_unique_id(id),
+            None => sticker,
+        }
+    }
+
+    #[test]
+    fn adds_unknown_stickers() {
+        let manifest = [
+            sticker("kept", Some("a")),
+            sticker("gone", Some("z")),
+            sticker("new", None),
+        ];
+        let remote = ["a", "b"].iter().copied().collect();
+
+        let keys: Vec<_> = new_stickers(&manifest, &remote)
+            .into_iter()
+            .map(|sticker| sticker.key.as_str())
+            .collect();
+        assert_eq!(keys, ["gone", "new"]);
+    }
+
+    #[test]
+    fn maps_keys_to_file_unique_ids() {
+        let manifest = [
+            sticker("kept", Some("a")),
+            sticker("first", None),
+            sticker("second", Some("z")),
+        ];
+        let added = ["first".to_owned(), "second".to_owned()];
+
+        let ids = file_unique_ids(&manifest, &added, &["a", "b", "c", "d"]);
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids["kept"], "a");
+        assert_eq!(ids["first"], "c");
+        assert_eq!(ids["second"], "d");
+    }
+
+    #[test]
+    fn moves_unwanted_stickers_to_the_end() {
+        let current = ["unwanted", "b", "c", "a"];
+        let wanted = [Some("a"), Some("b"), None, Some("c")];
+
+        let moves = moves(&wanted, &current);
+        assert_eq!(moves, [(0, 3, 0), (1, 1, 1), (3, 2, 2)]);
+
+        let mut order = current.to_vec();
+        for (_, sticker, position) in moves {
+            let from = order.iter().position(|&id| id == current[sticker]);
+            let id = order.remove(from.unwrap());
+            order.insert(position, id);
+        }
+        assert_eq!(order, ["a", "b", "c", "unwanted"]);
+    }
+
+    #[test]
+    fn appends_suffix_once() {
+        assert_eq!(set_name("rust", "tbot_bot"), "rust_by_tbot_bot");
+        assert_eq!(
+            set_name("rust_by_Tbot_bot", "tbot_bot"),
+            "rust_by_Tbot_bot"
+        );
+    }
+}